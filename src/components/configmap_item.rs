@@ -1,5 +1,10 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::core::v1::ConfigMap;
+use kube::api::ApiResource;
+
+use crate::components::{DeleteDialog, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::CONFIGMAPS;
 
 #[derive(Clone)]
 struct ConfigMapData {
@@ -20,6 +25,8 @@ pub struct ConfigMapItemProps {
 #[component]
 pub fn ConfigMapItem(props: ConfigMapItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
 
     let configmap_data = ConfigMapData {
         name: props.configmap.metadata.name.clone().unwrap_or_default(),
@@ -41,6 +48,9 @@ pub fn ConfigMapItem(props: ConfigMapItemProps) -> Element {
 
     let data_keys_count = configmap_data.data.len() + configmap_data.binary_data_keys.len();
 
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", CONFIGMAPS, Some(&configmap_data.namespace));
+
     rsx! {
         div {
             key: "{configmap_data.namespace}-{configmap_data.name}",
@@ -56,6 +66,25 @@ pub fn ConfigMapItem(props: ConfigMapItemProps) -> Element {
                     span { class: "info-item-short", title: "Age", "{configmap_data.age}" }
                 }
                 div { class: "configmap-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -68,6 +97,26 @@ pub fn ConfigMapItem(props: ConfigMapItemProps) -> Element {
                 }
             }
 
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<ConfigMap>(&()),
+                        namespace: Some(configmap_data.namespace.clone()),
+                        name: configmap_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<ConfigMap>(&()),
+                    namespace: Some(configmap_data.namespace.clone()),
+                    name: configmap_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "configmap-details",
                     // Labels Section
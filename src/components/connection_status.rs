@@ -0,0 +1,79 @@
+use dioxus::prelude::*;
+
+use crate::contexts::ClientReloadContext;
+use crate::k8s::{ClusterHealth, ConnectionStatus};
+use crate::utils::time_utils::calculate_age;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct ConnectionStatusPillProps {
+    /// Show only the status dot, for the collapsed sidebar
+    pub collapsed: bool,
+}
+
+/// Status pill for the API server connection, expanding into version, latency and the last error
+#[component]
+pub fn ConnectionStatusPill(props: ConnectionStatusPillProps) -> Element {
+    let health = use_context::<Signal<ClusterHealth>>();
+    let client_reload_context = use_context::<ClientReloadContext>();
+    let mut show_details = use_signal(|| false);
+
+    let health = health();
+    let status_class = format!("connection-pill connection-{}", health.status.class());
+    let latency = health.latency.map(|latency| format!("{}ms", latency.as_millis()));
+    let summary = match (&health.status, &health.server_version, &latency) {
+        (ConnectionStatus::Healthy, Some(version), Some(latency)) => format!("{} · {} · {}", health.status.label(), version, latency),
+        (status, _, _) if health.consecutive_failures > 1 => format!("{} ({} failed checks)", status.label(), health.consecutive_failures),
+        (status, _, _) => status.label().to_string(),
+    };
+    let last_checked = health
+        .last_checked
+        .map(|checked| format!("{} ago", calculate_age(&checked.to_rfc3339())))
+        .unwrap_or_else(|| "never".to_string());
+    let next_probe = health.next_probe_in().as_secs();
+
+    rsx! {
+        div { class: "connection-status",
+            button {
+                class: "{status_class}",
+                title: "{summary}",
+                onclick: move |_| show_details.toggle(),
+                span { class: "connection-dot" }
+                if !props.collapsed {
+                    span { class: "connection-summary", "{summary}" }
+                }
+            }
+
+            if show_details() && !props.collapsed {
+                div { class: "connection-details",
+                    div { span { class: "connection-details-key", "Status" } span { "{health.status.label()}" } }
+                    div {
+                        span { class: "connection-details-key", "Server" }
+                        span { {health.server_version.clone().unwrap_or_else(|| "-".to_string())} }
+                    }
+                    div {
+                        span { class: "connection-details-key", "Latency" }
+                        span { {latency.clone().unwrap_or_else(|| "-".to_string())} }
+                    }
+                    div { span { class: "connection-details-key", "Checked" } span { "{last_checked}" } }
+                    if health.consecutive_failures > 0 {
+                        div { span { class: "connection-details-key", "Next retry" } span { "in {next_probe}s" } }
+                    }
+                    if let Some(error) = health.last_error.clone() {
+                        div { class: "connection-details-error",
+                            span { class: "connection-details-key", "Last error" }
+                            span { title: "{error}", "{error}" }
+                        }
+                    }
+                    button {
+                        class: "connection-reconnect",
+                        onclick: move |_| {
+                            let mut revision = client_reload_context.revision;
+                            revision += 1;
+                        },
+                        "Reconnect now"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::batch::v1::CronJob;
+use kube::api::ApiResource;
 use crate::utils::calculate_age_from_time;
 
 #[derive(Props, PartialEq, Clone)]
@@ -7,7 +8,9 @@ pub struct CronJobItemProps {
     cronjob: CronJob,
 }
 
-use crate::components::{PodContainerInfo, PodContainers, PodEnvVar, PodResources, PodVolumeMount};
+use crate::components::{DeleteDialog, PodContainerInfo, PodContainers, PodEnvVar, PodResources, PodVolumeMount, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::CRONJOBS;
 
 #[derive(Clone)]
 struct CronJobData {
@@ -30,6 +33,8 @@ struct CronJobData {
 #[component]
 pub fn CronJobItem(props: CronJobItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
 
     let cronjob_data = CronJobData {
         name: props.cronjob.metadata.name.clone().unwrap_or_default(),
@@ -120,6 +125,9 @@ pub fn CronJobItem(props: CronJobItemProps) -> Element {
         "Scheduled"
     };
 
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", CRONJOBS, Some(&cronjob_data.namespace));
+
     rsx! {
         div {
             key: "{key_base}",
@@ -145,6 +153,25 @@ pub fn CronJobItem(props: CronJobItemProps) -> Element {
                     }
                 }
                 div { class: "cronjob-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -157,6 +184,26 @@ pub fn CronJobItem(props: CronJobItemProps) -> Element {
                 }
             }
 
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<CronJob>(&()),
+                        namespace: Some(cronjob_data.namespace.clone()),
+                        name: cronjob_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<CronJob>(&()),
+                    namespace: Some(cronjob_data.namespace.clone()),
+                    name: cronjob_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "cronjob-details",
                     // Basic Info Section
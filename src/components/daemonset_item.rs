@@ -1,5 +1,10 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::apps::v1::DaemonSet;
+use kube::api::ApiResource;
+
+use crate::components::{DeleteDialog, RolloutHistory, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::{DAEMONSETS, WorkloadKind};
 
 #[derive(Clone)]
 struct DaemonSetData {
@@ -42,6 +47,9 @@ pub struct DaemonSetItemProps {
 #[component]
 pub fn DaemonSetItem(props: DaemonSetItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
+    let mut show_history = use_signal(|| false);
 
     let daemonset_data = DaemonSetData {
         name: props.daemonset.metadata.name.clone().unwrap_or_default(),
@@ -168,6 +176,9 @@ pub fn DaemonSetItem(props: DaemonSetItemProps) -> Element {
         _ => "status-unknown",
     };
 
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", DAEMONSETS, Some(&daemonset_data.namespace));
+
     rsx! {
         div {
             key: "{daemonset_data.name}",
@@ -181,6 +192,34 @@ pub fn DaemonSetItem(props: DaemonSetItemProps) -> Element {
                     }
                 }
                 div { class: "daemonset-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_history.set(!show_history());
+                        },
+                        title: "Rollout history",
+                        "🕘"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -193,6 +232,37 @@ pub fn DaemonSetItem(props: DaemonSetItemProps) -> Element {
                 }
             }
 
+            {show_history().then(|| rsx! {
+                div { class: "daemonset-history-section",
+                    RolloutHistory {
+                        kind: WorkloadKind::DaemonSet,
+                        namespace: daemonset_data.namespace.clone(),
+                        name: daemonset_data.name.clone(),
+                        on_close: move |_| show_history.set(false),
+                    }
+                }
+            })}
+
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<DaemonSet>(&()),
+                        namespace: Some(daemonset_data.namespace.clone()),
+                        name: daemonset_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<DaemonSet>(&()),
+                    namespace: Some(daemonset_data.namespace.clone()),
+                    name: daemonset_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "daemonset-details",
                     // Basic Info Section
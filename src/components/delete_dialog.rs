@@ -0,0 +1,155 @@
+#![allow(non_snake_case)] // Allow non-snake_case for component names
+
+use dioxus::prelude::*;
+use kube::{api::ApiResource, Client};
+
+use crate::k8s::{delete_resource, DeleteOptions, DeletePropagation};
+
+const DIALOG_CSS: Asset = asset!("/assets/styling/dialog.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct DeleteDialogProps {
+    /// Kind to delete, e.g. `ApiResource::erase::<Pod>(&())`
+    pub resource: ApiResource,
+    /// `None` for cluster-scoped kinds
+    pub namespace: Option<String>,
+    pub name: String,
+    /// Offer force deletion (grace period 0), pre-selected when the object is already terminating
+    #[props(default)]
+    pub allow_force: bool,
+    #[props(default)]
+    pub terminating: bool,
+    pub on_close: EventHandler<()>,
+}
+
+pub fn DeleteDialog(props: DeleteDialogProps) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+
+    let mut propagation = use_signal(DeletePropagation::default);
+    let mut grace_period = use_signal(String::new);
+    let mut force = use_signal(|| props.allow_force && props.terminating);
+    let mut is_deleting = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let grace_period_seconds = grace_period.read().trim().parse::<u32>().ok();
+    let grace_period_invalid = !grace_period.read().trim().is_empty() && grace_period_seconds.is_none();
+    let qualified_name = match &props.namespace {
+        Some(ns) => format!("{}/{}", ns, props.name),
+        None => props.name.clone(),
+    };
+
+    let confirm = {
+        let resource = props.resource.clone();
+        let namespace = props.namespace.clone();
+        let name = props.name.clone();
+        move |_| {
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                return;
+            };
+            let options = if force() {
+                DeleteOptions::force()
+            } else {
+                DeleteOptions {
+                    propagation: propagation(),
+                    grace_period_seconds,
+                }
+            };
+            let resource = resource.clone();
+            let namespace = namespace.clone();
+            let name = name.clone();
+            is_deleting.set(true);
+            error.set(None);
+            spawn(async move {
+                match delete_resource(client, &resource, namespace.as_deref(), &name, &options).await {
+                    // The watch removes the card once the object is gone
+                    Ok(()) => props.on_close.call(()),
+                    Err(e) => {
+                        error.set(Some(format!("Failed to delete {}: {}", name, e)));
+                        is_deleting.set(false);
+                    }
+                }
+            });
+        }
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: DIALOG_CSS }
+        div {
+            class: "dialog-overlay",
+            // Keep clicks inside the dialog from toggling the card underneath
+            onclick: move |evt| evt.stop_propagation(),
+            div { class: "dialog-box",
+                h3 { "Delete {props.resource.kind}" }
+                p { "{qualified_name}" }
+
+                if props.allow_force {
+                    label { class: "dialog-checkbox",
+                        input {
+                            r#type: "checkbox",
+                            checked: force(),
+                            onchange: move |evt| force.set(evt.checked()),
+                        }
+                        "Force delete (grace period 0)"
+                    }
+                    if force() {
+                        div { class: "dialog-warning",
+                            "The object is removed from the API immediately, even if its containers are still running on the node."
+                        }
+                    }
+                }
+
+                if !force() {
+                    div { class: "dialog-input-group",
+                        label { r#for: "delete-propagation", "Propagation policy:" }
+                        select {
+                            id: "delete-propagation",
+                            value: "{propagation}",
+                            onchange: move |evt| {
+                                if let Ok(policy) = evt.value().parse() {
+                                    propagation.set(policy);
+                                }
+                            },
+                            {DeletePropagation::ALL.iter().map(|policy| rsx! {
+                                option {
+                                    key: "{policy}",
+                                    value: "{policy}",
+                                    selected: *policy == propagation(),
+                                    "{policy} - {policy.description()}"
+                                }
+                            })}
+                        }
+                    }
+                    div { class: "dialog-input-group",
+                        label { r#for: "delete-grace-period", "Grace period (seconds):" }
+                        input {
+                            id: "delete-grace-period",
+                            r#type: "text",
+                            placeholder: "Default",
+                            value: "{grace_period}",
+                            oninput: move |evt| grace_period.set(evt.value()),
+                        }
+                    }
+                }
+
+                {error().map(|err| rsx! {
+                    div { class: "dialog-error", "{err}" }
+                })}
+
+                div { class: "dialog-buttons",
+                    button {
+                        class: "dialog-button cancel",
+                        onclick: move |_| props.on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "dialog-button danger",
+                        disabled: is_deleting() || (!force() && grace_period_invalid),
+                        onclick: confirm,
+                        if is_deleting() { "Deleting..." } else if force() { "Force Delete" } else { "Delete" }
+                    }
+                }
+            }
+        }
+    }
+}
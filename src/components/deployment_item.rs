@@ -1,5 +1,10 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::apps::v1::Deployment;
+use kube::{api::ApiResource, Client};
+
+use crate::components::{DeleteDialog, RollbackForm, RolloutHistory, RolloutProgressBar, ScaleForm, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::{deployment_rollout_progress, restart_workload, set_deployment_paused, DEPLOYMENTS, WorkloadKind};
 
 #[derive(Clone)]
 struct DeploymentData {
@@ -32,6 +37,15 @@ pub struct DeploymentItemProps {
 #[component]
 pub fn DeploymentItem(props: DeploymentItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
+    let mut show_scale = use_signal(|| false);
+    let mut show_rollback = use_signal(|| false);
+    let mut show_history = use_signal(|| false);
+    // Generation produced by the last action, tracked until its rollout converges
+    let mut rollout_generation = use_signal(|| None::<i64>);
+    let mut action_error = use_signal(|| None::<String>);
+    let client_signal = use_context::<Signal<Option<Client>>>();
 
     let deployment_data = DeploymentData {
         name: props.deployment.metadata.name.clone().unwrap_or_default(),
@@ -93,6 +107,51 @@ pub fn DeploymentItem(props: DeploymentItemProps) -> Element {
             .unwrap_or_default(),
     };
 
+    let is_paused = props.deployment.spec.as_ref().and_then(|s| s.paused).unwrap_or(false);
+    let rollout_progress = deployment_rollout_progress(&props.deployment);
+    let rollout_converged = rollout_generation().is_some_and(|generation| rollout_progress.has_converged(generation));
+    use_effect(use_reactive!(|rollout_converged| {
+        if rollout_converged {
+            rollout_generation.set(None);
+        }
+    }));
+
+    let restart = {
+        let namespace = deployment_data.namespace.clone();
+        let name = deployment_data.name.clone();
+        move |evt: MouseEvent| {
+            evt.stop_propagation();
+            let Some(client) = client_signal() else { return };
+            let namespace = namespace.clone();
+            let name = name.clone();
+            action_error.set(None);
+            spawn(async move {
+                match restart_workload(client, WorkloadKind::Deployment, &namespace, &name).await {
+                    Ok(generation) => rollout_generation.set(Some(generation)),
+                    Err(e) => action_error.set(Some(format!("Failed to restart {}: {}", name, e))),
+                }
+            });
+        }
+    };
+
+    let toggle_pause = {
+        let namespace = deployment_data.namespace.clone();
+        let name = deployment_data.name.clone();
+        move |evt: MouseEvent| {
+            evt.stop_propagation();
+            let Some(client) = client_signal() else { return };
+            let namespace = namespace.clone();
+            let name = name.clone();
+            action_error.set(None);
+            spawn(async move {
+                match set_deployment_paused(client, &namespace, &name, !is_paused).await {
+                    Ok(generation) => rollout_generation.set(Some(generation)),
+                    Err(e) => action_error.set(Some(format!("Failed to update {}: {}", name, e))),
+                }
+            });
+        }
+    };
+
     let status_class = match deployment_data.status.as_str() {
         "Available" => "status-running",
         "Progressing" => "status-pending",
@@ -101,6 +160,11 @@ pub fn DeploymentItem(props: DeploymentItemProps) -> Element {
         _ => "status-unknown"
     };
 
+    let access_context = use_context::<AccessContext>();
+    // Scaling, restarts, pausing and rollbacks are all patches
+    let can_patch = access_context.can("patch", DEPLOYMENTS, Some(&deployment_data.namespace));
+    let can_delete = access_context.can("delete", DEPLOYMENTS, Some(&deployment_data.namespace));
+
     rsx! {
         div {
             key: "{deployment_data.name}",
@@ -114,6 +178,70 @@ pub fn DeploymentItem(props: DeploymentItemProps) -> Element {
                     }
                 }
                 div { class: "deployment-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_rollback.set(false);
+                            show_scale.set(!show_scale());
+                        },
+                        disabled: !can_patch,
+                        title: "Scale",
+                        "⚖️"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: restart,
+                        disabled: !can_patch,
+                        title: "Restart rollout",
+                        "🔄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: toggle_pause,
+                        disabled: !can_patch,
+                        title: if is_paused { "Resume rollout" } else { "Pause rollout" },
+                        if is_paused { "▶️" } else { "⏸️" }
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_scale.set(false);
+                            show_rollback.set(!show_rollback());
+                        },
+                        disabled: !can_patch,
+                        title: "Roll back",
+                        "⏪"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_history.set(!show_history());
+                        },
+                        title: "Rollout history",
+                        "🕘"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -126,6 +254,75 @@ pub fn DeploymentItem(props: DeploymentItemProps) -> Element {
                 }
             }
 
+            {(show_scale() || show_rollback() || show_history() || rollout_generation().is_some() || action_error().is_some()).then(|| rsx! {
+                div { class: "deployment-actions-section",
+                    if show_scale() {
+                        ScaleForm {
+                            kind: WorkloadKind::Deployment,
+                            namespace: deployment_data.namespace.clone(),
+                            name: deployment_data.name.clone(),
+                            replicas: deployment_data.desired_replicas,
+                            on_close: move |generation: Option<i64>| {
+                                show_scale.set(false);
+                                if generation.is_some() {
+                                    rollout_generation.set(generation);
+                                }
+                            },
+                        }
+                    }
+                    if show_rollback() {
+                        RollbackForm {
+                            kind: WorkloadKind::Deployment,
+                            namespace: deployment_data.namespace.clone(),
+                            name: deployment_data.name.clone(),
+                            on_close: move |generation: Option<i64>| {
+                                show_rollback.set(false);
+                                if generation.is_some() {
+                                    rollout_generation.set(generation);
+                                }
+                            },
+                        }
+                    }
+                    if show_history() {
+                        RolloutHistory {
+                            kind: WorkloadKind::Deployment,
+                            namespace: deployment_data.namespace.clone(),
+                            name: deployment_data.name.clone(),
+                            on_close: move |_| show_history.set(false),
+                        }
+                    }
+                    if rollout_generation().is_some() {
+                        RolloutProgressBar {
+                            progress: rollout_progress.clone(),
+                            on_close: move |_| rollout_generation.set(None),
+                        }
+                    }
+                    {action_error().map(|err| rsx! {
+                        span { class: "rollout-error", "{err}" }
+                    })}
+                }
+            })}
+
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<Deployment>(&()),
+                        namespace: Some(deployment_data.namespace.clone()),
+                        name: deployment_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<Deployment>(&()),
+                    namespace: Some(deployment_data.namespace.clone()),
+                    name: deployment_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "deployment-details",
                     // Basic Info Section
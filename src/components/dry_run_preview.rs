@@ -0,0 +1,60 @@
+use dioxus::prelude::*;
+
+use crate::utils::diff_utils::DiffLine;
+
+const DRY_RUN_CSS: Asset = asset!("/assets/styling/dry_run.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct DryRunPreviewProps {
+    /// The object the server would persist, as YAML; shown when there is no live object to diff against
+    #[props(optional)]
+    pub yaml: Option<String>,
+    /// Changes against the live object, for updates
+    #[props(optional)]
+    pub diff: Option<Vec<DiffLine>>,
+    #[props(optional)]
+    pub on_close: Option<EventHandler<()>>,
+}
+
+#[component]
+pub fn DryRunPreview(props: DryRunPreviewProps) -> Element {
+    let title = if props.diff.is_some() { "Dry run: changes to the live object" } else { "Dry run: object the server would create" };
+    let unchanged = props.diff.as_ref().is_some_and(|diff| !diff.iter().any(DiffLine::is_change));
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: DRY_RUN_CSS }
+        div { class: "dry-run-preview",
+            div { class: "dry-run-header",
+                span { class: "dry-run-title", "{title}" }
+                span { class: "dry-run-hint", "Defaults and admission changes applied, nothing persisted" }
+                if let Some(on_close) = props.on_close {
+                    button {
+                        r#type: "button",
+                        class: "btn-icon",
+                        title: "Close preview",
+                        onclick: move |_| on_close.call(()),
+                        "✕"
+                    }
+                }
+            }
+            if unchanged {
+                div { class: "dry-run-unchanged", "No changes; applying would leave the object as it is" }
+            } else if let Some(diff) = &props.diff {
+                div { class: "dry-run-body",
+                    {diff.iter().enumerate().map(|(i, line)| {
+                        let (class, marker, text) = match line {
+                            DiffLine::Same(text) => ("dry-run-line", " ", text),
+                            DiffLine::Added(text) => ("dry-run-line dry-run-added", "+", text),
+                            DiffLine::Removed(text) => ("dry-run-line dry-run-removed", "-", text),
+                        };
+                        rsx! {
+                            div { key: "{i}", class: class, "{marker} {text}" }
+                        }
+                    })}
+                }
+            } else if let Some(yaml) = &props.yaml {
+                pre { class: "dry-run-body", "{yaml}" }
+            }
+        }
+    }
+}
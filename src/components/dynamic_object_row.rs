@@ -0,0 +1,163 @@
+use dioxus::prelude::*;
+use kube::{api::DynamicObject, ResourceExt};
+
+use crate::components::{DeleteDialog, YamlPanel};
+use crate::k8s::{object_conditions, ApiKind, PrinterColumn};
+use crate::utils::{calculate_age_from_time, time_utils::calculate_age};
+
+#[derive(Props, PartialEq, Clone)]
+pub struct DynamicObjectRowProps {
+    pub object: DynamicObject,
+    pub kind: ApiKind,
+    pub columns: Vec<PrinterColumn>,
+    /// Show the namespace column, when listing across all namespaces
+    pub show_namespace: bool,
+}
+
+/// Table row for an object of any kind, expanding into its labels, conditions and YAML
+#[component]
+pub fn DynamicObjectRow(props: DynamicObjectRowProps) -> Element {
+    let mut is_expanded = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+
+    let object = &props.object;
+    let name = object.name_any();
+    let namespace = object.namespace();
+    let age = calculate_age_from_time(object.metadata.creation_timestamp.as_ref());
+    let values: Vec<String> = props.columns.iter().map(|column| column.value(object)).collect();
+    let conditions = object_conditions(object);
+    let ready = conditions.iter().find(|c| c.type_ == "Ready").map(|c| c.status.clone());
+    let labels = object.labels().clone();
+    let owners: Vec<String> = object
+        .owner_references()
+        .iter()
+        .map(|owner| format!("{}/{}", owner.kind, owner.name))
+        .collect();
+    // Name, optional namespace, printer columns, Ready and Age
+    let span = 3 + props.columns.len() + usize::from(props.show_namespace);
+
+    rsx! {
+        tr {
+            class: if is_expanded() { "api-object-row expanded" } else { "api-object-row" },
+            onclick: move |_| is_expanded.set(!is_expanded()),
+            td { class: "api-object-name", "{name}" }
+            if props.show_namespace {
+                td { {namespace.clone().unwrap_or_default()} }
+            }
+            {values.iter().enumerate().map(|(i, value)| rsx! {
+                td { key: "{i}", "{value}" }
+            })}
+            td {
+                {match ready.as_deref() {
+                    Some("True") => rsx! { span { class: "condition-pill condition-true", "Ready" } },
+                    Some(status) => rsx! { span { class: "condition-pill condition-false", "Ready: {status}" } },
+                    None => rsx! { span { class: "condition-none", "-" } },
+                }}
+            }
+            td { "{age}" }
+        }
+
+        {is_expanded().then(|| rsx! {
+            tr { class: "api-object-detail-row",
+                td { colspan: "{span}",
+                    div { class: "api-object-detail",
+                        div { class: "api-object-actions",
+                            button {
+                                class: "btn btn-secondary",
+                                onclick: move |_| show_yaml.set(!show_yaml()),
+                                if show_yaml() { "Hide YAML" } else { "View YAML" }
+                            }
+                            button {
+                                class: "btn btn-danger",
+                                onclick: move |_| show_delete.set(true),
+                                "Delete"
+                            }
+                        }
+
+                        if !owners.is_empty() {
+                            div { class: "api-object-section",
+                                h4 { "Owned By" }
+                                span { class: "api-object-owners", {owners.join(", ")} }
+                            }
+                        }
+
+                        if !labels.is_empty() {
+                            div { class: "api-object-section",
+                                h4 { "Labels" }
+                                div { class: "labels-grid",
+                                    {labels.iter().map(|(key, value)| rsx! {
+                                        div {
+                                            key: "{key}",
+                                            class: "label",
+                                            span { class: "label-key", "{key}" }
+                                            span { class: "label-value", "{value}" }
+                                        }
+                                    })}
+                                }
+                            }
+                        }
+
+                        if !conditions.is_empty() {
+                            div { class: "api-object-section",
+                                h4 { "Conditions" }
+                                table { class: "conditions-table",
+                                    thead {
+                                        tr {
+                                            th { "Type" }
+                                            th { "Status" }
+                                            th { "Reason" }
+                                            th { "Message" }
+                                            th { "Last Transition" }
+                                        }
+                                    }
+                                    tbody {
+                                        {conditions.iter().map(|condition| {
+                                            let status_class = match condition.status.as_str() {
+                                                "True" => "condition-pill condition-true",
+                                                "False" => "condition-pill condition-false",
+                                                _ => "condition-pill condition-unknown",
+                                            };
+                                            let transition = condition
+                                                .last_transition_time
+                                                .as_deref()
+                                                .map(calculate_age)
+                                                .unwrap_or_else(|| "-".to_string());
+                                            rsx! {
+                                                tr { key: "{condition.type_}",
+                                                    td { "{condition.type_}" }
+                                                    td { span { class: status_class, "{condition.status}" } }
+                                                    td { {condition.reason.clone().unwrap_or_default()} }
+                                                    td { class: "condition-message", {condition.message.clone().unwrap_or_default()} }
+                                                    td { "{transition}" }
+                                                }
+                                            }
+                                        })}
+                                    }
+                                }
+                            }
+                        }
+
+                        if show_yaml() {
+                            YamlPanel {
+                                resource: props.kind.resource.clone(),
+                                namespace: namespace.clone(),
+                                name: name.clone(),
+                                on_close: move |_| show_yaml.set(false),
+                            }
+                        }
+
+                        if show_delete() {
+                            DeleteDialog {
+                                resource: props.kind.resource.clone(),
+                                namespace: namespace.clone(),
+                                name: name.clone(),
+                                on_close: move |_| show_delete.set(false),
+                            }
+                        }
+                    }
+                }
+            }
+        })}
+    }
+}
@@ -0,0 +1,24 @@
+use dioxus::prelude::*;
+
+const FEEDBACK_CSS: Asset = asset!("/assets/styling/feedback.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct EmptyStateProps {
+    message: String,
+    #[props(default)]
+    hint: Option<String>,
+}
+
+/// Placeholder for a list that loaded fine but has nothing in it
+#[component]
+pub fn EmptyState(props: EmptyStateProps) -> Element {
+    rsx! {
+        document::Link { rel: "stylesheet", href: FEEDBACK_CSS }
+        div { class: "empty-state",
+            div { class: "empty-state-message", "{props.message}" }
+            if let Some(hint) = props.hint {
+                div { class: "empty-state-hint", "{hint}" }
+            }
+        }
+    }
+}
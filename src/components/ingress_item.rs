@@ -1,5 +1,10 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::networking::v1::Ingress;
+use kube::api::ApiResource;
+
+use crate::components::{DeleteDialog, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::INGRESSES;
 
 #[derive(Clone)]
 struct IngressData {
@@ -48,6 +53,8 @@ pub struct IngressItemProps {
 #[component]
 pub fn IngressItem(props: IngressItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
 
     let ingress_data = IngressData {
         name: props.ingress.metadata.name.clone().unwrap_or_default(),
@@ -132,6 +139,9 @@ pub fn IngressItem(props: IngressItemProps) -> Element {
         ingress_data.load_balancer_ips.join(", ")
     };
 
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", INGRESSES, Some(&ingress_data.namespace));
+
     rsx! {
         div {
             key: "{ingress_data.name}",
@@ -151,6 +161,25 @@ pub fn IngressItem(props: IngressItemProps) -> Element {
                     span { class: "info-item-short", title: "Hosts", "{display_hosts_truncated}" }
                 }
                 div { class: "ingress-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -163,6 +192,26 @@ pub fn IngressItem(props: IngressItemProps) -> Element {
                 }
             }
 
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<Ingress>(&()),
+                        namespace: Some(ingress_data.namespace.clone()),
+                        name: ingress_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<Ingress>(&()),
+                    namespace: Some(ingress_data.namespace.clone()),
+                    name: ingress_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "ingress-details",
                     // Basic Info Section
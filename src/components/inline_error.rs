@@ -0,0 +1,41 @@
+use dioxus::prelude::*;
+
+use crate::components::RetryButton;
+use crate::contexts::error::AppError;
+
+const FEEDBACK_CSS: Asset = asset!("/assets/styling/feedback.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct InlineErrorProps {
+    error: AppError,
+    /// Shows a retry button when set
+    #[props(default)]
+    on_retry: Option<EventHandler<()>>,
+}
+
+/// Error in place of content that couldn't be loaded, saying why and what can be done about it
+#[component]
+pub fn InlineError(props: InlineErrorProps) -> Element {
+    let kind = match &props.error {
+        AppError::Forbidden { .. } => "forbidden",
+        AppError::MetricsUnavailable(_) => "unavailable",
+        _ => "failed",
+    };
+    let message = props.error.to_string();
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: FEEDBACK_CSS }
+        div { class: "inline-error inline-error-{kind}",
+            div { class: "inline-error-body",
+                div { class: "inline-error-title", "{props.error.title()}" }
+                div { class: "inline-error-message", title: "{message}", "{message}" }
+                if let Some(hint) = props.error.hint() {
+                    div { class: "inline-error-hint", "{hint}" }
+                }
+            }
+            if let Some(on_retry) = props.on_retry {
+                RetryButton { on_retry }
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use dioxus::prelude::*;
-use k8s_openapi::api::batch::v1::Job;
-use crate::components::{PodContainerInfo, PodEnvVar, PodContainers, PodResources, PodVolumeMount};
+use dioxus::{logger::tracing, prelude::*};
+use k8s_openapi::api::{batch::v1::Job, core::v1::Pod};
+use kube::{api::{ApiResource, ListParams}, Api, Client};
+use crate::components::{DeleteDialog, PodContainerInfo, PodContainers, PodEnvVar, PodResources, PodVolumeMount, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::JOBS;
 
 #[derive(Props, PartialEq, Clone)]
 pub struct JobItemProps {
@@ -10,6 +13,8 @@ pub struct JobItemProps {
 #[component]
 pub fn JobItem(props: JobItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
 
     let job_data = JobData {
         name: props.job.metadata.name.clone().unwrap_or_default(),
@@ -225,6 +230,38 @@ pub fn JobItem(props: JobItemProps) -> Element {
             .unwrap_or_default(),
     };
 
+    // Look up one of the job's running pods so its containers can be opened in a shell
+    let client_signal = use_context::<Signal<Option<Client>>>();
+    let mut running_pod = use_signal(|| None::<(String, String)>);
+    use_effect({
+        let namespace = job_data.namespace.clone();
+        let name = job_data.name.clone();
+        let active = job_data.active;
+        move || {
+            if !is_expanded() || active == 0 {
+                return;
+            }
+            let Some(client) = client_signal() else { return };
+            let namespace = namespace.clone();
+            let name = name.clone();
+            spawn(async move {
+                let pods: Api<Pod> = Api::namespaced(client, &namespace);
+                let params = ListParams::default()
+                    .labels(&format!("job-name={}", name))
+                    .fields("status.phase=Running");
+                match pods.list(&params).await {
+                    Ok(list) => running_pod.set(
+                        list.items
+                            .into_iter()
+                            .find_map(|pod| pod.metadata.name)
+                            .map(|pod_name| (namespace.clone(), pod_name)),
+                    ),
+                    Err(e) => tracing::error!("Failed to list pods for job {}: {:?}", name, e),
+                }
+            });
+        }
+    });
+
     let key_base = format!("{}-{}", job_data.namespace, job_data.name);
     let status = if job_data.succeeded > 0 {
         "Succeeded"
@@ -236,6 +273,9 @@ pub fn JobItem(props: JobItemProps) -> Element {
         "Pending"
     };
 
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", JOBS, Some(&job_data.namespace));
+
     rsx! {
         div {
             key: "{key_base}",
@@ -261,6 +301,25 @@ pub fn JobItem(props: JobItemProps) -> Element {
                     }
                 }
                 div { class: "job-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -273,6 +332,26 @@ pub fn JobItem(props: JobItemProps) -> Element {
                 }
             }
 
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<Job>(&()),
+                        namespace: Some(job_data.namespace.clone()),
+                        name: job_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<Job>(&()),
+                    namespace: Some(job_data.namespace.clone()),
+                    name: job_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "job-details",
                     // Basic Info Section
@@ -413,6 +492,7 @@ pub fn JobItem(props: JobItemProps) -> Element {
                         PodContainers {
                             containers: job_data.containers.clone(),
                             key_base: key_base.clone(),
+                            running_pod: running_pod(),
                         }
                     })}
 
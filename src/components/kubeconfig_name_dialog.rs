@@ -7,6 +7,8 @@ const DIALOG_CSS: Asset = asset!("/assets/styling/dialog.css");
 #[derive(Props, PartialEq, Clone)]
 pub struct KubeconfigNameDialogProps {
     pub original_filename: String,
+    #[props(default = "Name Kubeconfig Context".to_string())]
+    pub title: String,
     pub on_close: EventHandler<Option<String>>,
 }
 
@@ -24,7 +26,7 @@ pub fn KubeconfigNameDialog(props: KubeconfigNameDialogProps) -> Element {
         document::Link { rel: "stylesheet", href: DIALOG_CSS }
         div { class: "dialog-overlay", // Modal overlay
             div { class: "dialog-box", // Dialog container
-                h3 { "Name Kubeconfig Context" }
+                h3 { "{props.title}" }
                 p { "Original file: {props.original_filename}" }
                 div { class: "dialog-input-group",
                     label { r#for: "kubeconfig-name-input", "Context Name:" }
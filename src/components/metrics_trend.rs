@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use dioxus::prelude::*;
+
+use crate::k8s::{format_cpu, format_memory, MetricSummary, MetricsHistory, MetricsSeries, MetricsSource};
+
+const METRICS_TREND_CSS: Asset = asset!("/assets/styling/metrics_trend.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct SparklineProps {
+    values: Vec<f64>,
+}
+
+/// A small line chart of `values`, scaled to fill its box
+#[component]
+pub fn Sparkline(props: SparklineProps) -> Element {
+    let summary = MetricSummary::of(props.values.iter().copied());
+    let (min, max) = summary.map(|s| (s.min, s.max)).unwrap_or((0.0, 0.0));
+    // A flat line sits in the middle rather than along an edge
+    let range = if max > min { max - min } else { 1.0 };
+    let offset = if max > min { 0.0 } else { 0.5 };
+    let step = 100.0 / (props.values.len().max(2) - 1) as f64;
+    let points = props
+        .values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let y = 22.0 - ((value - min) / range + offset) * 20.0;
+            format!("{:.2},{:.2}", i as f64 * step, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    rsx! {
+        svg {
+            class: "sparkline",
+            view_box: "0 0 100 24",
+            preserve_aspect_ratio: "none",
+            polyline {
+                points: "{points}",
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "1.5",
+                vector_effect: "non-scaling-stroke",
+            }
+        }
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct MetricsTrendProps {
+    series: MetricsSeries,
+    /// How far back to chart and summarize
+    #[props(default = 15)]
+    minutes: u64,
+}
+
+/// CPU and memory sparklines with min, average and max over the last few minutes, from the
+/// background metrics sampler
+#[component]
+pub fn MetricsTrend(props: MetricsTrendProps) -> Element {
+    let history = use_context::<Signal<MetricsHistory>>();
+    let samples = history.read().recent(&props.series, Duration::from_secs(props.minutes * 60));
+    if samples.is_empty() {
+        return rsx! {};
+    }
+
+    let rows = [
+        ("CPU", samples.iter().map(|s| s.cpu).collect::<Vec<_>>(), format_cpu as fn(f64) -> String),
+        ("Memory", samples.iter().map(|s| s.memory).collect(), format_memory),
+    ];
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: METRICS_TREND_CSS }
+        div { class: "metrics-trend",
+            if samples.len() < 2 {
+                div { class: "metrics-trend-pending", "Collecting usage history…" }
+            } else {
+                {rows.into_iter().map(|(label, values, format)| {
+                    let summary = MetricSummary::of(values.iter().copied());
+                    rsx! {
+                        div { key: "{label}", class: "metrics-trend-row",
+                            span { class: "metrics-trend-label", "{label}" }
+                            Sparkline { values }
+                            if let Some(summary) = summary {
+                                span {
+                                    class: "metrics-trend-stats",
+                                    title: "Min / avg / max over the last {props.minutes} minutes",
+                                    "{format(summary.min)} / {format(summary.avg)} / {format(summary.max)}"
+                                }
+                            }
+                        }
+                    }
+                })}
+            }
+        }
+    }
+}
+
+/// Which source the usage figures come from, once the sampler has heard from one
+#[component]
+pub fn MetricsSourceNote() -> Element {
+    let history = use_context::<Signal<MetricsHistory>>();
+    let Some(source) = history.read().source() else {
+        return rsx! {};
+    };
+    let title = match source {
+        MetricsSource::MetricsServer => "CPU and memory from metrics-server, storage from the kubelets",
+        MetricsSource::KubeletSummary => "metrics-server isn't answering, so usage is read from each kubelet",
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: METRICS_TREND_CSS }
+        span { class: "metrics-source", title: "{title}", "Usage via {source.label()}" }
+    }
+}
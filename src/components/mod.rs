@@ -7,11 +7,61 @@ pub use namespace_item::{NamespaceItem, ResourceQuota, LimitRange, NamespaceItem
 mod node_item;
 pub use node_item::{NodeItem, NodeCondition};
 
+mod metrics_trend;
+pub use metrics_trend::{MetricsSourceNote, MetricsTrend};
+
+mod prometheus_charts;
+pub use prometheus_charts::{PromQlPanel, PrometheusCharts, PrometheusSetup, TimeChart};
+
 pub mod kubeconfig_name_dialog;
 
+pub mod vault_dialog;
+
+mod connection_status;
+pub use connection_status::ConnectionStatusPill;
+
+mod retry_button;
+pub use retry_button::RetryButton;
+
+mod inline_error;
+pub use inline_error::InlineError;
+
+mod empty_state;
+pub use empty_state::EmptyState;
+
+mod delete_dialog;
+pub use delete_dialog::DeleteDialog;
+
+mod dry_run_preview;
+pub use dry_run_preview::DryRunPreview;
+
+mod yaml_panel;
+pub use yaml_panel::YamlPanel;
+
+mod dynamic_object_row;
+pub use dynamic_object_row::DynamicObjectRow;
+
 mod pod_item;
 pub use pod_item::PodItem;
 
+mod pod_log_panel;
+pub use pod_log_panel::PodLogPanel;
+
+mod pod_terminal;
+pub use pod_terminal::PodTerminal;
+
+mod port_forward_form;
+pub use port_forward_form::PortForwardForm;
+
+mod port_forward_list;
+pub use port_forward_list::PortForwardList;
+
+mod rollout_controls;
+pub use rollout_controls::{RollbackForm, RolloutProgressBar, ScaleForm};
+
+mod rollout_history;
+pub use rollout_history::RolloutHistory;
+
 mod deployment_item;
 pub use deployment_item::DeploymentItem;
 
@@ -1,4 +1,10 @@
 use dioxus::prelude::*;
+use k8s_openapi::api::core::v1::Namespace;
+use kube::api::ApiResource;
+
+use crate::components::{DeleteDialog, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::{ParsedQuantity, NAMESPACES};
 
 #[derive(PartialEq, Clone)]
 pub struct ResourceQuota {
@@ -39,40 +45,20 @@ fn calculate_pod_progress(used: u32, limit: u32) -> f32 {
 }
 
 fn calculate_progress_width(used: &str, limit: &str) -> f32 {
-    match (parse_resource_value(used), parse_resource_value(limit)) {
-        (Some(used), Some(limit)) if limit > 0.0 => (used / limit * 100.0).min(100.0),
+    match (ParsedQuantity::parse(used), ParsedQuantity::parse(limit)) {
+        (Ok(used), Ok(limit)) => used.percent_of(&limit).min(100.0) as f32,
         _ => 0.0,
     }
 }
 
-fn parse_resource_value(value: &str) -> Option<f32> {
-    if value.is_empty() || value == "0" {
-        return None;
-    }
-
-    // Parse memory values (Gi, Mi, Ki)
-    if let Some(value) = value.strip_suffix("Gi") {
-        return value.parse::<f32>().ok();
-    }
-    if let Some(value) = value.strip_suffix("Mi") {
-        return value.parse::<f32>().ok().map(|v| v / 1024.0);
-    }
-    if let Some(value) = value.strip_suffix("Ki") {
-        return value.parse::<f32>().ok().map(|v| v / (1024.0 * 1024.0));
-    }
-
-    // Parse CPU values (m for millicores)
-    if let Some(value) = value.strip_suffix('m') {
-        return value.parse::<f32>().ok().map(|v| v / 1000.0);
-    }
-
-    // Try parsing as a plain number
-    value.parse::<f32>().ok()
-}
-
 #[component]
 pub fn NamespaceItem(props: NamespaceItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
+
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", NAMESPACES, None);
 
     rsx! {
         div {
@@ -88,6 +74,25 @@ pub fn NamespaceItem(props: NamespaceItemProps) -> Element {
                 }
                 div {
                     class: "namespace-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -100,6 +105,26 @@ pub fn NamespaceItem(props: NamespaceItemProps) -> Element {
                 }
             }
 
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<Namespace>(&()),
+                        namespace: None,
+                        name: props.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<Namespace>(&()),
+                    namespace: None,
+                    name: props.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx!(
                 div {
                     class: "labels-section margin-top-6",
@@ -1,6 +1,12 @@
-use dioxus::{prelude::*};
+use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::core::v1::Namespace;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
+
+use crate::contexts::AccessContext;
+use crate::k8s::ClusterCache;
+
+/// Option value that swaps the select for a text input
+const ENTER_NAMESPACE: &str = "__enter_namespace__";
 
 #[derive(Props, PartialEq, Clone)]
 pub struct NamespaceSelectorProps {
@@ -10,47 +16,126 @@ pub struct NamespaceSelectorProps {
 
 #[component]
 pub fn NamespaceSelector(props: NamespaceSelectorProps) -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
 
     // Signal for holding namespaces fetched from Kubernetes
     let mut namespaces = use_signal(|| Vec::<String>::new());
+    let mut is_entering = use_signal(|| false);
+    let mut entered_namespace = use_signal(String::new);
+    let mut switched_to_default = use_signal(|| false);
+    let mut watch_task = use_signal(|| None::<Task>);
 
     // Fetch namespaces using `use_effect` - always call the hook but handle conditionals inside
     use_effect({        
         move || {
-            if let Some(client) = &*client_signal.read() {
-                let client = client.clone();
-                spawn(async move {
-                    let ns_api: Api<Namespace> = Api::all(client);
-                    match ns_api.list(&ListParams::default()).await {
-                        Ok(ns_list) => {
-                            let mut ns_names = ns_list.items
-                                .into_iter()
-                                .filter_map(|ns| ns.metadata.name)
-                                .collect::<Vec<_>>();
-                            ns_names.insert(0, "All".to_string());
-                            namespaces.set(ns_names);
-                        }
-                        Err(e) => {
-                            println!("Error fetching namespaces: {:?}", e);
+            // Stop following the previous cluster's namespaces
+            if let Some(task) = watch_task.write().take() {
+                task.cancel();
+            }
+            // Without list access the watch would only fail, and the options come from
+            // known_namespaces() instead
+            if !access_context.can_list_namespaces() {
+                return;
+            }
+            if let Some(cache) = &*cache_signal.read() {
+                let cache = cache.clone();
+                let task = spawn(async move {
+                    let mut snapshots = Box::pin(cache.snapshots::<Namespace>());
+                    while let Some(snapshot) = snapshots.next().await {
+                        match snapshot {
+                            Ok(ns_list) => {
+                                let mut ns_names = ns_list
+                                    .into_iter()
+                                    .filter_map(|ns| ns.metadata.name)
+                                    .collect::<Vec<_>>();
+                                ns_names.insert(0, "All".to_string());
+                                namespaces.set(ns_names);
+                            }
+                            Err(e) => {
+                                tracing::warn!("Failed to fetch namespaces: {}", e);
+                            }
                         }
                     }
                 });
+                watch_task.set(Some(task));
             } else {
-                // Set default namespaces when no client is available
+                // Set default namespaces when no cluster is connected
                 namespaces.set(vec!["All".to_string()]);
             }
         }
     });
 
+    // Without access to every namespace "All" usually shows nothing, so start in the
+    // kubeconfig's namespace instead, once
+    use_effect(use_reactive(&props.selected_namespace, move |selected| {
+        if selected != "All" || access_context.can_list_namespaces() || *switched_to_default.peek() {
+            return;
+        }
+        if let Some(namespace) = access_context.default_namespace.peek().clone() {
+            switched_to_default.set(true);
+            props.on_change.call(namespace);
+        }
+    }));
+
+    let can_list = access_context.can_list_namespaces();
+    let options = if can_list {
+        namespaces()
+    } else {
+        // Namespaces can't be listed, so offer the ones we know of
+        let mut known = vec!["All".to_string()];
+        known.extend(access_context.known_namespaces());
+        if !known.contains(&props.selected_namespace) {
+            known.push(props.selected_namespace.clone());
+        }
+        known
+    };
+
+    let mut submit_entered = move || {
+        let namespace = entered_namespace().trim().to_string();
+        if !namespace.is_empty() {
+            let mut manual = access_context.manual_namespaces;
+            if !manual.read().contains(&namespace) {
+                manual.write().push(namespace.clone());
+            }
+            props.on_change.call(namespace);
+        }
+        entered_namespace.set(String::new());
+        is_entering.set(false);
+    };
+
+    if is_entering() {
+        return rsx! {
+            input {
+                class: "namespace-select",
+                r#type: "text",
+                placeholder: "Namespace, then Enter",
+                autofocus: true,
+                value: "{entered_namespace}",
+                oninput: move |evt| entered_namespace.set(evt.value()),
+                onkeydown: move |evt| match evt.key() {
+                    Key::Enter => submit_entered(),
+                    Key::Escape => is_entering.set(false),
+                    _ => {}
+                },
+                onblur: move |_| is_entering.set(false),
+            }
+        };
+    }
+
     rsx! {
         select {
             class: "namespace-select",
             value: "{props.selected_namespace}",
+            title: if can_list { "" } else { "You're not allowed to list namespaces" },
             onchange: move |evt| {
-                props.on_change.call(evt.value());
+                if evt.value() == ENTER_NAMESPACE {
+                    is_entering.set(true);
+                } else {
+                    props.on_change.call(evt.value());
+                }
             },
-            {namespaces().iter().map(|ns| {
+            {options.iter().map(|ns| {
                 rsx! {
                     option {
                         value: "{ns}",
@@ -58,6 +143,9 @@ pub fn NamespaceSelector(props: NamespaceSelectorProps) -> Element {
                     }
                 }
             })}
+            if !can_list {
+                option { value: ENTER_NAMESPACE, "Enter namespace…" }
+            }
         }
     }
 }
@@ -1,5 +1,8 @@
 use dioxus::prelude::*;
 
+use crate::components::{MetricsTrend, PrometheusCharts};
+use crate::k8s::{MetricsSeries, PromScope};
+
 #[derive(Props, PartialEq, Clone)]
 pub struct NodeCondition {
     pub condition_type: String,
@@ -68,6 +71,9 @@ pub fn NodeItem(props: NodeItemProps) -> Element {
                 }
             }
 
+            MetricsTrend { series: MetricsSeries::Node(props.name.clone()) }
+            PrometheusCharts { scope: PromScope::Node(props.name.clone()) }
+
             div { class: "node-info",
                 div { class: "info-group",
                     div { class: "info-item",
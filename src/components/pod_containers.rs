@@ -1,6 +1,10 @@
 use dioxus::prelude::*;
 use std::collections::HashMap;
 
+use crate::components::PodTerminal;
+use crate::contexts::AccessContext;
+use crate::k8s::POD_EXEC;
+
 #[derive(Clone, PartialEq)]
 pub struct PodContainerInfo {
     pub name: String,
@@ -36,11 +40,20 @@ pub struct PodVolumeMount {
 pub struct PodContainersProps {
     pub containers: Vec<PodContainerInfo>,
     pub key_base: String,
+    // Running pod (namespace, name) to open shells in; templates without one get no shell button
+    #[props(optional)]
+    pub running_pod: Option<(String, String)>,
 }
 
 #[component]
 pub fn PodContainers(props: PodContainersProps) -> Element {
     let mut expanded_states = use_signal(|| HashMap::<String, bool>::new());
+    let mut terminal_container = use_signal(|| None::<String>);
+    let access_context = use_context::<AccessContext>();
+    let can_exec = props
+        .running_pod
+        .as_ref()
+        .is_some_and(|(namespace, _)| access_context.can("create", POD_EXEC, Some(namespace)));
     
     let mut toggle_container = move |name: String| {
         expanded_states.with_mut(|states| {
@@ -56,6 +69,8 @@ pub fn PodContainers(props: PodContainersProps) -> Element {
                 {props.containers.iter().cloned().map(|container| {
                     let is_expanded = expanded_states.read().get(&container.name).copied().unwrap_or(false);
                     let container_name = container.name.clone();
+                    let shell_container = container.name.clone();
+                    let show_terminal = terminal_container.read().as_deref() == Some(container.name.as_str());
                     rsx! {
                         div { 
                             key: "container-{props.key_base}-{container.name}",
@@ -67,6 +82,15 @@ pub fn PodContainers(props: PodContainersProps) -> Element {
                                 div { class: "container-right",
                                     
                                     span { class: "image-tag", "{container.image}" }
+                                    if props.running_pod.is_some() {
+                                        button {
+                                            class: "btn-icon",
+                                            onclick: move |_| terminal_container.set(Some(shell_container.clone())),
+                                            disabled: !can_exec,
+                                            title: if can_exec { "Open shell" } else { "You're not allowed to exec into pods" },
+                                            ">_"
+                                        }
+                                    }
                                     button {
                                         class: "btn-icon expand-toggle",
                                         onclick: move |_| toggle_container(container_name.clone()),
@@ -75,6 +99,15 @@ pub fn PodContainers(props: PodContainersProps) -> Element {
                                     }
                                 }
                             }
+                            {props.running_pod.clone().filter(|_| show_terminal).map(|(namespace, pod_name)| rsx! {
+                                PodTerminal {
+                                    key: "{namespace}-{pod_name}-{container.name}-terminal",
+                                    namespace,
+                                    pod_name,
+                                    container: container.name.clone(),
+                                    on_close: move |_| terminal_container.set(None),
+                                }
+                            })}
                             {is_expanded.then(|| rsx! {
                                 div { class: "container-content",
                                     // Command and Args
@@ -1,5 +1,18 @@
+use std::collections::BTreeMap;
+
 use dioxus::{prelude::*};
-use k8s_openapi::{api::core::v1::Pod, apimachinery::pkg::api::resource::Quantity};
+use k8s_openapi::{api::core::v1::{ContainerStatus, Pod}, apimachinery::pkg::api::resource::Quantity};
+use kube::api::ApiResource;
+
+use crate::components::{
+    DeleteDialog, MetricsTrend, PodLogPanel, PodTerminal, PortForwardForm, PrometheusCharts, YamlPanel,
+};
+use crate::contexts::AccessContext;
+use crate::k8s::{
+    container_names, format_cpu, format_memory, is_container_running, prefers_previous_logs, ForwardTarget,
+    MetricsHistory, MetricsSeries, ParsedQuantity, PromScope, PODS, POD_EXEC, POD_LOGS, POD_PORT_FORWARD,
+};
+use crate::utils::calculate_age_from_time;
 
 
 #[derive(Clone)]
@@ -23,9 +36,56 @@ struct ContainerData {
     name: String,
     image: String,
     status: String,
-    cpu_usage: f32,
-    memory_usage: String,
-    memory_limit: std::option::Option<Quantity>,
+    /// Why the container is waiting or terminated, e.g. `CrashLoopBackOff`
+    reason: Option<String>,
+    restart_count: i32,
+    cpu: ResourceAllocation,
+    memory: ResourceAllocation,
+}
+
+/// What a container asked for of one resource
+#[derive(Clone, Default)]
+struct ResourceAllocation {
+    request: Option<f64>,
+    limit: Option<f64>,
+}
+
+impl ResourceAllocation {
+    fn new(
+        resource: &str,
+        requests: Option<&BTreeMap<String, Quantity>>,
+        limits: Option<&BTreeMap<String, Quantity>>,
+    ) -> Self {
+        let value = |quantities: Option<&BTreeMap<String, Quantity>>| {
+            quantities
+                .and_then(|quantities| quantities.get(resource))
+                .map(|quantity| ParsedQuantity::from_quantity(quantity).as_f64())
+                .filter(|value| *value > 0.0)
+        };
+        Self { request: value(requests), limit: value(limits) }
+    }
+}
+
+/// The container's state and the reason it's in it, if any
+fn container_state(status: Option<&ContainerStatus>) -> (String, Option<String>) {
+    let Some(state) = status.and_then(|status| status.state.as_ref()) else {
+        return ("Unknown".to_string(), None);
+    };
+    if let Some(waiting) = &state.waiting {
+        ("Waiting".to_string(), waiting.reason.clone())
+    } else if let Some(terminated) = &state.terminated {
+        let reason = terminated.reason.clone().or_else(|| Some(format!("Exit code {}", terminated.exit_code)));
+        ("Terminated".to_string(), reason)
+    } else if state.running.is_some() {
+        ("Running".to_string(), None)
+    } else {
+        ("Unknown".to_string(), None)
+    }
+}
+
+/// `used` as a whole percentage of `total`
+fn percent(used: f64, total: Option<f64>) -> Option<f64> {
+    total.map(|total| (used / total * 100.0).round())
 }
 
 #[derive(Clone)]
@@ -36,22 +96,6 @@ struct PodCondition {
     reason: String,
 }
 
-/// Parses a Kubernetes quantity string like "512Mi", "2Gi", "1024Ki" into a float (in MiB)
-fn parse_quantity_string(q: &str) -> f32 {
-    if let Some(stripped) = q.strip_suffix("Ki") {
-        stripped.parse::<f32>().unwrap_or(0.0) / 1024.0
-    } else if let Some(stripped) = q.strip_suffix("Mi") {
-        stripped.parse::<f32>().unwrap_or(0.0)
-    } else if let Some(stripped) = q.strip_suffix("Gi") {
-        stripped.parse::<f32>().unwrap_or(0.0) * 1024.0
-    } else if let Some(stripped) = q.strip_suffix("Ti") {
-        stripped.parse::<f32>().unwrap_or(0.0) * 1024.0 * 1024.0
-    } else {
-        // Assume it's already a plain number in MiB
-        q.parse::<f32>().unwrap_or(0.0)
-    }
-}
-
 #[derive(Props, PartialEq, Clone)]
 pub struct PodItemProps {
     pod: Pod,
@@ -61,12 +105,39 @@ pub struct PodItemProps {
 #[component]
 pub fn PodItem(props: PodItemProps) -> Element {
     let mut is_expanded = use_signal(||false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
+    let mut logs_container = use_signal(|| None::<String>);
+    let mut terminal_container = use_signal(|| None::<String>);
+    let mut show_port_forward = use_signal(|| false);
+    let container_ports: Vec<u16> = props.pod.spec.as_ref()
+        .map(|spec| {
+            spec.containers.iter()
+                .flat_map(|c| c.ports.iter().flatten())
+                .map(|p| p.container_port as u16)
+                .collect()
+        })
+        .unwrap_or_default();
+    // Deleted pods keep their phase until the kubelet confirms the containers have stopped
+    let is_terminating = props.pod.metadata.deletion_timestamp.is_some();
+    let default_container = props.pod.spec.as_ref()
+        .and_then(|spec| spec.containers.first())
+        .map(|c| c.name.clone())
+        .unwrap_or_default();
+
+    let container_statuses = props.pod.status.as_ref()
+        .and_then(|status| status.container_statuses.clone())
+        .unwrap_or_default();
 
     let pod_data = PodData {
         name: props.pod.metadata.name.clone().unwrap(),
         namespace: props.pod.metadata.namespace.clone().unwrap(),
-        status: props.pod.status.clone().unwrap().phase.unwrap_or_default(),
-        age: "1h".to_string(), // Placeholder for age
+        status: if is_terminating {
+            "Terminating".to_string()
+        } else {
+            props.pod.status.clone().unwrap().phase.unwrap_or_default()
+        },
+        age: calculate_age_from_time(props.pod.metadata.creation_timestamp.as_ref()),
         ready_containers: {
             let total = props.pod
                 .spec
@@ -86,10 +157,12 @@ pub fn PodItem(props: PodItemProps) -> Element {
                 .unwrap_or(0);
             (ready, total)
         },
-        restart_count: 0, // Placeholder for restart count
+        restart_count: container_statuses.iter().map(|status| status.restart_count.max(0) as u32).sum(),
         ip: props.pod.status.clone().unwrap().pod_ip.unwrap_or_default(),
         node: props.pod.spec.clone().unwrap().node_name.unwrap_or_default(),
-        qos_class: "BestEffort".to_string(), // Placeholder for QoS class
+        qos_class: props.pod.status.as_ref()
+            .and_then(|status| status.qos_class.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
         containers: props.pod
             .spec
             .as_ref()
@@ -97,17 +170,18 @@ pub fn PodItem(props: PodItemProps) -> Element {
                 spec.containers
                     .iter()
                     .map(|c| {
+                        let status = container_statuses.iter().find(|status| status.name == c.name);
+                        let (state, reason) = container_state(status);
+                        let requests = c.resources.as_ref().and_then(|res| res.requests.as_ref());
+                        let limits = c.resources.as_ref().and_then(|res| res.limits.as_ref());
                         ContainerData {
                             name: c.name.clone(),
                             image: c.image.clone().unwrap_or_default(),
-                            status: "Unknown".to_string(), // Placeholder
-                            cpu_usage: 0.0,                // Placeholder
-                            memory_usage: "12Mi".to_string(), // Placeholder
-                            memory_limit: c
-                                .resources
-                                .as_ref()
-                                .and_then(|res| res.limits.as_ref())
-                                .and_then(|limits| limits.get("memory").cloned()),
+                            status: state,
+                            reason,
+                            restart_count: status.map(|status| status.restart_count).unwrap_or(0),
+                            cpu: ResourceAllocation::new("cpu", requests, limits),
+                            memory: ResourceAllocation::new("memory", requests, limits),
                         }
                     })
                     .collect()
@@ -139,6 +213,13 @@ pub fn PodItem(props: PodItemProps) -> Element {
             .unwrap_or_default(),
     };
 
+    let metrics_history = use_context::<Signal<MetricsHistory>>();
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", PODS, Some(&pod_data.namespace));
+    let can_view_logs = access_context.can("get", POD_LOGS, Some(&pod_data.namespace));
+    let can_port_forward = access_context.can("create", POD_PORT_FORWARD, Some(&pod_data.namespace));
+    let can_exec = access_context.can("create", POD_EXEC, Some(&pod_data.namespace));
+
     rsx! {
         // Add Tailwind: padding
         div {
@@ -151,6 +232,49 @@ pub fn PodItem(props: PodItemProps) -> Element {
                     span { class: "status-badge status-{pod_data.status.to_lowercase()}", "{pod_data.status}" }
                 }
                 div { class: "pod-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            if logs_container.read().is_some() {
+                                logs_container.set(None);
+                            } else {
+                                logs_container.set(Some(default_container.clone()));
+                            }
+                        },
+                        disabled: !can_view_logs,
+                        title: if can_view_logs { "View logs" } else { "You're not allowed to read pod logs" },
+                        "📜"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_port_forward.set(!show_port_forward());
+                        },
+                        disabled: !can_port_forward,
+                        title: if can_port_forward { "Port forward" } else { "You're not allowed to port forward to pods" },
+                        "🔌"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -163,6 +287,69 @@ pub fn PodItem(props: PodItemProps) -> Element {
                 }
             }
 
+            {logs_container().map(|container| {
+                let containers = container_names(&props.pod);
+                let previous = prefers_previous_logs(&props.pod, &container);
+                rsx! {
+                    div { class: "pod-logs-section",
+                        PodLogPanel {
+                            key: "{pod_data.name}-{container}",
+                            namespace: pod_data.namespace.clone(),
+                            pod_name: pod_data.name.clone(),
+                            containers,
+                            initial_container: container,
+                            initial_previous: previous,
+                            on_close: move |_| logs_container.set(None),
+                        }
+                    }
+                }
+            })}
+
+            {show_port_forward().then(|| rsx! {
+                div { class: "pod-port-forward-section",
+                    PortForwardForm {
+                        namespace: pod_data.namespace.clone(),
+                        target: ForwardTarget::Pod(pod_data.name.clone()),
+                        ports: container_ports.clone(),
+                        on_close: move |_| show_port_forward.set(false),
+                    }
+                }
+            })}
+
+            {terminal_container().map(|container| rsx! {
+                div { class: "pod-terminal-section",
+                    PodTerminal {
+                        key: "{pod_data.name}-{container}-terminal",
+                        namespace: pod_data.namespace.clone(),
+                        pod_name: pod_data.name.clone(),
+                        container,
+                        on_close: move |_| terminal_container.set(None),
+                    }
+                }
+            })}
+
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<Pod>(&()),
+                        namespace: Some(pod_data.namespace.clone()),
+                        name: pod_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<Pod>(&()),
+                    namespace: Some(pod_data.namespace.clone()),
+                    name: pod_data.name.clone(),
+                    allow_force: true,
+                    terminating: is_terminating,
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "pod-details",
                     div { class: "pod-info",
@@ -196,6 +383,13 @@ pub fn PodItem(props: PodItemProps) -> Element {
                         }
                     }
 
+                    PrometheusCharts {
+                        scope: PromScope::Pod {
+                            namespace: pod_data.namespace.clone(),
+                            name: pod_data.name.clone(),
+                        },
+                    }
+
                     div { class: "labels-section",
                         h4 { "Labels" }
                         div { class: "labels-grid",
@@ -214,18 +408,17 @@ pub fn PodItem(props: PodItemProps) -> Element {
                         h4 { "Containers ({pod_data.ready_containers.0}/{pod_data.ready_containers.1})" }
                         div { class: "containers-grid",
                             {pod_data.containers.iter().map(|container| {
-                                let memory_usage = parse_quantity_string(&container.memory_usage);
-
-                                let memory_limit = container.memory_limit
-                                    .as_ref()
-                                    .map(|q| parse_quantity_string(&q.0))
-                                    .unwrap_or(0.0);
-
-                                let percent = if memory_limit > 0.0 {
-                                    (memory_usage / memory_limit) * 100.0
-                                } else {
-                                    0.0
+                                let series = MetricsSeries::Container {
+                                    namespace: pod_data.namespace.clone(),
+                                    pod: pod_data.name.clone(),
+                                    container: container.name.clone(),
                                 };
+                                // Only this round's figures, so a restarted container doesn't show its old ones
+                                let usage = metrics_history.read().current_sample(&series);
+                                let metrics = [
+                                    ("CPU", usage.map(|u| u.cpu), &container.cpu, format_cpu as fn(f64) -> String),
+                                    ("Memory", usage.map(|u| u.memory), &container.memory, format_memory),
+                                ];
 
                                 // Extract limits and requests for display
                                 let (limits_str, requests_str) = props.pod
@@ -266,30 +459,82 @@ pub fn PodItem(props: PodItemProps) -> Element {
                                                 h5 { "{container.name}" }
                                                 span { class: "container-image", "{container.image}" }
                                             }
-                                            span { class: "status-badge status-{container.status.to_lowercase()}", "{container.status}" }
-                                        }
-                                        div { class: "resource-metrics",
-                                            div { class: "metric",
-                                                span { class: "metric-label", "CPU" }
-                                                div { class: "progress-bar",
-                                                    div {
-                                                        class: "progress-fill",
-                                                        style: "width: {container.cpu_usage * 100.0}%"
+                                            div { class: "container-actions",
+                                                button {
+                                                    class: "btn-icon",
+                                                    disabled: !can_view_logs,
+                                                    title: "View logs for {container.name}",
+                                                    onclick: {
+                                                        let name = container.name.clone();
+                                                        move |_| logs_container.set(Some(name.clone()))
+                                                    },
+                                                    "📜"
+                                                }
+                                                if is_container_running(&props.pod, &container.name) {
+                                                    button {
+                                                        class: "btn-icon",
+                                                        disabled: !can_exec,
+                                                        title: "Open shell in {container.name}",
+                                                        onclick: {
+                                                            let name = container.name.clone();
+                                                            move |_| terminal_container.set(Some(name.clone()))
+                                                        },
+                                                        ">_"
+                                                    }
+                                                }
+                                                if container.restart_count > 0 {
+                                                    span { class: "container-restarts", "{container.restart_count} restarts" }
+                                                }
+                                                span {
+                                                    class: "status-badge status-{container.status.to_lowercase()}",
+                                                    "{container.status}"
+                                                    if let Some(reason) = &container.reason {
+                                                        ": {reason}"
                                                     }
                                                 }
-                                                span { class: "metric-value", "{container.cpu_usage * 100.0:.1}%" }
                                             }
-                                            div { class: "metric",
-                                                span { class: "metric-label", "Memory" }
-                                                div { class: "progress-bar",
-                                                    div {
-                                                        class: "progress-fill",
-                                                        style: "width: {percent}%"
+                                        }
+                                        div { class: "resource-metrics",
+                                            {metrics.into_iter().map(|(label, used, allocation, format)| {
+                                                let of_request = used.and_then(|used| percent(used, allocation.request));
+                                                let of_limit = used.and_then(|used| percent(used, allocation.limit));
+                                                // Fill against the limit, or the request when there's no limit
+                                                let fill = of_limit.or(of_request).unwrap_or(0.0).min(100.0);
+                                                let value = used.map(format).unwrap_or_else(|| "–".to_string());
+                                                let describe = |share: Option<f64>, total: Option<f64>, name: &str| match (share, total) {
+                                                    (Some(share), _) => format!("{share}% of {name}"),
+                                                    (None, Some(total)) => format!("{name} {}", format(total)),
+                                                    (None, None) => format!("no {name}"),
+                                                };
+                                                let of_request = describe(of_request, allocation.request, "request");
+                                                let of_limit = describe(of_limit, allocation.limit, "limit");
+                                                rsx! {
+                                                    div { key: "{label}", class: "metric-group",
+                                                        div { class: "metric",
+                                                            span { class: "metric-label", "{label}" }
+                                                            div { class: "progress-bar",
+                                                                div {
+                                                                    class: "progress-fill",
+                                                                    style: "width: {fill}%"
+                                                                }
+                                                            }
+                                                            span { class: "metric-value", "{value}" }
+                                                        }
+                                                        div { class: "metric-allocation", "{of_request} · {of_limit}" }
                                                     }
                                                 }
-                                                span { class: "metric-value", "{percent}%" }
+                                            })}
+                                            if let Some(storage) = usage.and_then(|u| u.storage) {
+                                                div { class: "metric-allocation", "Ephemeral storage: {format_memory(storage)}" }
                                             }
                                         }
+                                        MetricsTrend {
+                                            series: MetricsSeries::Container {
+                                                namespace: pod_data.namespace.clone(),
+                                                pod: pod_data.name.clone(),
+                                                container: container.name.clone(),
+                                            },
+                                        }
                                         div { class: "container-limits-requests",
                                             div { class: "limits",
                                                 span { class: "limits-label", "Resource Limits:" }
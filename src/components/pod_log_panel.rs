@@ -0,0 +1,219 @@
+use dioxus::prelude::*;
+use futures::StreamExt;
+use kube::Client;
+
+use crate::k8s::{stream_pod_logs, LogOptions, MAX_LOG_LINES};
+
+const POD_LOGS_CSS: Asset = asset!("/assets/styling/pod_logs.css");
+
+// (label, seconds) pairs for the "since" selector, 0 meaning no limit
+const SINCE_OPTIONS: &[(&str, i64)] = &[
+    ("All time", 0),
+    ("5m", 300),
+    ("15m", 900),
+    ("1h", 3600),
+    ("6h", 21600),
+    ("24h", 86400),
+];
+
+#[derive(Props, PartialEq, Clone)]
+pub struct PodLogPanelProps {
+    pub namespace: String,
+    pub pod_name: String,
+    pub containers: Vec<String>,
+    #[props(optional)]
+    pub initial_container: Option<String>,
+    #[props(default = false)]
+    pub initial_previous: bool,
+    #[props(optional)]
+    pub on_close: Option<EventHandler<()>>,
+}
+
+#[component]
+pub fn PodLogPanel(props: PodLogPanelProps) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+
+    let mut selected_container = use_signal(|| {
+        props.initial_container.clone()
+            .or_else(|| props.containers.first().cloned())
+            .unwrap_or_default()
+    });
+    let mut follow = use_signal(|| !props.initial_previous);
+    let mut previous = use_signal(|| props.initial_previous);
+    let mut timestamps = use_signal(|| false);
+    let mut tail_lines = use_signal(|| "500".to_string());
+    let mut since_seconds = use_signal(|| 0i64);
+    let mut reload = use_signal(|| 0u32);
+
+    let mut lines = use_signal(Vec::<String>::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut is_streaming = use_signal(|| false);
+    let mut log_task = use_signal(|| None::<Task>);
+
+    // (Re)open the log stream whenever any of the options change
+    use_effect({
+        let namespace = props.namespace.clone();
+        let pod_name = props.pod_name.clone();
+        move || {
+            let options = LogOptions {
+                container: Some(selected_container()).filter(|c| !c.is_empty()),
+                follow: follow(),
+                previous: previous(),
+                timestamps: timestamps(),
+                tail_lines: tail_lines().trim().parse().ok(),
+                since_seconds: Some(since_seconds()),
+            };
+            let _ = reload();
+
+            if let Some(task) = log_task.write().take() {
+                task.cancel();
+            }
+            lines.set(Vec::new());
+            error.set(None);
+
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                return;
+            };
+
+            let namespace = namespace.clone();
+            let pod_name = pod_name.clone();
+            is_streaming.set(true);
+            let task = spawn(async move {
+                match stream_pod_logs(client, &namespace, &pod_name, &options).await {
+                    Ok(mut stream) => {
+                        while let Some(line) = stream.next().await {
+                            match line {
+                                Ok(line) => {
+                                    let mut buffer = lines.write();
+                                    buffer.push(line);
+                                    if buffer.len() > MAX_LOG_LINES {
+                                        let overflow = buffer.len() - MAX_LOG_LINES;
+                                        buffer.drain(..overflow);
+                                    }
+                                }
+                                Err(e) => {
+                                    error.set(Some(format!("Log stream interrupted: {}", e)));
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error.set(Some(format!("Failed to fetch logs: {}", e)));
+                    }
+                }
+                is_streaming.set(false);
+            });
+            log_task.set(Some(task));
+        }
+    });
+
+    let log_text = lines.read().join("\n");
+    let line_count = lines.read().len();
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: POD_LOGS_CSS }
+        div { class: "log-panel",
+            div { class: "log-toolbar",
+                div { class: "log-toolbar-group",
+                    if props.containers.len() > 1 {
+                        select {
+                            class: "status-select log-container-select",
+                            value: "{selected_container}",
+                            onchange: move |evt| selected_container.set(evt.value()),
+                            {props.containers.iter().map(|name| rsx! {
+                                option { key: "{name}", value: "{name}", "{name}" }
+                            })}
+                        }
+                    } else {
+                        span { class: "log-container-name", "{selected_container}" }
+                    }
+                    label { class: "log-toggle",
+                        input {
+                            r#type: "checkbox",
+                            checked: follow(),
+                            disabled: previous(),
+                            onchange: move |evt| follow.set(evt.checked()),
+                        }
+                        "Follow"
+                    }
+                    label { class: "log-toggle",
+                        input {
+                            r#type: "checkbox",
+                            checked: previous(),
+                            onchange: move |evt| previous.set(evt.checked()),
+                        }
+                        "Previous"
+                    }
+                    label { class: "log-toggle",
+                        input {
+                            r#type: "checkbox",
+                            checked: timestamps(),
+                            onchange: move |evt| timestamps.set(evt.checked()),
+                        }
+                        "Timestamps"
+                    }
+                }
+                div { class: "log-toolbar-group",
+                    label { class: "log-field",
+                        "Tail"
+                        input {
+                            class: "log-number-input",
+                            r#type: "number",
+                            min: "0",
+                            placeholder: "all",
+                            value: "{tail_lines}",
+                            onchange: move |evt| tail_lines.set(evt.value()),
+                        }
+                    }
+                    label { class: "log-field",
+                        "Since"
+                        select {
+                            class: "status-select log-since-select",
+                            value: "{since_seconds}",
+                            onchange: move |evt| since_seconds.set(evt.value().parse().unwrap_or(0)),
+                            {SINCE_OPTIONS.iter().map(|(label, secs)| rsx! {
+                                option { key: "{secs}", value: "{secs}", "{label}" }
+                            })}
+                        }
+                    }
+                    button {
+                        class: "btn btn-secondary",
+                        onclick: move |_| reload += 1,
+                        "Reload"
+                    }
+                    if let Some(on_close) = props.on_close {
+                        button {
+                            class: "btn-icon",
+                            title: "Close logs",
+                            onclick: move |_| on_close.call(()),
+                            "✕"
+                        }
+                    }
+                }
+            }
+
+            {error().map(|err| rsx! {
+                div { class: "log-error", "{err}" }
+            })}
+
+            div { class: "log-output",
+                if line_count == 0 && error().is_none() {
+                    div { class: "log-empty",
+                        if is_streaming() { "Waiting for log output..." } else { "No log output" }
+                    }
+                } else {
+                    pre { class: "log-text", "{log_text}" }
+                }
+            }
+
+            div { class: "log-status",
+                span { "{line_count} lines" }
+                if is_streaming() && follow() && !previous() {
+                    span { class: "log-live", "● Live" }
+                }
+            }
+        }
+    }
+}
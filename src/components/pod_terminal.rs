@@ -0,0 +1,241 @@
+use dioxus::prelude::*;
+use futures::StreamExt;
+use kube::Client;
+
+use crate::k8s::{start_exec_session, ExecOptions, ExecSession, TerminalInput, TerminalOutput};
+use crate::utils::terminal_utils::TerminalBuffer;
+
+const POD_TERMINAL_CSS: Asset = asset!("/assets/styling/pod_terminal.css");
+
+// Approximate glyph size of the terminal font, used to turn the element size into rows and columns
+const CHAR_WIDTH_PX: f64 = 7.2;
+const LINE_HEIGHT_PX: f64 = 18.0;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct PodTerminalProps {
+    pub namespace: String,
+    pub pod_name: String,
+    pub container: String,
+    #[props(optional)]
+    pub on_close: Option<EventHandler<()>>,
+}
+
+#[component]
+pub fn PodTerminal(props: PodTerminalProps) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+
+    let mut buffer = use_signal(TerminalBuffer::default);
+    let mut session = use_signal(|| None::<ExecSession>);
+    let mut session_task = use_signal(|| None::<Task>);
+    let mut error = use_signal(|| None::<String>);
+    let mut is_connected = use_signal(|| false);
+    let mut tty = use_signal(|| true);
+    let mut reconnect = use_signal(|| 0u32);
+    // Line being typed in non-TTY mode, where there is no remote echo or line editing
+    let mut line_input = use_signal(String::new);
+    let mut terminal_size = use_signal(|| None::<(u16, u16)>);
+
+    // (Re)start the shell when the TTY mode changes or the user reconnects
+    use_effect({
+        let namespace = props.namespace.clone();
+        let pod_name = props.pod_name.clone();
+        let container = props.container.clone();
+        move || {
+            let options = ExecOptions {
+                container: Some(container.clone()),
+                tty: tty(),
+                ..Default::default()
+            };
+            let _ = reconnect();
+
+            if let Some(task) = session_task.write().take() {
+                task.cancel();
+            }
+            // Dropping the previous session terminates its remote process
+            session.set(None);
+            buffer.set(TerminalBuffer::default());
+            line_input.set(String::new());
+            error.set(None);
+
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                return;
+            };
+
+            let namespace = namespace.clone();
+            let pod_name = pod_name.clone();
+            let task = spawn(async move {
+                match start_exec_session(client, &namespace, &pod_name, &options).await {
+                    Ok((new_session, mut output)) => {
+                        if let Some((cols, rows)) = *terminal_size.peek() {
+                            new_session.send(TerminalInput::Resize { cols, rows });
+                        }
+                        session.set(Some(new_session));
+                        is_connected.set(true);
+                        while let Some(chunk) = output.next().await {
+                            match chunk {
+                                TerminalOutput::Stdout(bytes) | TerminalOutput::Stderr(bytes) => {
+                                    buffer.write().feed(&bytes);
+                                }
+                                TerminalOutput::Exited(message) => {
+                                    buffer.write().push_message(&format!("[{}]", message));
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error.set(Some(format!("Failed to start shell: {}", e)));
+                    }
+                }
+                is_connected.set(false);
+            });
+            session_task.set(Some(task));
+        }
+    });
+
+    let send = move |input: TerminalInput| {
+        if let Some(session) = &*session.read() {
+            session.send(input);
+        }
+    };
+
+    let onkeydown = move |evt: KeyboardEvent| {
+        let key = evt.key();
+        let modifiers = evt.modifiers();
+        // Leave copy/paste shortcuts to the webview
+        if modifiers.meta() || (modifiers.ctrl() && modifiers.shift()) {
+            return;
+        }
+        evt.prevent_default();
+
+        if tty() {
+            if let Some(bytes) = key_to_bytes(&key, modifiers.ctrl()) {
+                send(TerminalInput::Data(bytes));
+            }
+            return;
+        }
+
+        match key {
+            Key::Enter => {
+                let line = std::mem::take(&mut *line_input.write());
+                buffer.write().feed(format!("{}\n", line).as_bytes());
+                send(TerminalInput::Data(format!("{}\n", line).into_bytes()));
+            }
+            Key::Backspace => {
+                line_input.write().pop();
+            }
+            Key::Character(c) if !modifiers.ctrl() => line_input.write().push_str(&c),
+            _ => {}
+        }
+    };
+
+    let onresize = move |evt: ResizeEvent| {
+        let Ok(size) = evt.get_content_box_size() else { return };
+        let cols = (size.width / CHAR_WIDTH_PX).floor().max(1.0) as u16;
+        let rows = (size.height / LINE_HEIGHT_PX).floor().max(1.0) as u16;
+        if *terminal_size.peek() == Some((cols, rows)) {
+            return;
+        }
+        terminal_size.set(Some((cols, rows)));
+        send(TerminalInput::Resize { cols, rows });
+    };
+
+    let output_text = buffer.read().text();
+    let size_label = terminal_size()
+        .map(|(cols, rows)| format!("{}x{}", cols, rows))
+        .unwrap_or_default();
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: POD_TERMINAL_CSS }
+        div { class: "terminal-panel",
+            div { class: "terminal-toolbar",
+                div { class: "terminal-toolbar-group",
+                    span { class: "terminal-title", "{props.container}" }
+                    label { class: "terminal-toggle",
+                        input {
+                            r#type: "checkbox",
+                            checked: tty(),
+                            onchange: move |evt| tty.set(evt.checked()),
+                        }
+                        "TTY"
+                    }
+                }
+                div { class: "terminal-toolbar-group",
+                    button {
+                        class: "btn btn-secondary",
+                        onclick: move |_| reconnect += 1,
+                        "Reconnect"
+                    }
+                    if let Some(on_close) = props.on_close {
+                        button {
+                            class: "btn-icon",
+                            title: "Close terminal",
+                            onclick: move |_| on_close.call(()),
+                            "✕"
+                        }
+                    }
+                }
+            }
+
+            {error().map(|err| rsx! {
+                div { class: "terminal-error", "{err}" }
+            })}
+
+            div {
+                class: "terminal-output",
+                tabindex: "0",
+                autofocus: true,
+                onkeydown,
+                onresize,
+                pre { class: "terminal-text",
+                    "{output_text}"
+                    if !tty() {
+                        "{line_input}"
+                    }
+                    span { class: "terminal-cursor", " " }
+                }
+            }
+
+            div { class: "terminal-status",
+                span { "{props.namespace}/{props.pod_name} {size_label}" }
+                if is_connected() {
+                    span { class: "terminal-connected", "● Connected" }
+                } else {
+                    span { "Disconnected" }
+                }
+            }
+        }
+    }
+}
+
+/// Translate a key press into the bytes a terminal would send for it
+fn key_to_bytes(key: &Key, ctrl: bool) -> Option<Vec<u8>> {
+    let bytes: &[u8] = match key {
+        Key::Character(c) if ctrl => {
+            let c = c.chars().next()?.to_ascii_lowercase();
+            return match c {
+                'a'..='z' => Some(vec![c as u8 - b'a' + 1]),
+                '[' => Some(vec![0x1b]),
+                '\\' => Some(vec![0x1c]),
+                ']' => Some(vec![0x1d]),
+                _ => None,
+            };
+        }
+        Key::Character(c) => return Some(c.as_bytes().to_vec()),
+        Key::Enter => b"\r",
+        Key::Backspace => b"\x7f",
+        Key::Tab => b"\t",
+        Key::Escape => b"\x1b",
+        Key::ArrowUp => b"\x1b[A",
+        Key::ArrowDown => b"\x1b[B",
+        Key::ArrowRight => b"\x1b[C",
+        Key::ArrowLeft => b"\x1b[D",
+        Key::Home => b"\x1b[H",
+        Key::End => b"\x1b[F",
+        Key::Delete => b"\x1b[3~",
+        Key::PageUp => b"\x1b[5~",
+        Key::PageDown => b"\x1b[6~",
+        _ => return None,
+    };
+    Some(bytes.to_vec())
+}
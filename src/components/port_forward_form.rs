@@ -0,0 +1,128 @@
+use dioxus::prelude::*;
+use kube::Client;
+
+use crate::contexts::PortForwardContext;
+use crate::k8s::{ForwardTarget, PortForwardRequest};
+
+const PORT_FORWARDS_CSS: Asset = asset!("/assets/styling/port_forwards.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct PortForwardFormProps {
+    pub namespace: String,
+    pub target: ForwardTarget,
+    /// Known ports on the target; container ports for pods, service ports for services
+    pub ports: Vec<u16>,
+    #[props(optional)]
+    pub on_close: Option<EventHandler<()>>,
+}
+
+#[component]
+pub fn PortForwardForm(props: PortForwardFormProps) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+    let port_forwards = use_context::<PortForwardContext>();
+
+    let first_port = props.ports.first().map(|p| p.to_string()).unwrap_or_default();
+    let mut remote_port = use_signal(|| first_port.clone());
+    let mut local_port = use_signal(|| first_port.clone());
+    let mut error = use_signal(|| None::<String>);
+
+    let start = {
+        let namespace = props.namespace.clone();
+        let target = props.target.clone();
+        let on_close = props.on_close;
+        move |_| {
+            let Ok(remote) = remote_port().trim().parse::<u16>() else {
+                error.set(Some("Remote port must be a number between 1 and 65535".to_string()));
+                return;
+            };
+            // An empty local port lets the OS pick a free one
+            let local = match local_port().trim() {
+                "" => 0,
+                value => match value.parse::<u16>() {
+                    Ok(port) => port,
+                    Err(_) => {
+                        error.set(Some("Local port must be a number between 0 and 65535".to_string()));
+                        return;
+                    }
+                },
+            };
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                return;
+            };
+
+            port_forwards.start(client, PortForwardRequest {
+                namespace: namespace.clone(),
+                target: target.clone(),
+                remote_port: remote,
+                local_port: local,
+            });
+            if let Some(on_close) = on_close {
+                on_close.call(());
+            }
+        }
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: PORT_FORWARDS_CSS }
+        div { class: "port-forward-form",
+            span { class: "port-forward-target", "{props.target}" }
+            label { class: "port-forward-field",
+                "Remote"
+                if props.ports.is_empty() {
+                    input {
+                        class: "port-forward-input",
+                        r#type: "number",
+                        min: "1",
+                        max: "65535",
+                        value: "{remote_port}",
+                        oninput: move |evt| remote_port.set(evt.value()),
+                    }
+                } else {
+                    select {
+                        class: "status-select port-forward-select",
+                        value: "{remote_port}",
+                        onchange: move |evt| {
+                            // Keep the local port in step unless the user changed it
+                            if local_port() == remote_port() {
+                                local_port.set(evt.value());
+                            }
+                            remote_port.set(evt.value());
+                        },
+                        {props.ports.iter().map(|port| rsx! {
+                            option { key: "{port}", value: "{port}", "{port}" }
+                        })}
+                    }
+                }
+            }
+            label { class: "port-forward-field",
+                "Local"
+                input {
+                    class: "port-forward-input",
+                    r#type: "number",
+                    min: "0",
+                    max: "65535",
+                    placeholder: "auto",
+                    value: "{local_port}",
+                    oninput: move |evt| local_port.set(evt.value()),
+                }
+            }
+            button {
+                class: "btn btn-primary",
+                onclick: start,
+                "Forward"
+            }
+            if let Some(on_close) = props.on_close {
+                button {
+                    class: "btn-icon",
+                    title: "Cancel",
+                    onclick: move |_| on_close.call(()),
+                    "✕"
+                }
+            }
+            {error().map(|err| rsx! {
+                span { class: "port-forward-error", "{err}" }
+            })}
+        }
+    }
+}
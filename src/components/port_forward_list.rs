@@ -0,0 +1,66 @@
+use dioxus::prelude::*;
+
+use crate::contexts::{PortForwardContext, PortForwardStatus};
+
+const PORT_FORWARDS_CSS: Asset = asset!("/assets/styling/port_forwards.css");
+
+/// Active port-forwards, shared across views through `PortForwardContext`
+#[component]
+pub fn PortForwardList() -> Element {
+    let port_forwards = use_context::<PortForwardContext>();
+    let forwards = port_forwards.forwards.read().clone();
+
+    if forwards.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: PORT_FORWARDS_CSS }
+        div { class: "port-forward-list",
+            h4 { "Port Forwards ({forwards.len()})" }
+            {forwards.into_iter().map(|forward| {
+                let id = forward.id;
+                let local = forward.local_port
+                    .map(|port| format!("localhost:{}", port))
+                    .unwrap_or_else(|| "…".to_string());
+                let remote = format!(
+                    "{}:{}",
+                    forward.pod_name.clone().unwrap_or_else(|| forward.request.target.to_string()),
+                    forward.request.remote_port
+                );
+                let (status_class, status_text) = match &forward.status {
+                    PortForwardStatus::Starting => ("status-pending", "Starting".to_string()),
+                    PortForwardStatus::Active => ("status-running", "Active".to_string()),
+                    PortForwardStatus::Failed(_) => ("status-failed", "Failed".to_string()),
+                };
+                let error = match &forward.status {
+                    PortForwardStatus::Failed(e) => Some(e.clone()),
+                    _ => forward.last_error.clone(),
+                };
+                let is_failed = matches!(forward.status, PortForwardStatus::Failed(_));
+                rsx! {
+                    div {
+                        key: "{id}",
+                        class: "port-forward-row",
+                        div { class: "port-forward-main",
+                            span { class: "status-badge {status_class}", "{status_text}" }
+                            span { class: "port-forward-target", "{forward.request.namespace}/{forward.request.target}" }
+                            span { class: "port-forward-mapping", "{local} → {remote}" }
+                            span { class: "port-forward-connections",
+                                "{forward.active_connections} active / {forward.total_connections} total"
+                            }
+                            button {
+                                class: "btn btn-secondary",
+                                onclick: move |_| port_forwards.stop(id),
+                                if is_failed { "Dismiss" } else { "Stop" }
+                            }
+                        }
+                        {error.map(|err| rsx! {
+                            div { class: "port-forward-error", "{err}" }
+                        })}
+                    }
+                }
+            })}
+        }
+    }
+}
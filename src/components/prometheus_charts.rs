@@ -0,0 +1,429 @@
+use dioxus::prelude::*;
+use futures::future::join_all;
+
+use crate::contexts::error::AppError;
+use crate::contexts::PrometheusContext;
+use crate::k8s::{
+    format_cpu, format_memory, prom_panels, PromScope, PromSeries, PromUnit, PrometheusEndpoint, QueryWindow,
+};
+
+const PROMETHEUS_CSS: Asset = asset!("/assets/styling/prometheus.css");
+
+/// Lines drawn per ad-hoc query before the rest are left out
+const MAX_LINES: usize = 8;
+
+fn format_value(unit: PromUnit, value: f64) -> String {
+    const KIB: f64 = 1024.0;
+    match unit {
+        PromUnit::Cores => format_cpu(value),
+        PromUnit::Bytes => format_memory(value),
+        PromUnit::BytesPerSecond if value >= KIB * KIB => format!("{:.1} MiB/s", value / (KIB * KIB)),
+        PromUnit::BytesPerSecond if value >= KIB => format!("{:.1} KiB/s", value / KIB),
+        PromUnit::BytesPerSecond => format!("{:.0} B/s", value),
+        PromUnit::PerHour => format!("{:.1}/h", value),
+        PromUnit::Plain => format!("{}", (value * 1000.0).round() / 1000.0),
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct TimeChartProps {
+    /// Legend label and series of each line
+    lines: Vec<(String, PromSeries)>,
+    unit: PromUnit,
+}
+
+/// Lines over time on shared axes, with each line's latest and highest value in the legend
+#[component]
+pub fn TimeChart(props: TimeChartProps) -> Element {
+    let (mut first, mut last) = (i64::MAX, i64::MIN);
+    let (mut low, mut high) = (0.0f64, 0.0f64);
+    for (at, value) in props.lines.iter().flat_map(|(_, series)| series.points.iter()) {
+        first = first.min(at.timestamp_millis());
+        last = last.max(at.timestamp_millis());
+        low = low.min(*value);
+        high = high.max(*value);
+    }
+    if first > last {
+        return rsx! { div { class: "prom-chart-empty", "No data in this window" } };
+    }
+    let span = (last - first).max(1) as f64;
+    let range = if high > low { high - low } else { 1.0 };
+    let to_points = |series: &PromSeries| {
+        series.points
+            .iter()
+            .map(|(at, value)| {
+                let x = (at.timestamp_millis() - first) as f64 / span * 300.0;
+                let y = 98.0 - (value - low) / range * 96.0;
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let unit = props.unit;
+
+    rsx! {
+        div { class: "prom-chart",
+            span { class: "prom-axis-max", "{format_value(unit, high)}" }
+            svg {
+                class: "prom-chart-plot",
+                view_box: "0 0 300 100",
+                preserve_aspect_ratio: "none",
+                {props.lines.iter().enumerate().map(|(i, (_, series))| rsx! {
+                    polyline {
+                        key: "{i}",
+                        class: "prom-line prom-line-{i % 4}",
+                        points: to_points(series),
+                        fill: "none",
+                        stroke_width: "1.5",
+                        vector_effect: "non-scaling-stroke",
+                    }
+                })}
+            }
+            div { class: "prom-legend",
+                {props.lines.iter().enumerate().map(|(i, (label, series))| {
+                    let latest = series.points.last().map(|(_, value)| format_value(unit, *value));
+                    let max = series.points.iter().map(|(_, value)| *value).reduce(f64::max);
+                    let values = match (latest, max) {
+                        (Some(latest), Some(max)) => format!("{} (max {})", latest, format_value(unit, max)),
+                        _ => "no data".to_string(),
+                    };
+                    rsx! {
+                        div { key: "{i}", class: "prom-legend-item",
+                            span { class: "prom-swatch prom-line-{i % 4}" }
+                            span { class: "prom-legend-label", title: "{label}", "{label}" }
+                            span { class: "prom-legend-value", "{values}" }
+                        }
+                    }
+                })}
+            }
+        }
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct WindowPickerProps {
+    window: Signal<QueryWindow>,
+}
+
+#[component]
+fn WindowPicker(props: WindowPickerProps) -> Element {
+    let mut window = props.window;
+    rsx! {
+        div { class: "prom-windows",
+            for option in QueryWindow::ALL {
+                button {
+                    key: "{option.label()}",
+                    class: if window() == option { "prom-window active" } else { "prom-window" },
+                    onclick: move |_| window.set(option),
+                    "{option.label()}"
+                }
+            }
+        }
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct PrometheusChartsProps {
+    scope: PromScope,
+    /// Show the charts straight away instead of behind a toggle
+    #[props(default)]
+    expanded: bool,
+}
+
+/// CPU, memory, restart and network history for a cluster, node or pod from the context's
+/// Prometheus. Renders nothing when no Prometheus is set up.
+#[component]
+pub fn PrometheusCharts(props: PrometheusChartsProps) -> Element {
+    let client = use_context::<PrometheusContext>().client;
+    let mut expanded = use_signal(|| props.expanded);
+    let window = use_signal(|| QueryWindow::Hour);
+
+    let scope = props.scope.clone();
+    let panels = use_resource(use_reactive!(|scope| async move {
+        let (Some(prometheus), true) = (client(), expanded()) else {
+            return Vec::new();
+        };
+        let window = window();
+        join_all(prom_panels(&scope).into_iter().map(|panel| {
+            let prometheus = prometheus.clone();
+            async move {
+                let results =
+                    join_all(panel.queries.iter().map(|(_, query)| prometheus.query_range(query, window))).await;
+                let lines: Result<Vec<(String, PromSeries)>, AppError> = panel.queries
+                    .iter()
+                    .zip(results)
+                    .map(|((label, _), result)| {
+                        result.map(|series| series.into_iter().map(|s| (label.to_string(), s)).collect::<Vec<_>>())
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|lines| lines.into_iter().flatten().collect());
+                (panel, lines)
+            }
+        }))
+        .await
+    }));
+
+    if client.read().is_none() {
+        return rsx! {};
+    }
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: PROMETHEUS_CSS }
+        div { class: "prom-charts",
+            div { class: "prom-charts-header",
+                button {
+                    class: "btn btn-secondary prom-toggle",
+                    onclick: move |_| expanded.set(!expanded()),
+                    if expanded() { "Hide Prometheus history" } else { "Show Prometheus history" }
+                }
+                if expanded() {
+                    WindowPicker { window }
+                }
+            }
+            if expanded() {
+                {match &*panels.read() {
+                    None => rsx! { div { class: "prom-loading", "Querying Prometheus…" } },
+                    Some(results) => rsx! {
+                        div { class: "prom-chart-grid",
+                            for (panel, lines) in results.iter() {
+                                div { key: "{panel.title}", class: "prom-chart-card",
+                                    h4 { "{panel.title}" }
+                                    {match lines {
+                                        Ok(lines) => rsx! { TimeChart { lines: lines.clone(), unit: panel.unit } },
+                                        Err(error) => rsx! { div { class: "prom-chart-error", "{error}" } },
+                                    }}
+                                }
+                            }
+                        }
+                    },
+                }}
+            }
+        }
+    }
+}
+
+/// Ad-hoc PromQL range queries against the context's Prometheus
+#[component]
+pub fn PromQlPanel() -> Element {
+    let client = use_context::<PrometheusContext>().client;
+    let mut query = use_signal(String::new);
+    let window = use_signal(|| QueryWindow::Hour);
+    let mut result = use_signal(|| None::<Result<Vec<PromSeries>, AppError>>);
+    let mut running = use_signal(|| false);
+
+    let mut run = move || {
+        let query = query();
+        let Some(prometheus) = client.peek().clone() else { return };
+        if query.trim().is_empty() || running() {
+            return;
+        }
+        running.set(true);
+        spawn(async move {
+            result.set(Some(prometheus.query_range(&query, window()).await));
+            running.set(false);
+        });
+    };
+
+    if client.read().is_none() {
+        return rsx! {};
+    }
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: PROMETHEUS_CSS }
+        div { class: "promql-panel",
+            h4 { "PromQL" }
+            div { class: "promql-controls",
+                input {
+                    class: "promql-input",
+                    r#type: "text",
+                    placeholder: "sum by (namespace) (rate(container_cpu_usage_seconds_total[5m]))",
+                    value: "{query}",
+                    oninput: move |evt| query.set(evt.value()),
+                    onkeydown: move |evt| {
+                        if evt.key() == Key::Enter {
+                            run();
+                        }
+                    },
+                }
+                WindowPicker { window }
+                button {
+                    class: "btn btn-primary",
+                    disabled: running() || query().trim().is_empty(),
+                    onclick: move |_| run(),
+                    if running() { "Running…" } else { "Run" }
+                }
+            }
+            {match &*result.read() {
+                None => rsx! {},
+                Some(Err(error)) => rsx! { div { class: "prom-chart-error", "{error}" } },
+                Some(Ok(series)) if series.is_empty() => rsx! { div { class: "prom-chart-empty", "No series matched" } },
+                Some(Ok(series)) => rsx! {
+                    if series.len() > MAX_LINES {
+                        div { class: "promql-note", "Showing {MAX_LINES} of {series.len()} series" }
+                    }
+                    TimeChart {
+                        lines: series.iter().take(MAX_LINES).map(|s| (s.name(), s.clone())).collect::<Vec<_>>(),
+                        unit: PromUnit::Plain,
+                    }
+                },
+            }}
+        }
+    }
+}
+
+/// Choose, change or remove the Prometheus endpoint of the connected context
+#[component]
+pub fn PrometheusSetup() -> Element {
+    let prometheus = use_context::<PrometheusContext>();
+    let mut editing = use_signal(|| false);
+    let mut in_cluster = use_signal(|| true);
+    let mut url = use_signal(|| "http://localhost:9090".to_string());
+    let mut namespace = use_signal(|| "monitoring".to_string());
+    let mut service = use_signal(|| "prometheus-operated".to_string());
+    let mut port = use_signal(|| "9090".to_string());
+    let mut error = use_signal(|| None::<String>);
+
+    if prometheus.key.read().is_none() {
+        return rsx! {};
+    }
+    let endpoint = prometheus.endpoint.read().clone();
+
+    let save = {
+        let prometheus = prometheus.clone();
+        move |_| {
+            let endpoint = if in_cluster() {
+                PrometheusEndpoint::Service {
+                    namespace: namespace().trim().to_string(),
+                    name: service().trim().to_string(),
+                    port: port().trim().to_string(),
+                }
+            } else {
+                PrometheusEndpoint::Url { url: url().trim().to_string() }
+            };
+            let incomplete = match &endpoint {
+                PrometheusEndpoint::Url { url } => url.is_empty(),
+                PrometheusEndpoint::Service { namespace, name, port } => {
+                    namespace.is_empty() || name.is_empty() || port.is_empty()
+                }
+            };
+            if incomplete {
+                error.set(Some("Fill in every field".to_string()));
+                return;
+            }
+            match prometheus.save(Some(endpoint)) {
+                Ok(()) => {
+                    editing.set(false);
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    };
+    let remove = {
+        let prometheus = prometheus.clone();
+        move |_| {
+            if let Err(e) = prometheus.save(None) {
+                error.set(Some(e));
+            }
+        }
+    };
+    let edit = {
+        let endpoint = endpoint.clone();
+        move |_| {
+            match &endpoint {
+                Some(PrometheusEndpoint::Url { url: current }) => {
+                    in_cluster.set(false);
+                    url.set(current.clone());
+                }
+                Some(PrometheusEndpoint::Service { namespace: ns, name, port: p }) => {
+                    in_cluster.set(true);
+                    namespace.set(ns.clone());
+                    service.set(name.clone());
+                    port.set(p.clone());
+                }
+                None => {}
+            }
+            editing.set(true);
+        }
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: PROMETHEUS_CSS }
+        div { class: "prom-setup",
+            if editing() {
+                div { class: "prom-setup-form",
+                    div { class: "prom-setup-modes",
+                        label {
+                            input {
+                                r#type: "radio",
+                                name: "prometheus-mode",
+                                checked: in_cluster(),
+                                onchange: move |_| in_cluster.set(true),
+                            }
+                            "In-cluster service"
+                        }
+                        label {
+                            input {
+                                r#type: "radio",
+                                name: "prometheus-mode",
+                                checked: !in_cluster(),
+                                onchange: move |_| in_cluster.set(false),
+                            }
+                            "URL"
+                        }
+                    }
+                    if in_cluster() {
+                        div { class: "prom-setup-fields",
+                            input {
+                                class: "form-input",
+                                placeholder: "Namespace",
+                                value: "{namespace}",
+                                oninput: move |evt| namespace.set(evt.value()),
+                            }
+                            input {
+                                class: "form-input",
+                                placeholder: "Service",
+                                value: "{service}",
+                                oninput: move |evt| service.set(evt.value()),
+                            }
+                            input {
+                                class: "form-input prom-port",
+                                placeholder: "Port",
+                                title: "Port number or name",
+                                value: "{port}",
+                                oninput: move |evt| port.set(evt.value()),
+                            }
+                        }
+                    } else {
+                        div { class: "prom-setup-fields",
+                            input {
+                                class: "form-input",
+                                placeholder: "https://prometheus.example.com",
+                                value: "{url}",
+                                oninput: move |evt| url.set(evt.value()),
+                            }
+                        }
+                    }
+                    div { class: "prom-setup-actions",
+                        button { class: "btn btn-primary", onclick: save, "Save" }
+                        button { class: "btn btn-secondary", onclick: move |_| editing.set(false), "Cancel" }
+                    }
+                }
+            } else if let Some(endpoint) = &endpoint {
+                div { class: "prom-setup-current",
+                    span { "Using Prometheus at {endpoint}" }
+                    button { class: "btn btn-secondary", onclick: edit, "Change" }
+                    button { class: "btn btn-secondary", onclick: remove, "Remove" }
+                }
+            } else {
+                div { class: "prom-setup-current",
+                    span { "Connect a Prometheus to chart CPU, memory, restarts and network I/O over 1h, 24h and 7d." }
+                    button { class: "btn btn-primary", onclick: edit, "Set up" }
+                }
+            }
+            if let Some(error) = error() {
+                div { class: "prom-chart-error", "{error}" }
+            }
+        }
+    }
+}
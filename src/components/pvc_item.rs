@@ -1,5 +1,10 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
+use kube::api::ApiResource;
+
+use crate::components::{DeleteDialog, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::{format_memory, MetricsHistory, PVCS};
 
 #[derive(Clone)]
 struct PvcData {
@@ -8,6 +13,8 @@ struct PvcData {
     status: String,
     volume: Option<String>,
     capacity: Option<String>,
+    /// Space used on the mounted volume, as the kubelet reports it
+    used: Option<String>,
     access_modes: Vec<String>,
     storage_class: Option<String>,
     age: String,
@@ -23,10 +30,20 @@ pub struct PvcItemProps {
 #[component]
 pub fn PvcItem(props: PvcItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
+
+    let metrics_history = use_context::<Signal<MetricsHistory>>();
+    let name = props.pvc.metadata.name.clone().unwrap_or_default();
+    let namespace = props.pvc.metadata.namespace.clone().unwrap_or_default();
+    let used = metrics_history.read().volume(&namespace, &name).map(|fs| {
+        let percent = if fs.capacity > 0.0 { fs.used / fs.capacity * 100.0 } else { 0.0 };
+        format!("{} ({:.0}%)", format_memory(fs.used), percent)
+    });
 
     let pvc_data = PvcData {
-        name: props.pvc.metadata.name.clone().unwrap_or_default(),
-        namespace: props.pvc.metadata.namespace.clone().unwrap_or_default(),
+        name,
+        namespace,
         status: props.pvc.status.as_ref()
             .and_then(|s| s.phase.clone())
             .unwrap_or_else(|| "Unknown".to_string()),
@@ -36,6 +53,7 @@ pub fn PvcItem(props: PvcItemProps) -> Element {
             .and_then(|s| s.capacity.as_ref())
             .and_then(|c| c.get("storage"))
             .map(|q| q.0.clone()),
+        used,
         access_modes: props.pvc.spec.as_ref()
             .and_then(|s| s.access_modes.clone())
             .unwrap_or_default(),
@@ -59,6 +77,9 @@ pub fn PvcItem(props: PvcItemProps) -> Element {
 
     let access_modes_str = pvc_data.access_modes.join(", ");
 
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", PVCS, Some(&pvc_data.namespace));
+
     rsx! {
         div {
             key: "{pvc_data.name}",
@@ -72,10 +93,32 @@ pub fn PvcItem(props: PvcItemProps) -> Element {
                 div { class: "pvc-info-short",
                     span { class: "info-item-short", title: "Namespace", "{pvc_data.namespace}" }
                     span { class: "info-item-short", title: "Capacity", "{pvc_data.capacity.as_deref().unwrap_or(\"-\")}" }
+                    if let Some(used) = &pvc_data.used {
+                        span { class: "info-item-short", title: "Used", "{used} used" }
+                    }
                     span { class: "info-item-short", title: "Access Modes", "{access_modes_str}" }
                     span { class: "info-item-short", title: "Storage Class", "{pvc_data.storage_class.as_deref().unwrap_or(\"<none>\")}" }
                 }
                 div { class: "pvc-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -88,6 +131,26 @@ pub fn PvcItem(props: PvcItemProps) -> Element {
                 }
             }
 
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<PersistentVolumeClaim>(&()),
+                        namespace: Some(pvc_data.namespace.clone()),
+                        name: pvc_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<PersistentVolumeClaim>(&()),
+                    namespace: Some(pvc_data.namespace.clone()),
+                    name: pvc_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "pvc-details",
                     // Basic Info Section
@@ -98,6 +161,7 @@ pub fn PvcItem(props: PvcItemProps) -> Element {
                             div { class: "info-item", span { class: "info-label", "Status" } span { class: "info-value", "{pvc_data.status}" } }
                             div { class: "info-item", span { class: "info-label", "Bound Volume" } span { class: "info-value", "{pvc_data.volume.as_deref().unwrap_or(\"-\")}" } }
                             div { class: "info-item", span { class: "info-label", "Capacity" } span { class: "info-value", "{pvc_data.capacity.as_deref().unwrap_or(\"-\")}" } }
+                            div { class: "info-item", span { class: "info-label", "Used" } span { class: "info-value", "{pvc_data.used.as_deref().unwrap_or(\"-\")}" } }
                             div { class: "info-item", span { class: "info-label", "Access Modes" } span { class: "info-value", "{access_modes_str}" } }
                             div { class: "info-item", span { class: "info-label", "Storage Class" } span { class: "info-value", "{pvc_data.storage_class.as_deref().unwrap_or(\"<none>\")}" } }
                             div { class: "info-item", span { class: "info-label", "Age" } span { class: "info-value", "{pvc_data.age}" } }
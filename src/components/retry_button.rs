@@ -0,0 +1,23 @@
+use dioxus::prelude::*;
+
+const FEEDBACK_CSS: Asset = asset!("/assets/styling/feedback.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct RetryButtonProps {
+    on_retry: EventHandler<()>,
+    #[props(default = "Retry".to_string())]
+    label: String,
+}
+
+/// Button for running a failed request again
+#[component]
+pub fn RetryButton(props: RetryButtonProps) -> Element {
+    rsx! {
+        document::Link { rel: "stylesheet", href: FEEDBACK_CSS }
+        button {
+            class: "retry-button",
+            onclick: move |_| props.on_retry.call(()),
+            "↻ {props.label}"
+        }
+    }
+}
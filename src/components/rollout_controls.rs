@@ -0,0 +1,248 @@
+use dioxus::prelude::*;
+use kube::Client;
+
+use crate::k8s::{list_revisions, rollback_workload, scale_workload, RolloutProgress, WorkloadKind, WorkloadRevision};
+
+const ROLLOUT_CSS: Asset = asset!("/assets/styling/rollout.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct ScaleFormProps {
+    pub kind: WorkloadKind,
+    pub namespace: String,
+    pub name: String,
+    pub replicas: i32,
+    /// Called with the workload's new generation once scaled, or `None` when cancelled
+    pub on_close: EventHandler<Option<i64>>,
+}
+
+#[component]
+pub fn ScaleForm(props: ScaleFormProps) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+    let mut replicas = use_signal(|| props.replicas.to_string());
+    let mut is_applying = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let apply = {
+        let namespace = props.namespace.clone();
+        let name = props.name.clone();
+        move |_| {
+            let Ok(count) = replicas().trim().parse::<i32>() else {
+                error.set(Some("Replicas must be a whole number".to_string()));
+                return;
+            };
+            if count < 0 {
+                error.set(Some("Replicas can't be negative".to_string()));
+                return;
+            }
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                return;
+            };
+            let namespace = namespace.clone();
+            let name = name.clone();
+            is_applying.set(true);
+            error.set(None);
+            spawn(async move {
+                match scale_workload(client, props.kind, &namespace, &name, count).await {
+                    Ok(generation) => props.on_close.call(Some(generation)),
+                    Err(e) => {
+                        error.set(Some(format!("Failed to scale {}: {}", name, e)));
+                        is_applying.set(false);
+                    }
+                }
+            });
+        }
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: ROLLOUT_CSS }
+        div { class: "rollout-form",
+            label { class: "rollout-field",
+                "Replicas"
+                input {
+                    class: "rollout-input",
+                    r#type: "number",
+                    min: "0",
+                    value: "{replicas}",
+                    oninput: move |evt| replicas.set(evt.value()),
+                }
+            }
+            button {
+                class: "btn btn-primary",
+                disabled: is_applying(),
+                onclick: apply,
+                if is_applying() { "Scaling..." } else { "Scale" }
+            }
+            button {
+                class: "btn-icon",
+                title: "Cancel",
+                onclick: move |_| props.on_close.call(None),
+                "✕"
+            }
+            {error().map(|err| rsx! {
+                span { class: "rollout-error", "{err}" }
+            })}
+        }
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct RollbackFormProps {
+    pub kind: WorkloadKind,
+    pub namespace: String,
+    pub name: String,
+    /// Called with the workload's new generation once rolled back, or `None` when cancelled
+    pub on_close: EventHandler<Option<i64>>,
+}
+
+#[component]
+pub fn RollbackForm(props: RollbackFormProps) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+    let mut revisions = use_signal(Vec::<WorkloadRevision>::new);
+    let mut selected = use_signal(|| None::<i64>);
+    let mut is_loading = use_signal(|| true);
+    let mut is_applying = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    use_effect({
+        let namespace = props.namespace.clone();
+        let name = props.name.clone();
+        move || {
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                is_loading.set(false);
+                return;
+            };
+            let namespace = namespace.clone();
+            let name = name.clone();
+            spawn(async move {
+                match list_revisions(client, props.kind, &namespace, &name).await {
+                    Ok(list) => {
+                        // The newest revision is the one running now, so default to the one before it
+                        selected.set(list.get(1).map(|r| r.revision));
+                        revisions.set(list);
+                    }
+                    Err(e) => error.set(Some(format!("Failed to load revisions: {}", e))),
+                }
+                is_loading.set(false);
+            });
+        }
+    });
+
+    let rollback = {
+        let namespace = props.namespace.clone();
+        let name = props.name.clone();
+        move |_| {
+            let Some(revision) = selected() else { return };
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                return;
+            };
+            let namespace = namespace.clone();
+            let name = name.clone();
+            is_applying.set(true);
+            error.set(None);
+            spawn(async move {
+                match rollback_workload(client, props.kind, &namespace, &name, revision).await {
+                    Ok(generation) => props.on_close.call(Some(generation)),
+                    Err(e) => {
+                        error.set(Some(format!("Failed to roll back {}: {}", name, e)));
+                        is_applying.set(false);
+                    }
+                }
+            });
+        }
+    };
+
+    let current_revision = revisions.read().first().map(|r| r.revision);
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: ROLLOUT_CSS }
+        div { class: "rollout-form",
+            label { class: "rollout-field",
+                "Revision"
+                if is_loading() {
+                    span { class: "rollout-hint", "Loading..." }
+                } else if revisions.read().len() < 2 {
+                    span { class: "rollout-hint", "No earlier revisions" }
+                } else {
+                    select {
+                        class: "status-select rollout-select",
+                        onchange: move |evt| selected.set(evt.value().parse().ok()),
+                        {revisions.read().iter().filter(|r| Some(r.revision) != current_revision).map(|r| {
+                            let cause = r.change_cause.clone().unwrap_or_default();
+                            rsx! {
+                                option {
+                                    key: "{r.name}",
+                                    value: "{r.revision}",
+                                    selected: selected() == Some(r.revision),
+                                    if cause.is_empty() { "#{r.revision}" } else { "#{r.revision} - {cause}" }
+                                }
+                            }
+                        })}
+                    }
+                }
+            }
+            button {
+                class: "btn btn-primary",
+                disabled: is_applying() || selected().is_none(),
+                onclick: rollback,
+                if is_applying() { "Rolling back..." } else { "Roll back" }
+            }
+            button {
+                class: "btn-icon",
+                title: "Cancel",
+                onclick: move |_| props.on_close.call(None),
+                "✕"
+            }
+            {error().map(|err| rsx! {
+                span { class: "rollout-error", "{err}" }
+            })}
+        }
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct RolloutProgressBarProps {
+    pub progress: RolloutProgress,
+    #[props(optional)]
+    pub on_close: Option<EventHandler<()>>,
+}
+
+#[component]
+pub fn RolloutProgressBar(props: RolloutProgressBarProps) -> Element {
+    let progress = &props.progress;
+    let state = if progress.failed {
+        "failed"
+    } else if progress.done {
+        "done"
+    } else {
+        "running"
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: ROLLOUT_CSS }
+        div { class: "rollout-progress rollout-{state}",
+            div { class: "rollout-progress-header",
+                span { class: "rollout-message", "{progress.message}" }
+                span { class: "rollout-counts",
+                    "{progress.updated} updated · {progress.ready} ready · {progress.desired} desired"
+                    if let Some(on_close) = props.on_close {
+                        button {
+                            class: "btn-icon",
+                            title: "Dismiss",
+                            onclick: move |_| on_close.call(()),
+                            "✕"
+                        }
+                    }
+                }
+            }
+            div { class: "rollout-bar",
+                div {
+                    class: "rollout-bar-fill",
+                    style: "width: {progress.percent()}%"
+                }
+            }
+        }
+    }
+}
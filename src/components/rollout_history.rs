@@ -0,0 +1,201 @@
+use dioxus::prelude::*;
+use kube::Client;
+
+use crate::k8s::{diff_pod_templates, list_revisions, WorkloadKind, WorkloadRevision};
+use crate::utils::calculate_age_from_time;
+
+const ROLLOUT_CSS: Asset = asset!("/assets/styling/rollout.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct RolloutHistoryProps {
+    pub kind: WorkloadKind,
+    pub namespace: String,
+    pub name: String,
+    #[props(optional)]
+    pub on_close: Option<EventHandler<()>>,
+}
+
+#[component]
+pub fn RolloutHistory(props: RolloutHistoryProps) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+    let mut revisions = use_signal(Vec::<WorkloadRevision>::new);
+    // Up to two revisions picked for comparison, in the order they were picked
+    let mut selected = use_signal(Vec::<i64>::new);
+    let mut is_loading = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+    let mut reload = use_signal(|| 0u32);
+
+    use_effect({
+        let namespace = props.namespace.clone();
+        let name = props.name.clone();
+        move || {
+            let _ = reload();
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                is_loading.set(false);
+                return;
+            };
+            let namespace = namespace.clone();
+            let name = name.clone();
+            is_loading.set(true);
+            spawn(async move {
+                match list_revisions(client, props.kind, &namespace, &name).await {
+                    Ok(list) => {
+                        // Compare the running revision with the one before it by default
+                        selected.set(list.iter().take(2).map(|r| r.revision).collect());
+                        revisions.set(list);
+                        error.set(None);
+                    }
+                    Err(e) => error.set(Some(format!("Failed to load rollout history: {}", e))),
+                }
+                is_loading.set(false);
+            });
+        }
+    });
+
+    let toggle = move |revision: i64| {
+        let mut picked = selected.write();
+        if let Some(pos) = picked.iter().position(|r| *r == revision) {
+            picked.remove(pos);
+        } else {
+            if picked.len() == 2 {
+                picked.remove(0);
+            }
+            picked.push(revision);
+        }
+    };
+
+    let revisions_list = revisions.read().clone();
+    let current_revision = revisions_list.first().map(|r| r.revision);
+    let comparison = {
+        let picked = selected.read();
+        match picked.as_slice() {
+            [a, b] => {
+                let (older, newer) = if a < b { (*a, *b) } else { (*b, *a) };
+                let find = |rev: i64| revisions_list.iter().find(|r| r.revision == rev);
+                match (find(older), find(newer)) {
+                    (Some(old), Some(new)) => Some((older, newer, old.template.clone(), new.template.clone())),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: ROLLOUT_CSS }
+        div { class: "rollout-history",
+            div { class: "rollout-history-header",
+                h4 { "Rollout History" }
+                div { class: "rollout-history-actions",
+                    button {
+                        class: "btn btn-secondary",
+                        disabled: is_loading(),
+                        onclick: move |_| reload += 1,
+                        "Refresh"
+                    }
+                    if let Some(on_close) = props.on_close {
+                        button {
+                            class: "btn-icon",
+                            title: "Close history",
+                            onclick: move |_| on_close.call(()),
+                            "✕"
+                        }
+                    }
+                }
+            }
+
+            {error().map(|err| rsx! {
+                span { class: "rollout-error", "{err}" }
+            })}
+
+            if is_loading() && revisions_list.is_empty() {
+                span { class: "rollout-hint", "Loading revisions..." }
+            } else if revisions_list.is_empty() {
+                span { class: "rollout-hint", "No revisions recorded" }
+            } else {
+                table { class: "rollout-history-table",
+                    thead {
+                        tr {
+                            th { "" }
+                            th { "Revision" }
+                            th { "Change Cause" }
+                            th { "Age" }
+                            if props.kind == WorkloadKind::Deployment {
+                                th { "Replicas" }
+                            }
+                        }
+                    }
+                    tbody {
+                        {revisions_list.iter().map(|rev| {
+                            let revision = rev.revision;
+                            let is_selected = selected.read().contains(&revision);
+                            let age = calculate_age_from_time(rev.created.as_ref());
+                            let cause = rev.change_cause.clone().unwrap_or_else(|| "-".to_string());
+                            rsx! {
+                                tr {
+                                    key: "{rev.name}",
+                                    class: if is_selected { "selected" } else { "" },
+                                    onclick: move |_| toggle(revision),
+                                    td {
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: is_selected,
+                                        }
+                                    }
+                                    td {
+                                        "#{revision}"
+                                        if Some(revision) == current_revision {
+                                            span { class: "rollout-current", "current" }
+                                        }
+                                    }
+                                    td { class: "rollout-cause", "{cause}" }
+                                    td { "{age}" }
+                                    if let Some((ready, desired)) = rev.replicas {
+                                        td { "{ready}/{desired}" }
+                                    }
+                                }
+                            }
+                        })}
+                    }
+                }
+
+                {match comparison {
+                    Some((older, newer, Some(old_template), Some(new_template))) => {
+                        let changes = diff_pod_templates(&old_template, &new_template);
+                        rsx! {
+                            div { class: "rollout-diff",
+                                h5 { "Changes from #{older} to #{newer}" }
+                                if changes.is_empty() {
+                                    span { class: "rollout-hint", "No changes to images, env, resources or probes" }
+                                }
+                                {changes.iter().enumerate().map(|(i, change)| rsx! {
+                                    div {
+                                        key: "{i}",
+                                        class: "rollout-diff-row",
+                                        span { class: "rollout-diff-container", "{change.container}" }
+                                        span { class: "rollout-diff-category", "{change.category}" }
+                                        span { class: "rollout-diff-field", "{change.field}" }
+                                        span { class: "rollout-diff-before",
+                                            {change.before.clone().unwrap_or_else(|| "(none)".to_string())}
+                                        }
+                                        span { class: "rollout-diff-arrow", "→" }
+                                        span { class: "rollout-diff-after",
+                                            {change.after.clone().unwrap_or_else(|| "(none)".to_string())}
+                                        }
+                                    }
+                                })}
+                            }
+                        }
+                    }
+                    Some((older, newer, _, _)) => rsx! {
+                        span { class: "rollout-hint", "Pod template not available for #{older} or #{newer}" }
+                    },
+                    None => rsx! {
+                        span { class: "rollout-hint", "Select two revisions to compare their pod templates" }
+                    },
+                }}
+            }
+        }
+    }
+}
@@ -1,5 +1,10 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::core::v1::Secret;
+use kube::api::ApiResource;
+
+use crate::components::{DeleteDialog, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::SECRETS;
 
 #[derive(Clone)]
 struct SecretData {
@@ -20,6 +25,8 @@ pub struct SecretItemProps {
 #[component]
 pub fn SecretItem(props: SecretItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
     let mut revealed_keys = use_signal(|| std::collections::HashSet::new());
 
     let secret_data = SecretData {
@@ -63,6 +70,9 @@ pub fn SecretItem(props: SecretItemProps) -> Element {
     let key_base = format!("{}-{}", secret_data.namespace, secret_data.name);
     let data_keys = secret_data.data_keys.clone();
 
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", SECRETS, Some(&secret_data.namespace));
+
     rsx! {
         div {
             key: "{key_base}",
@@ -79,6 +89,25 @@ pub fn SecretItem(props: SecretItemProps) -> Element {
                     span { class: "info-item-short", title: "Age", "{secret_data.age}" }
                 }
                 div { class: "secret-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -91,6 +120,26 @@ pub fn SecretItem(props: SecretItemProps) -> Element {
                 }
             }
 
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<Secret>(&()),
+                        namespace: Some(secret_data.namespace.clone()),
+                        name: secret_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<Secret>(&()),
+                    namespace: Some(secret_data.namespace.clone()),
+                    name: secret_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "secret-details",
                     // Details Section
@@ -1,5 +1,10 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::core::v1::Service;
+use kube::api::ApiResource;
+
+use crate::components::{DeleteDialog, PortForwardForm, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::{ForwardTarget, POD_PORT_FORWARD, SERVICES};
 
 #[derive(Clone)]
 struct ServiceData {
@@ -30,6 +35,9 @@ pub struct ServiceItemProps {
 #[component]
 pub fn ServiceItem(props: ServiceItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
+    let mut show_port_forward = use_signal(|| false);
 
     let service_data = ServiceData {
         name: props.service.metadata.name.clone().unwrap_or_default(),
@@ -80,6 +88,11 @@ pub fn ServiceItem(props: ServiceItemProps) -> Element {
         _ => "status-unknown",
     };
 
+    let access_context = use_context::<AccessContext>();
+    let can_delete = access_context.can("delete", SERVICES, Some(&service_data.namespace));
+    // Forwarding to a service goes through one of its pods
+    let can_port_forward = access_context.can("create", POD_PORT_FORWARD, Some(&service_data.namespace));
+
     rsx! {
         div {
             key: "{service_data.name}",
@@ -91,6 +104,38 @@ pub fn ServiceItem(props: ServiceItemProps) -> Element {
                     span { class: "status-badge {status_class}", "{service_data.service_type}" }
                 }
                 div { class: "service-controls",
+                    // Forwarding needs a selector to find a backing pod
+                    if !service_data.selector.is_empty() && !service_data.ports.is_empty() {
+                        button {
+                            class: "btn-icon",
+                            onclick: move |evt| {
+                                evt.stop_propagation();
+                                show_port_forward.set(!show_port_forward());
+                            },
+                            disabled: !can_port_forward,
+                            title: if can_port_forward { "Port forward" } else { "You're not allowed to port forward to pods" },
+                            "🔌"
+                        }
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -103,6 +148,37 @@ pub fn ServiceItem(props: ServiceItemProps) -> Element {
                 }
             }
 
+            {show_port_forward().then(|| rsx! {
+                div { class: "service-port-forward-section",
+                    PortForwardForm {
+                        namespace: service_data.namespace.clone(),
+                        target: ForwardTarget::Service(service_data.name.clone()),
+                        ports: service_data.ports.iter().map(|p| p.port as u16).collect::<Vec<_>>(),
+                        on_close: move |_| show_port_forward.set(false),
+                    }
+                }
+            })}
+
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<Service>(&()),
+                        namespace: Some(service_data.namespace.clone()),
+                        name: service_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<Service>(&()),
+                    namespace: Some(service_data.namespace.clone()),
+                    name: service_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "service-details",
                     // Basic Info Section
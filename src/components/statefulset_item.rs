@@ -1,5 +1,10 @@
 use dioxus::prelude::*;
 use k8s_openapi::api::apps::v1::StatefulSet;
+use kube::{api::ApiResource, Client};
+
+use crate::components::{DeleteDialog, RollbackForm, RolloutHistory, RolloutProgressBar, ScaleForm, YamlPanel};
+use crate::contexts::AccessContext;
+use crate::k8s::{restart_workload, statefulset_rollout_progress, STATEFULSETS, WorkloadKind};
 
 #[derive(Clone)]
 struct StatefulSetData {
@@ -34,6 +39,15 @@ pub struct StatefulSetItemProps {
 #[component]
 pub fn StatefulSetItem(props: StatefulSetItemProps) -> Element {
     let mut is_expanded = use_signal(|| false);
+    let mut show_delete = use_signal(|| false);
+    let mut show_yaml = use_signal(|| false);
+    let mut show_scale = use_signal(|| false);
+    let mut show_rollback = use_signal(|| false);
+    let mut show_history = use_signal(|| false);
+    // Generation produced by the last action, tracked until its rollout converges
+    let mut rollout_generation = use_signal(|| None::<i64>);
+    let mut action_error = use_signal(|| None::<String>);
+    let client_signal = use_context::<Signal<Option<Client>>>();
 
     let statefulset_data = StatefulSetData {
         name: props.statefulset.metadata.name.clone().unwrap_or_default(),
@@ -70,6 +84,32 @@ pub fn StatefulSetItem(props: StatefulSetItemProps) -> Element {
             .unwrap_or_default(),
     };
 
+    let rollout_progress = statefulset_rollout_progress(&props.statefulset);
+    let rollout_converged = rollout_generation().is_some_and(|generation| rollout_progress.has_converged(generation));
+    use_effect(use_reactive!(|rollout_converged| {
+        if rollout_converged {
+            rollout_generation.set(None);
+        }
+    }));
+
+    let restart = {
+        let namespace = statefulset_data.namespace.clone();
+        let name = statefulset_data.name.clone();
+        move |evt: MouseEvent| {
+            evt.stop_propagation();
+            let Some(client) = client_signal() else { return };
+            let namespace = namespace.clone();
+            let name = name.clone();
+            action_error.set(None);
+            spawn(async move {
+                match restart_workload(client, WorkloadKind::StatefulSet, &namespace, &name).await {
+                    Ok(generation) => rollout_generation.set(Some(generation)),
+                    Err(e) => action_error.set(Some(format!("Failed to restart {}: {}", name, e))),
+                }
+            });
+        }
+    };
+
     let status_class = match statefulset_data.status.as_str() {
         "Available" => "status-running",
         "Progressing" => "status-pending",
@@ -86,6 +126,10 @@ pub fn StatefulSetItem(props: StatefulSetItemProps) -> Element {
         format!("({}/{})", statefulset_data.ready_replicas, statefulset_data.desired_replicas)
     };
 
+    let access_context = use_context::<AccessContext>();
+    let can_patch = access_context.can("patch", STATEFULSETS, Some(&statefulset_data.namespace));
+    let can_delete = access_context.can("delete", STATEFULSETS, Some(&statefulset_data.namespace));
+
     rsx! {
         div {
             key: "{statefulset_data.name}",
@@ -99,6 +143,63 @@ pub fn StatefulSetItem(props: StatefulSetItemProps) -> Element {
                     }
                 }
                 div { class: "statefulset-controls",
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_rollback.set(false);
+                            show_scale.set(!show_scale());
+                        },
+                        disabled: !can_patch,
+                        title: "Scale",
+                        "⚖️"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: restart,
+                        disabled: !can_patch,
+                        title: "Restart rollout",
+                        "🔄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_scale.set(false);
+                            show_rollback.set(!show_rollback());
+                        },
+                        disabled: !can_patch,
+                        title: "Roll back",
+                        "⏪"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_history.set(!show_history());
+                        },
+                        title: "Rollout history",
+                        "🕘"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_yaml.set(!show_yaml());
+                        },
+                        title: "View YAML",
+                        "📄"
+                    }
+                    button {
+                        class: "btn-icon",
+                        onclick: move |evt| {
+                            evt.stop_propagation();
+                            show_delete.set(true);
+                        },
+                        disabled: !can_delete,
+                        title: if can_delete { "Delete" } else { "You're not allowed to delete this" },
+                        "🗑️"
+                    }
                     button {
                         class: "btn-icon expand-toggle",
                         onclick: move |evt| {
@@ -111,6 +212,75 @@ pub fn StatefulSetItem(props: StatefulSetItemProps) -> Element {
                 }
             }
 
+            {(show_scale() || show_rollback() || show_history() || rollout_generation().is_some() || action_error().is_some()).then(|| rsx! {
+                div { class: "statefulset-actions-section",
+                    if show_scale() {
+                        ScaleForm {
+                            kind: WorkloadKind::StatefulSet,
+                            namespace: statefulset_data.namespace.clone(),
+                            name: statefulset_data.name.clone(),
+                            replicas: statefulset_data.desired_replicas,
+                            on_close: move |generation: Option<i64>| {
+                                show_scale.set(false);
+                                if generation.is_some() {
+                                    rollout_generation.set(generation);
+                                }
+                            },
+                        }
+                    }
+                    if show_rollback() {
+                        RollbackForm {
+                            kind: WorkloadKind::StatefulSet,
+                            namespace: statefulset_data.namespace.clone(),
+                            name: statefulset_data.name.clone(),
+                            on_close: move |generation: Option<i64>| {
+                                show_rollback.set(false);
+                                if generation.is_some() {
+                                    rollout_generation.set(generation);
+                                }
+                            },
+                        }
+                    }
+                    if show_history() {
+                        RolloutHistory {
+                            kind: WorkloadKind::StatefulSet,
+                            namespace: statefulset_data.namespace.clone(),
+                            name: statefulset_data.name.clone(),
+                            on_close: move |_| show_history.set(false),
+                        }
+                    }
+                    if rollout_generation().is_some() {
+                        RolloutProgressBar {
+                            progress: rollout_progress.clone(),
+                            on_close: move |_| rollout_generation.set(None),
+                        }
+                    }
+                    {action_error().map(|err| rsx! {
+                        span { class: "rollout-error", "{err}" }
+                    })}
+                }
+            })}
+
+            {show_yaml().then(|| rsx! {
+                div { class: "resource-yaml-section",
+                    YamlPanel {
+                        resource: ApiResource::erase::<StatefulSet>(&()),
+                        namespace: Some(statefulset_data.namespace.clone()),
+                        name: statefulset_data.name.clone(),
+                        on_close: move |_| show_yaml.set(false),
+                    }
+                }
+            })}
+
+            {show_delete().then(|| rsx! {
+                DeleteDialog {
+                    resource: ApiResource::erase::<StatefulSet>(&()),
+                    namespace: Some(statefulset_data.namespace.clone()),
+                    name: statefulset_data.name.clone(),
+                    on_close: move |_| show_delete.set(false),
+                }
+            })}
+
             {is_expanded().then(|| rsx! {
                 div { class: "statefulset-details",
                     // Basic Info Section
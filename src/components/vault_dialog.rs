@@ -0,0 +1,115 @@
+#![allow(non_snake_case)] // Allow non-snake_case for component names
+
+use dioxus::prelude::*;
+
+const DIALOG_CSS: Asset = asset!("/assets/styling/dialog.css");
+
+/// Minimum passphrase length accepted when encryption is first set up
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VaultDialogMode {
+    /// Enter the passphrase for already encrypted kubeconfigs
+    Unlock,
+    /// Choose a passphrase and encrypt the stored kubeconfigs with it
+    Enable,
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct VaultDialogProps {
+    pub mode: VaultDialogMode,
+    /// Error from the last attempt, e.g. a wrong passphrase
+    pub error: Option<String>,
+    pub on_submit: EventHandler<String>,
+    pub on_cancel: EventHandler<()>,
+}
+
+pub fn VaultDialog(props: VaultDialogProps) -> Element {
+    let mut passphrase = use_signal(String::new);
+    let mut confirmation = use_signal(String::new);
+
+    let is_enable = props.mode == VaultDialogMode::Enable;
+    let validation = if !is_enable {
+        None
+    } else if passphrase.read().chars().count() < MIN_PASSPHRASE_LEN {
+        Some(format!("Use at least {} characters", MIN_PASSPHRASE_LEN))
+    } else if *passphrase.read() != *confirmation.read() {
+        Some("Passphrases don't match".to_string())
+    } else {
+        None
+    };
+    let can_submit = !passphrase.read().is_empty() && validation.is_none();
+
+    let submit = move || {
+        if can_submit {
+            props.on_submit.call(passphrase());
+        }
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: DIALOG_CSS }
+        div { class: "dialog-overlay",
+            div { class: "dialog-box",
+                if is_enable {
+                    h3 { "Encrypt Stored Kubeconfigs" }
+                    p { "Stored kubeconfigs will be encrypted with a key derived from this passphrase. It is never saved, and you'll be asked for it each time Kontour starts." }
+                } else {
+                    h3 { "Unlock Kubeconfigs" }
+                    p { "Your stored kubeconfigs are encrypted. Enter the passphrase to use them." }
+                }
+                div { class: "dialog-input-group",
+                    label { r#for: "vault-passphrase-input", "Passphrase:" }
+                    input {
+                        id: "vault-passphrase-input",
+                        r#type: "password",
+                        autofocus: true,
+                        value: "{passphrase}",
+                        oninput: move |evt| passphrase.set(evt.value()),
+                        onkeydown: move |evt| {
+                            if evt.key() == Key::Enter && !is_enable {
+                                submit();
+                            }
+                        }
+                    }
+                }
+                if is_enable {
+                    div { class: "dialog-input-group",
+                        label { r#for: "vault-confirm-input", "Confirm passphrase:" }
+                        input {
+                            id: "vault-confirm-input",
+                            r#type: "password",
+                            value: "{confirmation}",
+                            oninput: move |evt| confirmation.set(evt.value()),
+                            onkeydown: move |evt| {
+                                if evt.key() == Key::Enter {
+                                    submit();
+                                }
+                            }
+                        }
+                    }
+                    if !passphrase.read().is_empty() {
+                        if let Some(message) = validation {
+                            div { class: "dialog-hint", "{message}" }
+                        }
+                    }
+                }
+                if let Some(error) = props.error.clone() {
+                    div { class: "dialog-error", "{error}" }
+                }
+                div { class: "dialog-buttons",
+                    button {
+                        class: "dialog-button cancel",
+                        onclick: move |_| props.on_cancel.call(()),
+                        if is_enable { "Cancel" } else { "Skip" }
+                    }
+                    button {
+                        class: "dialog-button ok",
+                        disabled: !can_submit,
+                        onclick: move |_| submit(),
+                        if is_enable { "Encrypt" } else { "Unlock" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use dioxus::prelude::*;
+use kube::{
+    api::{ApiResource, DynamicObject},
+    Client,
+};
+
+use crate::components::DryRunPreview;
+use crate::k8s::{apply_object_yaml, get_object, object_to_yaml, preview_diff, ApplyError, FIELD_MANAGER};
+use crate::utils::diff_utils::DiffLine;
+
+const YAML_PANEL_CSS: Asset = asset!("/assets/styling/yaml_panel.css");
+
+#[derive(Props, PartialEq, Clone)]
+pub struct YamlPanelProps {
+    /// Kind to show, e.g. `ApiResource::erase::<Pod>(&())`
+    pub resource: ApiResource,
+    /// `None` for cluster-scoped kinds
+    pub namespace: Option<String>,
+    pub name: String,
+    #[props(optional)]
+    pub on_close: Option<EventHandler<()>>,
+}
+
+#[component]
+pub fn YamlPanel(props: YamlPanelProps) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+
+    let mut live = use_signal(|| None::<DynamicObject>);
+    let mut yaml = use_signal(String::new);
+    let mut draft = use_signal(String::new);
+    let mut is_editing = use_signal(|| false);
+    let mut force = use_signal(|| false);
+    let mut is_loading = use_signal(|| true);
+    let mut is_applying = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut is_conflict = use_signal(|| false);
+    let mut notice = use_signal(|| None::<String>);
+    let mut preview = use_signal(|| None::<Vec<DiffLine>>);
+    let mut reload = use_signal(|| 0u32);
+
+    // Load the live object when opened and on every reload
+    use_effect({
+        let resource = props.resource.clone();
+        let namespace = props.namespace.clone();
+        let name = props.name.clone();
+        move || {
+            let _ = reload();
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                is_loading.set(false);
+                return;
+            };
+            let resource = resource.clone();
+            let namespace = namespace.clone();
+            let name = name.clone();
+            is_loading.set(true);
+            error.set(None);
+            spawn(async move {
+                match get_object(client, &resource, namespace.as_deref(), &name).await {
+                    Ok(object) => {
+                        match object_to_yaml(&object) {
+                            Ok(text) => yaml.set(text),
+                            Err(e) => error.set(Some(format!("Failed to render YAML: {}", e))),
+                        }
+                        live.set(Some(object));
+                    }
+                    Err(e) => error.set(Some(format!("Failed to load {}: {}", name, e))),
+                }
+                is_loading.set(false);
+            });
+        }
+    });
+
+    let apply = use_callback({
+        let resource = props.resource.clone();
+        let namespace = props.namespace.clone();
+        let name = props.name.clone();
+        move |dry_run: bool| {
+            let Some(client) = client_signal() else {
+                error.set(Some("No Kubernetes connection available".to_string()));
+                return;
+            };
+            let resource = resource.clone();
+            let namespace = namespace.clone();
+            let name = name.clone();
+            let text = draft();
+            let force = force();
+            is_applying.set(true);
+            error.set(None);
+            is_conflict.set(false);
+            notice.set(None);
+            preview.set(None);
+            spawn(async move {
+                match apply_object_yaml(client, &resource, namespace.as_deref(), &name, &text, force, dry_run).await {
+                    Ok(previewed) if dry_run => {
+                        let diff = match &*live.read() {
+                            Some(current) => preview_diff(current, &previewed),
+                            None => Err("live object not loaded".to_string()),
+                        };
+                        match diff {
+                            Ok(diff) => preview.set(Some(diff)),
+                            Err(e) => error.set(Some(format!("Failed to render preview: {}", e))),
+                        }
+                    }
+                    Ok(applied) => {
+                        if let Ok(text) = object_to_yaml(&applied) {
+                            yaml.set(text);
+                        }
+                        live.set(Some(applied));
+                        is_editing.set(false);
+                        notice.set(Some(format!("Applied as field manager \"{}\"", FIELD_MANAGER)));
+                    }
+                    Err(e) => {
+                        is_conflict.set(matches!(e, ApplyError::Conflict(_)));
+                        error.set(Some(e.to_string()));
+                    }
+                }
+                is_applying.set(false);
+            });
+        }
+    });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: YAML_PANEL_CSS }
+        div { class: "yaml-panel",
+            div { class: "yaml-toolbar",
+                span { class: "yaml-title", "{props.resource.kind} YAML" }
+                div { class: "yaml-toolbar-group",
+                    if is_editing() {
+                        label { class: "yaml-toggle",
+                            title: "Take ownership of fields managed by other tools",
+                            input {
+                                r#type: "checkbox",
+                                checked: force(),
+                                onchange: move |evt| force.set(evt.checked()),
+                            }
+                            "Force conflicts"
+                        }
+                        button {
+                            class: "btn btn-secondary",
+                            onclick: move |_| {
+                                is_editing.set(false);
+                                error.set(None);
+                                preview.set(None);
+                            },
+                            "Cancel"
+                        }
+                        button {
+                            class: "btn btn-secondary",
+                            disabled: is_applying(),
+                            onclick: move |_| apply.call(true),
+                            "Preview"
+                        }
+                        button {
+                            class: "btn btn-primary",
+                            disabled: is_applying(),
+                            onclick: move |_| apply.call(false),
+                            if is_applying() { "Applying..." } else { "Apply" }
+                        }
+                    } else {
+                        button {
+                            class: "btn btn-secondary",
+                            disabled: is_loading(),
+                            onclick: move |_| reload += 1,
+                            "Reload"
+                        }
+                        button {
+                            class: "btn btn-primary",
+                            disabled: is_loading() || yaml.read().is_empty(),
+                            onclick: move |_| {
+                                draft.set(yaml());
+                                notice.set(None);
+                                error.set(None);
+                                is_editing.set(true);
+                            },
+                            "Edit"
+                        }
+                    }
+                    if let Some(on_close) = props.on_close {
+                        button {
+                            class: "btn-icon",
+                            title: "Close YAML",
+                            onclick: move |_| on_close.call(()),
+                            "✕"
+                        }
+                    }
+                }
+            }
+
+            {error().map(|err| rsx! {
+                div { class: if is_conflict() { "yaml-error yaml-conflict" } else { "yaml-error" },
+                    div { "{err}" }
+                    if is_conflict() {
+                        div { class: "yaml-hint",
+                            "Reload to pick up the latest version, or tick \"Force conflicts\" to take over fields owned by another manager."
+                        }
+                    }
+                }
+            })}
+
+            {notice().map(|msg| rsx! {
+                div { class: "yaml-notice", "{msg}" }
+            })}
+
+            {preview().map(|diff| rsx! {
+                DryRunPreview { diff, on_close: move |_| preview.set(None) }
+            })}
+
+            if is_editing() {
+                textarea {
+                    class: "yaml-editor",
+                    spellcheck: "false",
+                    value: "{draft}",
+                    oninput: move |evt| draft.set(evt.value()),
+                }
+            } else if is_loading() && yaml.read().is_empty() {
+                div { class: "yaml-loading", "Loading..." }
+            } else {
+                pre { class: "yaml-view", "{yaml}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use dioxus::prelude::*;
+
+use crate::k8s::{AccessKind, Permissions, NAMESPACES};
+
+/// What the current user may do in the cluster, shared so views can hide or disable
+/// what they aren't allowed to use
+#[derive(Clone, Copy)]
+pub struct AccessContext {
+    pub permissions: Signal<Permissions>,
+    /// Namespace of the active kubeconfig context
+    pub default_namespace: Signal<Option<String>>,
+    /// Namespaces entered by hand, offered when namespaces can't be listed
+    pub manual_namespaces: Signal<Vec<String>>,
+}
+
+impl AccessContext {
+    /// Whether `verb` is allowed on `kind` in `namespace`, or across all namespaces for `None` or "All"
+    pub fn can(&self, verb: &'static str, kind: AccessKind, namespace: Option<&str>) -> bool {
+        self.permissions.read().can(verb, kind, namespace)
+    }
+
+    /// Whether `verb` is allowed on `kind` anywhere, deciding if its nav entry is usable
+    pub fn can_anywhere(&self, verb: &'static str, kind: AccessKind) -> bool {
+        self.permissions.read().can_anywhere(verb, kind)
+    }
+
+    /// Whether the namespace selector can list namespaces, rather than offering known ones
+    pub fn can_list_namespaces(&self) -> bool {
+        self.can("list", NAMESPACES, None)
+    }
+
+    /// Namespaces to offer when they can't be listed: the kubeconfig default, then manual entries
+    pub fn known_namespaces(&self) -> Vec<String> {
+        let mut namespaces: Vec<String> = self.default_namespace.read().iter().cloned().collect();
+        for namespace in self.manual_namespaces.read().iter() {
+            if !namespaces.contains(namespace) {
+                namespaces.push(namespace.clone());
+            }
+        }
+        namespaces
+    }
+}
@@ -13,6 +13,12 @@ pub enum KubeconfigError {
     ClientCreationError(String),
     /// IO related errors
     IoError(String),
+    /// Error when a kubeconfig can't be parsed or doesn't contain the requested context
+    InvalidKubeconfig(String),
+    /// Error when stored kubeconfigs are encrypted and the passphrase hasn't been entered
+    Locked,
+    /// Error when encrypting, decrypting or unlocking stored kubeconfigs fails
+    EncryptionError(String),
 }
 
 impl fmt::Display for KubeconfigError {
@@ -23,6 +29,9 @@ impl fmt::Display for KubeconfigError {
             KubeconfigError::StorageError(msg) => write!(f, "Storage error: {}", msg),
             KubeconfigError::ClientCreationError(msg) => write!(f, "Client creation failed: {}", msg),
             KubeconfigError::IoError(msg) => write!(f, "IO error: {}", msg),
+            KubeconfigError::InvalidKubeconfig(msg) => write!(f, "Invalid kubeconfig: {}", msg),
+            KubeconfigError::Locked => write!(f, "Stored kubeconfigs are encrypted, unlock them first"),
+            KubeconfigError::EncryptionError(msg) => write!(f, "Encryption error: {}", msg),
         }
     }
 }
@@ -41,5 +50,202 @@ impl From<kube::Error> for KubeconfigError {
     }
 }
 
+impl From<kube::config::KubeconfigError> for KubeconfigError {
+    fn from(err: kube::config::KubeconfigError) -> Self {
+        KubeconfigError::InvalidKubeconfig(err.to_string())
+    }
+}
+
 /// Result type for kubeconfig operations
 pub type KubeconfigResult<T> = Result<T, KubeconfigError>;
+
+/// Errors from talking to the cluster, typed so views can tell "nothing there" apart from
+/// "not allowed to look" or "couldn't reach the API server"
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// RBAC denied the request, e.g. `list` on `pods`
+    Forbidden { verb: String, resource: String, message: String },
+    /// The object or the API serving it doesn't exist
+    NotFound(String),
+    /// The object changed since it was read, or already exists
+    Conflict(String),
+    /// The API server or the connection to it timed out
+    Timeout(String),
+    /// The API server couldn't be reached or rejected the credentials
+    Connection(String),
+    /// metrics-server isn't installed or isn't answering
+    MetricsUnavailable(String),
+    /// Anything else the API server or client reported
+    Other(String),
+}
+
+impl AppError {
+    /// Map a client error for `verb` on `resource`, used when the API server's message
+    /// doesn't name them itself
+    pub fn from_kube(err: kube::Error, verb: &str, resource: &str) -> Self {
+        let message = error_chain(&err);
+        match &err {
+            kube::Error::Api(response) => match response.code {
+                403 => {
+                    let (parsed_verb, parsed_resource) = parse_forbidden(&response.message)
+                        .unwrap_or_else(|| (verb.to_string(), resource.to_string()));
+                    AppError::Forbidden {
+                        verb: parsed_verb,
+                        resource: parsed_resource,
+                        message: response.message.clone(),
+                    }
+                }
+                401 => AppError::Connection(format!("Unauthorized: {}", response.message)),
+                404 => AppError::NotFound(response.message.clone()),
+                409 => AppError::Conflict(response.message.clone()),
+                408 | 504 => AppError::Timeout(response.message.clone()),
+                _ => AppError::Other(response.message.clone()),
+            },
+            _ if message.to_lowercase().contains("timed out") || message.to_lowercase().contains("deadline") => {
+                AppError::Timeout(message)
+            }
+            kube::Error::HyperError(_) | kube::Error::Service(_) | kube::Error::Auth(_) => AppError::Connection(message),
+            _ => AppError::Other(message),
+        }
+    }
+
+    /// Map an error from the metrics API, where a missing or unanswering metrics-server
+    /// shows up as 404 or 503 rather than anything more specific
+    pub fn from_metrics(err: kube::Error, resource: &str) -> Self {
+        match &err {
+            kube::Error::Api(response) if matches!(response.code, 404 | 503) => {
+                AppError::MetricsUnavailable(response.message.clone())
+            }
+            _ => AppError::from_kube(err, "list", resource),
+        }
+    }
+
+    /// Map an error from a watch on `resource`
+    pub fn from_watcher(err: kube::runtime::watcher::Error, resource: &str) -> Self {
+        use kube::runtime::watcher::Error as WatcherError;
+        match err {
+            WatcherError::InitialListFailed(e) => AppError::from_kube(e, "list", resource),
+            WatcherError::WatchStartFailed(e) | WatcherError::WatchFailed(e) => AppError::from_kube(e, "watch", resource),
+            WatcherError::WatchError(response) => AppError::from_kube(kube::Error::Api(response), "watch", resource),
+            e @ WatcherError::NoResourceVersion => AppError::Other(e.to_string()),
+        }
+    }
+
+    /// Short heading for the inline error
+    pub fn title(&self) -> String {
+        match self {
+            AppError::Forbidden { verb, resource, .. } => format!("You're not allowed to {} {}", verb, resource),
+            AppError::NotFound(_) => "Not found".to_string(),
+            AppError::Conflict(_) => "Conflict".to_string(),
+            AppError::Timeout(_) => "The request timed out".to_string(),
+            AppError::Connection(_) => "Can't reach the cluster".to_string(),
+            AppError::MetricsUnavailable(_) => "Metrics unavailable".to_string(),
+            AppError::Other(_) => "Something went wrong".to_string(),
+        }
+    }
+
+    /// What the user can do about it, if there's anything more useful than retrying
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            AppError::Forbidden { .. } => Some("Ask a cluster admin for access, or switch to a context that has it."),
+            AppError::MetricsUnavailable(_) => Some("Install metrics-server in the cluster to see usage."),
+            AppError::Connection(_) => Some("Check the connection status in the sidebar."),
+            AppError::Conflict(_) => Some("Reload the object and try again."),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Forbidden { message, .. } => write!(f, "Forbidden: {}", message),
+            AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            AppError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            AppError::Timeout(msg) => write!(f, "Timed out: {}", msg),
+            AppError::Connection(msg) => write!(f, "Connection error: {}", msg),
+            AppError::MetricsUnavailable(msg) => write!(f, "Metrics unavailable: {}", msg),
+            AppError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<kube::Error> for AppError {
+    fn from(err: kube::Error) -> Self {
+        AppError::from_kube(err, "access", "this resource")
+    }
+}
+
+/// Error message including its sources, where TLS and timeout details usually hide
+pub(crate) fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Verb and resource from an RBAC denial such as
+/// `pods is forbidden: User "jane" cannot list resource "pods" in API group "" at the cluster scope`
+fn parse_forbidden(message: &str) -> Option<(String, String)> {
+    let rest = &message[message.find(" cannot ")? + " cannot ".len()..];
+    let (verb, rest) = rest.split_once(' ')?;
+    let rest = rest.strip_prefix("resource \"")?;
+    let (resource, _) = rest.split_once('"')?;
+    Some((verb.to_string(), resource.to_string()))
+}
+
+/// Result type for cluster operations
+pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kube::core::ErrorResponse;
+
+    fn api_error(code: u16, message: &str) -> kube::Error {
+        kube::Error::Api(ErrorResponse {
+            status: "Failure".to_string(),
+            message: message.to_string(),
+            reason: String::new(),
+            code,
+        })
+    }
+
+    #[test]
+    fn test_app_error_from_kube() {
+        let forbidden = api_error(
+            403,
+            r#"pods is forbidden: User "jane" cannot list resource "pods" in API group "" at the cluster scope"#,
+        );
+        match AppError::from_kube(forbidden, "watch", "deployments") {
+            AppError::Forbidden { verb, resource, .. } => {
+                assert_eq!(verb, "list");
+                assert_eq!(resource, "pods");
+            }
+            other => panic!("expected Forbidden, got {:?}", other),
+        }
+
+        // Falls back to what the caller asked for when the message doesn't say
+        match AppError::from_kube(api_error(403, "forbidden"), "watch", "deployments") {
+            AppError::Forbidden { verb, resource, .. } => {
+                assert_eq!(verb, "watch");
+                assert_eq!(resource, "deployments");
+            }
+            other => panic!("expected Forbidden, got {:?}", other),
+        }
+
+        assert!(matches!(AppError::from(api_error(404, "not found")), AppError::NotFound(_)));
+        assert!(matches!(AppError::from(api_error(409, "already exists")), AppError::Conflict(_)));
+        assert!(matches!(AppError::from(api_error(504, "gateway timeout")), AppError::Timeout(_)));
+        assert!(matches!(
+            AppError::from_metrics(api_error(404, "the server could not find the requested resource"), "pods.metrics.k8s.io"),
+            AppError::MetricsUnavailable(_)
+        ));
+    }
+}
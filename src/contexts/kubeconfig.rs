@@ -1,15 +1,21 @@
-use dioxus::prelude::*;
-use kube::Client;
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::{Arc, Mutex};
+use dioxus::{logger::tracing, prelude::*};
+use futures::Stream;
+use kube::{
+    config::{KubeConfigOptions, Kubeconfig},
+    Client, Config,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
 
 use super::error::{KubeconfigError, KubeconfigResult};
-use crate::utils::config;
+use super::vault::KubeconfigVault;
+use crate::utils::{config, crypto_utils, file_utils};
 
-// Global storage for kubeconfig file paths (name -> file path mapping)
-static KUBECONFIG_STORAGE: std::sync::LazyLock<Arc<Mutex<HashMap<String, String>>>> = 
-    std::sync::LazyLock::new(|| Arc::new(Mutex::new(HashMap::new())));
+// Global storage for kubeconfig file paths (name -> file path), in the order they were added
+static KUBECONFIG_STORAGE: std::sync::LazyLock<Arc<Mutex<Vec<StoredKubeconfig>>>> = 
+    std::sync::LazyLock::new(|| Arc::new(Mutex::new(Vec::new())));
 
 /// Context for sharing file paths across the application
 #[derive(Clone, Default)]
@@ -17,38 +23,209 @@ pub struct FilePathsContext {
     pub kubeconfig_paths: Signal<Vec<String>>,
 }
 
+/// A named kubeconfig in the registry
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StoredKubeconfig {
+    pub name: String,
+    pub path: String,
+}
+
+/// On-disk layout of the registry index
+#[derive(Default, Serialize, Deserialize)]
+struct KubeconfigIndex {
+    #[serde(default)]
+    kubeconfigs: Vec<StoredKubeconfig>,
+}
+
 /// Context for managing kubeconfig file paths storage
 #[derive(Clone)]
 pub struct KubeconfigStorage {
-    storage: Arc<Mutex<HashMap<String, String>>>, // name -> file_path mapping
+    storage: Arc<Mutex<Vec<StoredKubeconfig>>>,
+    /// Index file every change is written to; `None` keeps the registry in memory only
+    index_path: Option<PathBuf>,
+    vault: KubeconfigVault,
 }
 
 impl Default for KubeconfigStorage {
     fn default() -> Self {
         Self {
             storage: KUBECONFIG_STORAGE.clone(),
+            index_path: None,
+            vault: KubeconfigVault::default(),
         }
     }
 }
 
 impl KubeconfigStorage {
-    /// Store kubeconfig file path with a given name
-    pub fn store_file_path(&self, name: String, file_path: String) -> KubeconfigResult<()> {
-        if let Ok(mut storage) = self.storage.lock() {
-            storage.insert(name, file_path);
-            Ok(())
+    /// Load the registry from an index file, starting empty if it doesn't exist yet
+    pub fn load(index_path: PathBuf) -> KubeconfigResult<Self> {
+        let index = if index_path.exists() {
+            let content = std::fs::read_to_string(&index_path)?;
+            serde_yaml::from_str::<KubeconfigIndex>(&content)
+                .map_err(|e| KubeconfigError::StorageError(format!("Failed to parse {}: {}", index_path.display(), e)))?
         } else {
-            Err(KubeconfigError::StorageError("Failed to acquire storage lock".to_string()))
+            KubeconfigIndex::default()
+        };
+        Ok(Self {
+            storage: Arc::new(Mutex::new(index.kubeconfigs)),
+            vault: KubeconfigVault::new(index_path.with_file_name(config::KUBECONFIG_VAULT_FILE)),
+            index_path: Some(index_path),
+        })
+    }
+
+    /// The registry persisted in `~/.kontour`, or an in-memory one if it can't be read
+    pub fn persisted() -> Self {
+        let loaded = file_utils::get_kubeconfig_index_path()
+            .map_err(KubeconfigError::from)
+            .and_then(Self::load);
+        match loaded {
+            Ok(storage) => storage,
+            Err(e) => {
+                tracing::error!("Failed to load kubeconfig registry: {}", e);
+                Self::default()
+            }
         }
     }
 
+    /// Passphrase state for encrypting stored kubeconfigs
+    pub fn vault(&self) -> &KubeconfigVault {
+        &self.vault
+    }
+
+    fn entries(&self) -> KubeconfigResult<MutexGuard<'_, Vec<StoredKubeconfig>>> {
+        self.storage
+            .lock()
+            .map_err(|_| KubeconfigError::StorageError("Failed to acquire storage lock".to_string()))
+    }
+
+    /// Write the registry to the index file, if it has one
+    fn save(&self, entries: &[StoredKubeconfig]) -> KubeconfigResult<()> {
+        let Some(index_path) = &self.index_path else { return Ok(()) };
+        let index = KubeconfigIndex { kubeconfigs: entries.to_vec() };
+        let content = serde_yaml::to_string(&index)
+            .map_err(|e| KubeconfigError::StorageError(e.to_string()))?;
+        std::fs::write(index_path, content)?;
+        Ok(())
+    }
+
+    /// Store kubeconfig file path with a given name, replacing any entry with that name
+    pub fn store_file_path(&self, name: String, file_path: String) -> KubeconfigResult<()> {
+        let mut entries = self.entries()?;
+        match entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => entry.path = file_path,
+            None => entries.push(StoredKubeconfig { name, path: file_path }),
+        }
+        self.save(&entries)
+    }
+
     /// Retrieve kubeconfig file path by name
     pub fn get_file_path(&self, name: &str) -> KubeconfigResult<Option<String>> {
-        if let Ok(storage) = self.storage.lock() {
-            Ok(storage.get(name).cloned())
+        Ok(self.entries()?.iter().find(|entry| entry.name == name).map(|entry| entry.path.clone()))
+    }
+
+    /// Names of all stored kubeconfigs, in the order they were added
+    pub fn names(&self) -> KubeconfigResult<Vec<String>> {
+        Ok(self.entries()?.iter().map(|entry| entry.name.clone()).collect())
+    }
+
+    /// Rename a stored kubeconfig, moving its file to match the new name
+    pub fn rename(&self, name: &str, new_name: &str) -> KubeconfigResult<()> {
+        let mut entries = self.entries()?;
+        if new_name == config::DEFAULT_KUBECONFIG || entries.iter().any(|entry| entry.name == new_name) {
+            return Err(KubeconfigError::StorageError(format!("A kubeconfig named {} already exists", new_name)));
+        }
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| KubeconfigError::NotFound(name.to_string()))?;
+
+        let old_path = PathBuf::from(&entry.path);
+        let extension = old_path.extension().and_then(|ext| ext.to_str()).unwrap_or("yaml");
+        let new_path = old_path.with_file_name(format!("{}.{}", file_utils::sanitize_filename(new_name), extension));
+        if new_path.exists() {
+            return Err(KubeconfigError::StorageError(format!("{} already exists", new_path.display())));
+        }
+        if old_path.exists() {
+            std::fs::rename(&old_path, &new_path)?;
+        }
+        entry.name = new_name.to_string();
+        entry.path = new_path.to_string_lossy().to_string();
+        self.save(&entries)
+    }
+
+    /// Write an uploaded kubeconfig to storage under `name`, encrypted if a passphrase is set,
+    /// replacing any kubeconfig already stored under that name
+    pub fn save_kubeconfig(&self, name: &str, content: &str) -> KubeconfigResult<()> {
+        let file_path = if self.vault.is_enabled() {
+            let data = self.vault.encrypt(content.as_bytes())?;
+            file_utils::save_encrypted_kubeconfig_file(name, &data)?
         } else {
-            Err(KubeconfigError::StorageError("Failed to acquire storage lock".to_string()))
+            file_utils::save_kubeconfig_file(name, content)?
+        };
+
+        let previous = self.get_file_path(name)?;
+        self.store_file_path(name.to_string(), file_path.clone())?;
+        // Don't leave a plaintext copy behind when a kubeconfig is replaced by an encrypted one
+        if let Some(previous) = previous.filter(|previous| *previous != file_path) {
+            let _ = std::fs::remove_file(previous);
+        }
+        Ok(())
+    }
+
+    /// Encrypt every stored kubeconfig that is still plaintext, returning how many were migrated.
+    /// Each one is written next to the original with an `.enc` extension before the plaintext is deleted.
+    pub fn encrypt_stored_files(&self) -> KubeconfigResult<usize> {
+        let mut entries = self.entries()?;
+        let plaintext: Vec<(usize, PathBuf, Vec<u8>)> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let path = PathBuf::from(&entry.path);
+                let data = std::fs::read(&path).ok()?;
+                (!crypto_utils::is_encrypted(&data)).then_some((index, path, data))
+            })
+            .collect();
+
+        let mut migrated = 0;
+        for (index, path, data) in plaintext {
+            let encrypted_path = path.with_extension(config::ENCRYPTED_KUBECONFIG_EXTENSION);
+            std::fs::write(&encrypted_path, self.vault.encrypt(&data)?)?;
+            entries[index].path = encrypted_path.to_string_lossy().to_string();
+            // Point the index at the encrypted copy before the plaintext goes away
+            self.save(&entries)?;
+            std::fs::remove_file(&path)?;
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+
+    /// Set a passphrase and encrypt every stored kubeconfig with it
+    pub fn enable_encryption(&self, passphrase: &str) -> KubeconfigResult<usize> {
+        self.vault.enable(passphrase)?;
+        self.encrypt_stored_files()
+    }
+
+    /// Unlock encrypted kubeconfigs, migrating any that are still plaintext
+    pub fn unlock(&self, passphrase: &str) -> KubeconfigResult<usize> {
+        self.vault.unlock(passphrase)?;
+        self.encrypt_stored_files()
+    }
+
+    /// Remove a stored kubeconfig and delete its file
+    pub fn remove(&self, name: &str) -> KubeconfigResult<()> {
+        let mut entries = self.entries()?;
+        let index = entries
+            .iter()
+            .position(|entry| entry.name == name)
+            .ok_or_else(|| KubeconfigError::NotFound(name.to_string()))?;
+        let removed = entries.remove(index);
+        match std::fs::remove_file(&removed.path) {
+            Ok(()) => {}
+            // Already gone, nothing left to clean up
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
         }
+        self.save(&entries)
     }
 }
 
@@ -56,55 +233,153 @@ impl KubeconfigStorage {
 #[derive(Clone)]
 pub struct ClientReloadContext {
     pub current_path: Signal<String>,
+    /// Bumped to rebuild the client when the selected kubeconfig's content changes
+    pub revision: Signal<u32>,
+    /// Context to connect with inside the kubeconfig; `None` uses its `current-context`
+    pub current_context: Signal<Option<String>>,
 }
 
-/// Function to create a Kubernetes client from a kubeconfig path
-pub async fn create_client_from_path(
-    name_or_path: &str, 
-    storage: &KubeconfigStorage
-) -> KubeconfigResult<Client> {
+/// A context inside a kubeconfig file
+#[derive(Clone, Debug, PartialEq)]
+pub struct KubeContext {
+    pub name: String,
+    pub cluster: String,
+    pub user: Option<String>,
+    pub namespace: Option<String>,
+    /// Whether the file's `current-context` points at this context
+    pub is_current: bool,
+}
+
+/// Resolve a kubeconfig name or path to its file, `None` for the default kubeconfig
+fn resolve_kubeconfig_path(name_or_path: &str, storage: &KubeconfigStorage) -> KubeconfigResult<Option<String>> {
     if name_or_path == config::DEFAULT_KUBECONFIG {
-        // Use default kubeconfig
-        Client::try_default().await.map_err(|e| e.into())
+        return Ok(None);
+    }
+
+    // First check if this is a stored name that maps to a file path
+    let file_path = if let Some(stored_path) = storage.get_file_path(name_or_path)? {
+        stored_path
+    } else if Path::new(name_or_path).exists() {
+        // It's already a file path
+        name_or_path.to_string()
     } else {
-        // First check if this is a stored name that maps to a file path
-        let file_path = if let Some(stored_path) = storage.get_file_path(name_or_path)? {
-            stored_path
-        } else if Path::new(name_or_path).exists() {
-            // It's already a file path
-            name_or_path.to_string()
-        } else {
-            return Err(KubeconfigError::NotFound(name_or_path.to_string()));
-        };
-        
-        // Verify the file exists before trying to use it
-        if !Path::new(&file_path).exists() {
-            return Err(KubeconfigError::FileNotFound(file_path));
+        return Err(KubeconfigError::NotFound(name_or_path.to_string()));
+    };
+
+    // Verify the file exists before trying to use it
+    if !Path::new(&file_path).exists() {
+        return Err(KubeconfigError::FileNotFound(file_path));
+    }
+    Ok(Some(file_path))
+}
+
+/// Parse a kubeconfig by name or path; the default kubeconfig follows `$KUBECONFIG` or `~/.kube/config`
+pub fn read_kubeconfig(name_or_path: &str, storage: &KubeconfigStorage) -> KubeconfigResult<Kubeconfig> {
+    match resolve_kubeconfig_path(name_or_path, storage)? {
+        Some(file_path) => {
+            let data = std::fs::read(&file_path)?;
+            if crypto_utils::is_encrypted(&data) {
+                Ok(Kubeconfig::from_yaml(&storage.vault().decrypt(&data)?)?)
+            } else {
+                Ok(Kubeconfig::read_from(file_path)?)
+            }
         }
-        
-        create_client_from_file_path(&file_path).await
+        None => Ok(Kubeconfig::read()?),
     }
 }
 
-/// Create a client from a file path
-async fn create_client_from_file_path(
-    path: &str
-) -> KubeconfigResult<Client> {
-    let original_kubeconfig = std::env::var("KUBECONFIG").ok();
-    std::env::set_var("KUBECONFIG", path);
-    
-    let result = Client::try_default().await;
-    
-    // Restore original KUBECONFIG if it existed, or remove it
-    match original_kubeconfig {
-        Some(original) => std::env::set_var("KUBECONFIG", original),
-        None => std::env::remove_var("KUBECONFIG"),
+/// Every context in a kubeconfig, in file order
+pub fn list_contexts(name_or_path: &str, storage: &KubeconfigStorage) -> KubeconfigResult<Vec<KubeContext>> {
+    let kubeconfig = read_kubeconfig(name_or_path, storage)?;
+    let current = kubeconfig.current_context.clone();
+    Ok(kubeconfig
+        .contexts
+        .into_iter()
+        .map(|named| {
+            let is_current = current.as_deref() == Some(named.name.as_str());
+            let context = named.context.unwrap_or_default();
+            KubeContext {
+                name: named.name,
+                cluster: context.cluster,
+                user: context.user,
+                namespace: context.namespace,
+                is_current,
+            }
+        })
+        .collect())
+}
+
+/// Files a kubeconfig is read from: the stored file, or for the default kubeconfig every
+/// file in `$KUBECONFIG` (falling back to `~/.kube/config`)
+pub fn kubeconfig_watch_paths(name_or_path: &str, storage: &KubeconfigStorage) -> Vec<PathBuf> {
+    match resolve_kubeconfig_path(name_or_path, storage) {
+        Ok(Some(file_path)) => vec![PathBuf::from(file_path)],
+        Ok(None) => match std::env::var_os("KUBECONFIG") {
+            Some(value) if !value.is_empty() => std::env::split_paths(&value)
+                .filter(|path| !path.as_os_str().is_empty())
+                .collect(),
+            _ => dirs::home_dir()
+                .map(|home| vec![home.join(".kube").join("config")])
+                .unwrap_or_default(),
+        },
+        Err(_) => Vec::new(),
     }
-    
-    result.map_err(|e| e.into())
 }
 
+/// Modification time and size of a file, `None` while it doesn't exist
+type FileStamp = Option<(SystemTime, u64)>;
+
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Yields each time one of `paths` is rewritten, polling every `interval`. A change is only
+/// reported once the file exists again and has stopped changing between two polls, so tools
+/// that replace the file in several steps (or via rename) trigger a single reload.
+pub fn kubeconfig_changes(paths: Vec<PathBuf>, interval: Duration) -> impl Stream<Item = ()> {
+    let initial: Vec<FileStamp> = paths.iter().map(|path| file_stamp(path)).collect();
+    futures::stream::unfold((paths, initial.clone(), initial), move |(paths, mut seen, mut last_poll)| async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            let current: Vec<FileStamp> = paths.iter().map(|path| file_stamp(path)).collect();
+            let settled = current == last_poll
+                && current.iter().zip(&seen).all(|(now, before)| now.is_some() || before.is_none());
+            last_poll = current.clone();
+            if settled && current != seen {
+                seen = current;
+                return Some(((), (paths, seen, last_poll)));
+            }
+        }
+    })
+}
 
+/// Build a client config for `context` in a parsed kubeconfig, or its `current-context` for `None`.
+/// Nothing is read from the environment, so clients for different files can be built side by side.
+pub async fn config_from_kubeconfig(kubeconfig: Kubeconfig, context: Option<&str>) -> KubeconfigResult<Config> {
+    let options = KubeConfigOptions {
+        context: context.map(str::to_string),
+        ..Default::default()
+    };
+    Ok(Config::from_custom_kubeconfig(kubeconfig, &options).await?)
+}
+
+/// Function to create a Kubernetes client from a kubeconfig path, for a specific context
+/// when one is given
+pub async fn create_client_from_path(
+    name_or_path: &str,
+    context: Option<&str>,
+    storage: &KubeconfigStorage
+) -> KubeconfigResult<Client> {
+    if name_or_path == config::DEFAULT_KUBECONFIG && context.is_none() {
+        // Use default kubeconfig, falling back to the in-cluster config
+        return Client::try_default().await.map_err(|e| e.into());
+    }
+
+    let kubeconfig = read_kubeconfig(name_or_path, storage)?;
+    let config = config_from_kubeconfig(kubeconfig, context).await?;
+    Ok(Client::try_from(config)?)
+}
 
 #[cfg(test)]
 mod tests {
@@ -119,4 +394,188 @@ mod tests {
         let path = storage.get_file_path("test").unwrap();
         assert_eq!(path, Some("/path/to/test-config".to_string()));
     }
+
+    #[test]
+    fn test_kubeconfig_registry_persists() {
+        let dir = std::env::temp_dir().join(format!("kontour-registry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let index_path = dir.join("kubeconfigs.yaml");
+        let staging = dir.join("staging.yaml");
+        let production = dir.join("production.yaml");
+        std::fs::write(&staging, "apiVersion: v1\nkind: Config").unwrap();
+        std::fs::write(&production, "apiVersion: v1\nkind: Config").unwrap();
+
+        let storage = KubeconfigStorage::load(index_path.clone()).unwrap();
+        storage.store_file_path("staging".to_string(), staging.to_string_lossy().to_string()).unwrap();
+        storage.store_file_path("production".to_string(), production.to_string_lossy().to_string()).unwrap();
+
+        // A fresh load sees the same entries, in the order they were added
+        let reloaded = KubeconfigStorage::load(index_path.clone()).unwrap();
+        assert_eq!(reloaded.names().unwrap(), vec!["staging", "production"]);
+
+        reloaded.rename("staging", "qa").unwrap();
+        assert!(reloaded.rename("qa", "production").is_err());
+        let qa_path = reloaded.get_file_path("qa").unwrap().unwrap();
+        assert!(qa_path.ends_with("qa.yaml"));
+        assert!(Path::new(&qa_path).exists());
+        assert!(!staging.exists());
+
+        reloaded.remove("production").unwrap();
+        assert!(!production.exists());
+
+        let reloaded = KubeconfigStorage::load(index_path).unwrap();
+        assert_eq!(reloaded.names().unwrap(), vec!["qa"]);
+        assert_eq!(reloaded.get_file_path("staging").unwrap(), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_encrypt_stored_kubeconfigs() {
+        let dir = std::env::temp_dir().join(format!("kontour-vault-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let index_path = dir.join("kubeconfigs.yaml");
+        let plaintext = write_test_kubeconfig("vaulted", "https://vaulted.example:6443", "apps");
+        let stored = dir.join("vaulted.yaml");
+        std::fs::rename(&plaintext, &stored).unwrap();
+
+        let storage = KubeconfigStorage::load(index_path.clone()).unwrap();
+        storage.store_file_path("vaulted".to_string(), stored.to_string_lossy().to_string()).unwrap();
+        assert!(!storage.vault().is_enabled());
+
+        // Setting a passphrase migrates the plaintext file and removes it
+        assert_eq!(storage.enable_encryption("correct horse").unwrap(), 1);
+        assert!(!stored.exists());
+        let encrypted_path = storage.get_file_path("vaulted").unwrap().unwrap();
+        assert!(encrypted_path.ends_with("vaulted.enc"));
+        let data = std::fs::read(&encrypted_path).unwrap();
+        assert!(crypto_utils::is_encrypted(&data));
+        assert!(!String::from_utf8_lossy(&data).contains("vaulted-token"));
+        assert_eq!(list_contexts("vaulted", &storage).unwrap()[0].namespace.as_deref(), Some("apps"));
+
+        // After a restart nothing can be read until the right passphrase is entered
+        let restarted = KubeconfigStorage::load(index_path).unwrap();
+        assert!(restarted.vault().is_enabled());
+        assert!(matches!(list_contexts("vaulted", &restarted), Err(KubeconfigError::Locked)));
+        assert!(restarted.unlock("wrong horse").is_err());
+        assert_eq!(restarted.unlock("correct horse").unwrap(), 0);
+        assert_eq!(list_contexts("vaulted", &restarted).unwrap().len(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_kubeconfig_changes() {
+        use futures::StreamExt;
+
+        let path = PathBuf::from(write_test_kubeconfig("watched", "https://watched.example:6443", "apps"));
+        let interval = Duration::from_millis(20);
+        let mut changes = Box::pin(kubeconfig_changes(vec![path.clone()], interval));
+
+        // Nothing happens while the file is left alone
+        assert!(tokio::time::timeout(interval * 5, changes.next()).await.is_err());
+
+        // A rewrite with rotated credentials is reported once
+        let rotated = std::fs::read_to_string(&path).unwrap().replace("watched-token", "rotated-token-value");
+        std::fs::write(&path, rotated).unwrap();
+        assert!(tokio::time::timeout(interval * 20, changes.next()).await.unwrap().is_some());
+        assert!(tokio::time::timeout(interval * 5, changes.next()).await.is_err());
+
+        // A file that's gone missing mid-rewrite doesn't trigger a reload
+        std::fs::remove_file(&path).unwrap();
+        assert!(tokio::time::timeout(interval * 5, changes.next()).await.is_err());
+    }
+
+    fn write_test_kubeconfig(name: &str, server: &str, namespace: &str) -> String {
+        let content = format!(
+            "apiVersion: v1
+kind: Config
+current-context: {name}
+clusters:
+- name: {name}
+  cluster:
+    server: {server}
+contexts:
+- name: {name}
+  context:
+    cluster: {name}
+    user: {name}
+    namespace: {namespace}
+- name: {name}-system
+  context:
+    cluster: {name}
+    user: {name}
+    namespace: kube-system
+users:
+- name: {name}
+  user:
+    token: {name}-token
+"
+        );
+        let path = std::env::temp_dir().join(format!("kontour-test-{}-{}.yaml", name, std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_list_contexts() {
+        let storage = KubeconfigStorage::default();
+        let path = write_test_kubeconfig("listed", "https://listed.example:6443", "apps");
+
+        let contexts = list_contexts(&path, &storage).unwrap();
+        assert_eq!(contexts.len(), 2);
+        assert_eq!(contexts[0].name, "listed");
+        assert_eq!(contexts[0].cluster, "listed");
+        assert_eq!(contexts[0].user.as_deref(), Some("listed"));
+        assert_eq!(contexts[0].namespace.as_deref(), Some("apps"));
+        assert!(contexts[0].is_current);
+        assert_eq!(contexts[1].name, "listed-system");
+        assert!(!contexts[1].is_current);
+
+        assert!(matches!(
+            list_contexts("/no/such/kubeconfig", &storage),
+            Err(KubeconfigError::NotFound(_))
+        ));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_clients_for_two_files_at_once() {
+        let storage = KubeconfigStorage::default();
+        let first = write_test_kubeconfig("first", "https://first.example:6443", "team-a");
+        let second = write_test_kubeconfig("second", "https://second.example:6443", "team-b");
+        storage.store_file_path("first".to_string(), first.clone()).unwrap();
+
+        // Built concurrently, each client must still see only its own file
+        let (first_client, second_client, second_system) = tokio::join!(
+            tokio::spawn({
+                let storage = storage.clone();
+                async move { create_client_from_path("first", None, &storage).await }
+            }),
+            tokio::spawn({
+                let storage = storage.clone();
+                let second = second.clone();
+                async move { create_client_from_path(&second, None, &storage).await }
+            }),
+            tokio::spawn({
+                let storage = storage.clone();
+                let second = second.clone();
+                async move { create_client_from_path(&second, Some("second-system"), &storage).await }
+            }),
+        );
+        assert_eq!(first_client.unwrap().unwrap().default_namespace(), "team-a");
+        assert_eq!(second_client.unwrap().unwrap().default_namespace(), "team-b");
+        assert_eq!(second_system.unwrap().unwrap().default_namespace(), "kube-system");
+
+        let (first_config, second_config) = tokio::join!(
+            config_from_kubeconfig(read_kubeconfig(&first, &storage).unwrap(), None),
+            config_from_kubeconfig(read_kubeconfig(&second, &storage).unwrap(), None),
+        );
+        assert_eq!(first_config.unwrap().cluster_url.to_string(), "https://first.example:6443/");
+        assert_eq!(second_config.unwrap().cluster_url.to_string(), "https://second.example:6443/");
+        assert!(std::env::var("KUBECONFIG").map_or(true, |value| value != first && value != second));
+
+        let _ = std::fs::remove_file(&first);
+        let _ = std::fs::remove_file(&second);
+    }
 }
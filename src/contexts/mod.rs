@@ -1,5 +1,13 @@
 // Context modules for application state management
+pub mod access;
 pub mod error;
 pub mod kubeconfig;
+pub mod port_forwards;
+pub mod prometheus;
+pub mod vault;
 
+pub use access::*;
 pub use kubeconfig::*;
+pub use port_forwards::*;
+pub use prometheus::*;
+pub use vault::*;
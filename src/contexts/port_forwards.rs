@@ -0,0 +1,107 @@
+use dioxus::prelude::*;
+use futures::StreamExt;
+use kube::Client;
+use std::collections::HashMap;
+
+use crate::k8s::{start_port_forward, PortForwardEvent, PortForwardHandle, PortForwardRequest};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PortForwardStatus {
+    Starting,
+    Active,
+    Failed(String),
+}
+
+/// A port-forward as shown in the UI
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortForwardInfo {
+    pub id: u64,
+    pub request: PortForwardRequest,
+    pub pod_name: Option<String>,
+    pub local_port: Option<u16>,
+    pub status: PortForwardStatus,
+    pub active_connections: u32,
+    pub total_connections: u32,
+    pub last_error: Option<String>,
+}
+
+/// Context holding port-forwards at the app level so they survive navigation between routes
+#[derive(Clone, Copy)]
+pub struct PortForwardContext {
+    pub forwards: Signal<Vec<PortForwardInfo>>,
+    pub handles: Signal<HashMap<u64, PortForwardHandle>>,
+    /// ID of the next forward. IDs are never reused, so a start that finishes after its forward
+    /// was stopped can't be mistaken for a newer one.
+    pub next_id: Signal<u64>,
+}
+
+impl PortForwardContext {
+    /// Start forwarding in the background; progress and errors show up in `forwards`
+    pub fn start(self, client: Client, request: PortForwardRequest) {
+        let mut forwards = self.forwards;
+        let mut handles = self.handles;
+        let mut next_id = self.next_id;
+        let id = *next_id.peek();
+        next_id.set(id + 1);
+        forwards.write().push(PortForwardInfo {
+            id,
+            request: request.clone(),
+            pod_name: None,
+            local_port: None,
+            status: PortForwardStatus::Starting,
+            active_connections: 0,
+            total_connections: 0,
+            last_error: None,
+        });
+
+        // Spawned at the root so the forward isn't cancelled when the launching view unmounts
+        spawn_forever(async move {
+            match start_port_forward(client, &request).await {
+                Ok((handle, mut events)) => {
+                    let pod_name = handle.pod_name.clone();
+                    let local_port = handle.local_addr.port();
+                    // The forward may have been stopped while it was starting
+                    if !forwards.peek().iter().any(|info| info.id == id) {
+                        return;
+                    }
+                    handles.write().insert(id, handle);
+                    update_forward(forwards, id, |info| {
+                        info.pod_name = Some(pod_name);
+                        info.local_port = Some(local_port);
+                        info.status = PortForwardStatus::Active;
+                    });
+                    while let Some(event) = events.next().await {
+                        update_forward(forwards, id, |info| match event {
+                            PortForwardEvent::ConnectionOpened => {
+                                info.active_connections += 1;
+                                info.total_connections += 1;
+                            }
+                            PortForwardEvent::ConnectionClosed => {
+                                info.active_connections = info.active_connections.saturating_sub(1);
+                            }
+                            PortForwardEvent::Error(e) => info.last_error = Some(e),
+                        });
+                    }
+                }
+                Err(e) => {
+                    update_forward(forwards, id, |info| info.status = PortForwardStatus::Failed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Stop a port-forward and remove it from the list
+    pub fn stop(self, id: u64) {
+        let mut forwards = self.forwards;
+        let mut handles = self.handles;
+        // Dropping the handle closes the listener and its connections
+        handles.write().remove(&id);
+        forwards.write().retain(|info| info.id != id);
+    }
+}
+
+fn update_forward(mut forwards: Signal<Vec<PortForwardInfo>>, id: u64, f: impl FnOnce(&mut PortForwardInfo)) {
+    if let Some(info) = forwards.write().iter_mut().find(|info| info.id == id) {
+        f(info);
+    }
+}
@@ -0,0 +1,169 @@
+use dioxus::{logger::tracing, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use super::kubeconfig::{read_kubeconfig, KubeconfigStorage};
+use crate::k8s::{Prometheus, PrometheusEndpoint};
+use crate::utils::file_utils;
+
+/// On-disk layout of the Prometheus settings, keyed by `context_key`
+#[derive(Default, Serialize, Deserialize)]
+struct PrometheusIndex {
+    #[serde(default)]
+    endpoints: BTreeMap<String, PrometheusEndpoint>,
+}
+
+/// Prometheus endpoint of each cluster context. Prometheus is optional, so most contexts
+/// have none.
+#[derive(Clone, Default)]
+pub struct PrometheusSettings {
+    endpoints: Arc<Mutex<BTreeMap<String, PrometheusEndpoint>>>,
+    /// File every change is written to; `None` keeps the settings in memory only
+    path: Option<PathBuf>,
+}
+
+impl PrometheusSettings {
+    /// Load the settings from a file, starting empty if it doesn't exist yet
+    pub fn load(path: PathBuf) -> Result<Self, String> {
+        let index = if path.exists() {
+            let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            serde_yaml::from_str::<PrometheusIndex>(&content)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?
+        } else {
+            PrometheusIndex::default()
+        };
+        Ok(Self {
+            endpoints: Arc::new(Mutex::new(index.endpoints)),
+            path: Some(path),
+        })
+    }
+
+    /// The settings persisted in `~/.kontour`, or in-memory ones if they can't be read
+    pub fn persisted() -> Self {
+        let loaded = file_utils::get_prometheus_settings_path()
+            .map_err(|e| e.to_string())
+            .and_then(Self::load);
+        match loaded {
+            Ok(settings) => settings,
+            Err(e) => {
+                tracing::error!("Failed to load Prometheus settings: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<PrometheusEndpoint> {
+        self.endpoints.lock().ok()?.get(key).cloned()
+    }
+
+    /// Set or, with `None`, remove the endpoint for a context and write the settings out
+    pub fn set(&self, key: &str, endpoint: Option<PrometheusEndpoint>) -> Result<(), String> {
+        self.update(|endpoints| {
+            match endpoint {
+                Some(endpoint) => endpoints.insert(key.to_string(), endpoint),
+                None => endpoints.remove(key),
+            };
+        })
+    }
+
+    /// Move the endpoints of a renamed kubeconfig's contexts over to its new name
+    pub fn rename_kubeconfig(&self, name: &str, new_name: &str) -> Result<(), String> {
+        let prefix = format!("{}/", name);
+        self.update(|endpoints| {
+            let keys: Vec<String> = endpoints.keys().filter(|key| key.starts_with(&prefix)).cloned().collect();
+            for key in keys {
+                if let Some(endpoint) = endpoints.remove(&key) {
+                    endpoints.insert(format!("{}/{}", new_name, &key[prefix.len()..]), endpoint);
+                }
+            }
+        })
+    }
+
+    /// Forget the endpoints of a removed kubeconfig's contexts
+    pub fn remove_kubeconfig(&self, name: &str) -> Result<(), String> {
+        let prefix = format!("{}/", name);
+        self.update(|endpoints| endpoints.retain(|key, _| !key.starts_with(&prefix)))
+    }
+
+    /// Apply `change` to the endpoints and write the settings out
+    fn update(&self, change: impl FnOnce(&mut BTreeMap<String, PrometheusEndpoint>)) -> Result<(), String> {
+        let mut endpoints = self.endpoints
+            .lock()
+            .map_err(|_| "Failed to acquire settings lock".to_string())?;
+        change(&mut endpoints);
+        let Some(path) = &self.path else { return Ok(()) };
+        let index = PrometheusIndex { endpoints: endpoints.clone() };
+        let content = serde_yaml::to_string(&index).map_err(|e| e.to_string())?;
+        std::fs::write(path, content).map_err(|e| e.to_string())
+    }
+}
+
+/// Settings key of a context: the kubeconfig it's in plus its name, following the
+/// kubeconfig's `current-context` when none was picked
+pub fn context_key(kubeconfig: &str, context: Option<&str>, storage: &KubeconfigStorage) -> Option<String> {
+    let context = match context {
+        Some(context) => context.to_string(),
+        None => read_kubeconfig(kubeconfig, storage).ok()?.current_context?,
+    };
+    Some(format!("{}/{}", kubeconfig, context))
+}
+
+/// The active context's Prometheus, if one is configured
+#[derive(Clone)]
+pub struct PrometheusContext {
+    pub settings: PrometheusSettings,
+    /// `context_key` of the connected context
+    pub key: Signal<Option<String>>,
+    pub endpoint: Signal<Option<PrometheusEndpoint>>,
+    /// Ready to query once an endpoint is set and the cluster is connected
+    pub client: Signal<Option<Prometheus>>,
+}
+
+impl PrometheusContext {
+    /// Remember `endpoint` for the active context, or forget it with `None`
+    pub fn save(&self, endpoint: Option<PrometheusEndpoint>) -> Result<(), String> {
+        let key = self.key.peek().clone().ok_or_else(|| "Not connected to a cluster".to_string())?;
+        self.settings.set(&key, endpoint.clone())?;
+        let mut current = self.endpoint;
+        current.set(endpoint);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus_settings() {
+        let path = std::env::temp_dir().join(format!("kontour-test-prometheus-{}.yaml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let in_cluster = PrometheusEndpoint::Service {
+            namespace: "monitoring".to_string(),
+            name: "prometheus-k8s".to_string(),
+            port: "web".to_string(),
+        };
+
+        let settings = PrometheusSettings::load(path.clone()).unwrap();
+        settings.set("default/prod", Some(in_cluster.clone())).unwrap();
+        settings.set("default/dev", Some(PrometheusEndpoint::Url { url: "http://localhost:9090".to_string() })).unwrap();
+        settings.set("default/dev", None).unwrap();
+
+        let reloaded = PrometheusSettings::load(path.clone()).unwrap();
+        assert_eq!(reloaded.get("default/prod"), Some(in_cluster.clone()));
+        assert_eq!(reloaded.get("default/dev"), None);
+
+        // Renaming or removing a kubeconfig carries its contexts' endpoints along
+        settings.set("staging/dev", Some(in_cluster.clone())).unwrap();
+        settings.rename_kubeconfig("default", "production").unwrap();
+        settings.remove_kubeconfig("staging").unwrap();
+        let reloaded = PrometheusSettings::load(path.clone()).unwrap();
+        assert_eq!(reloaded.get("default/prod"), None);
+        assert_eq!(reloaded.get("production/prod"), Some(in_cluster));
+        assert_eq!(reloaded.get("staging/dev"), None);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
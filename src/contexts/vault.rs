@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use super::error::{KubeconfigError, KubeconfigResult};
+use crate::utils::crypto_utils::{self, KdfParams, VaultKey, SALT_LEN};
+
+/// Known plaintext encrypted when the passphrase is set, decrypted to check it on unlock
+const PASSPHRASE_CHECK: &[u8] = b"kontour-vault";
+
+/// On-disk vault settings; the key itself is never written anywhere
+#[derive(Serialize, Deserialize)]
+struct VaultSettings {
+    /// Hex-encoded Argon2 salt
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    /// Hex-encoded `PASSPHRASE_CHECK`, encrypted with the derived key
+    check: String,
+}
+
+impl VaultSettings {
+    fn params(&self) -> KdfParams {
+        KdfParams {
+            memory_kib: self.memory_kib,
+            iterations: self.iterations,
+            parallelism: self.parallelism,
+        }
+    }
+}
+
+/// Passphrase-derived key for kubeconfigs encrypted at rest. Encryption stays off until
+/// a passphrase is set, and the key only ever lives in memory once unlocked.
+#[derive(Clone, Default)]
+pub struct KubeconfigVault {
+    /// Settings file; `None` keeps the vault disabled, e.g. for in-memory storage
+    settings_path: Option<PathBuf>,
+    key: Arc<Mutex<Option<VaultKey>>>,
+}
+
+impl KubeconfigVault {
+    pub fn new(settings_path: PathBuf) -> Self {
+        Self {
+            settings_path: Some(settings_path),
+            key: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Whether a passphrase has been set up for stored kubeconfigs
+    pub fn is_enabled(&self) -> bool {
+        self.settings_path.as_ref().is_some_and(|path| path.exists())
+    }
+
+    /// Whether the key is available to read and write encrypted kubeconfigs
+    pub fn is_unlocked(&self) -> bool {
+        self.key.lock().is_ok_and(|key| key.is_some())
+    }
+
+    /// The unlocked key, or `Locked` if the passphrase hasn't been entered yet
+    pub fn key(&self) -> KubeconfigResult<VaultKey> {
+        self.key
+            .lock()
+            .map_err(|_| KubeconfigError::StorageError("Failed to acquire vault lock".to_string()))?
+            .clone()
+            .ok_or(KubeconfigError::Locked)
+    }
+
+    fn set_key(&self, key: VaultKey) -> KubeconfigResult<()> {
+        let mut current = self
+            .key
+            .lock()
+            .map_err(|_| KubeconfigError::StorageError("Failed to acquire vault lock".to_string()))?;
+        *current = Some(key);
+        Ok(())
+    }
+
+    fn read_settings(&self) -> KubeconfigResult<VaultSettings> {
+        let path = self.settings_path.as_ref().filter(|path| path.exists()).ok_or_else(|| {
+            KubeconfigError::EncryptionError("Encryption has not been set up".to_string())
+        })?;
+        let content = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&content)
+            .map_err(|e| KubeconfigError::StorageError(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+
+    /// Derive the key from the passphrase and keep it in memory if the passphrase is right
+    pub fn unlock(&self, passphrase: &str) -> KubeconfigResult<()> {
+        let settings = self.read_settings()?;
+        let salt = hex::decode(&settings.salt).map_err(|e| KubeconfigError::EncryptionError(e.to_string()))?;
+        let check = hex::decode(&settings.check).map_err(|e| KubeconfigError::EncryptionError(e.to_string()))?;
+
+        let key = crypto_utils::derive_key(passphrase, &salt, settings.params()).map_err(KubeconfigError::EncryptionError)?;
+        match crypto_utils::decrypt(&key, &check) {
+            Ok(plaintext) if plaintext.as_slice() == PASSPHRASE_CHECK => self.set_key(key),
+            _ => Err(KubeconfigError::EncryptionError("Wrong passphrase".to_string())),
+        }
+    }
+
+    /// Set the passphrase for a vault that doesn't have one yet, leaving it unlocked
+    pub fn enable(&self, passphrase: &str) -> KubeconfigResult<()> {
+        let Some(path) = &self.settings_path else {
+            return Err(KubeconfigError::EncryptionError("Encryption needs a persisted registry".to_string()));
+        };
+        if self.is_enabled() {
+            return Err(KubeconfigError::EncryptionError("Encryption is already set up".to_string()));
+        }
+
+        let params = KdfParams::default();
+        let salt = crypto_utils::random_bytes::<SALT_LEN>().map_err(KubeconfigError::EncryptionError)?;
+        let key = crypto_utils::derive_key(passphrase, &salt, params).map_err(KubeconfigError::EncryptionError)?;
+        let check = crypto_utils::encrypt(&key, PASSPHRASE_CHECK).map_err(KubeconfigError::EncryptionError)?;
+        let settings = VaultSettings {
+            salt: hex::encode(salt),
+            memory_kib: params.memory_kib,
+            iterations: params.iterations,
+            parallelism: params.parallelism,
+            check: hex::encode(check),
+        };
+        let content = serde_yaml::to_string(&settings).map_err(|e| KubeconfigError::StorageError(e.to_string()))?;
+        std::fs::write(path, content)?;
+        self.set_key(key)
+    }
+
+    /// Encrypt data with the unlocked key
+    pub fn encrypt(&self, plaintext: &[u8]) -> KubeconfigResult<Vec<u8>> {
+        crypto_utils::encrypt(&self.key()?, plaintext).map_err(KubeconfigError::EncryptionError)
+    }
+
+    /// Decrypt data written by `encrypt`
+    pub fn decrypt(&self, data: &[u8]) -> KubeconfigResult<String> {
+        let plaintext = crypto_utils::decrypt(&self.key()?, data).map_err(KubeconfigError::EncryptionError)?;
+        String::from_utf8(plaintext.to_vec())
+            .map_err(|_| KubeconfigError::EncryptionError("Decrypted kubeconfig is not valid UTF-8".to_string()))
+    }
+}
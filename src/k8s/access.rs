@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use futures::{stream, StreamExt};
+use k8s_openapi::api::authorization::v1::{
+    ResourceAttributes, ResourceRule, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec, SelfSubjectRulesReview,
+    SelfSubjectRulesReviewSpec,
+};
+use kube::{
+    api::{Api, PostParams},
+    Client,
+};
+
+use crate::contexts::error::{AppError, AppResult};
+
+/// Reviews run at once, to keep a burst of checks from tripping client-side rate limits
+const CONCURRENT_REVIEWS: usize = 8;
+
+/// A resource, or subresource, whose permissions are checked
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AccessKind {
+    pub group: &'static str,
+    pub resource: &'static str,
+    pub subresource: Option<&'static str>,
+    pub namespaced: bool,
+}
+
+impl AccessKind {
+    const fn namespaced(group: &'static str, resource: &'static str) -> Self {
+        Self { group, resource, subresource: None, namespaced: true }
+    }
+
+    const fn cluster(group: &'static str, resource: &'static str) -> Self {
+        Self { group, resource, subresource: None, namespaced: false }
+    }
+
+    const fn pod_subresource(subresource: &'static str) -> Self {
+        Self { group: "", resource: "pods", subresource: Some(subresource), namespaced: true }
+    }
+
+    /// `resource` or `resource/subresource`, as written in RBAC rules
+    pub fn path(&self) -> String {
+        match self.subresource {
+            Some(subresource) => format!("{}/{}", self.resource, subresource),
+            None => self.resource.to_string(),
+        }
+    }
+
+    /// Tooltip for something hidden behind `verb` on this kind
+    pub fn denied_message(&self, verb: &str) -> String {
+        format!("You're not allowed to {} {}", verb, self.path())
+    }
+}
+
+pub const NAMESPACES: AccessKind = AccessKind::cluster("", "namespaces");
+pub const NODES: AccessKind = AccessKind::cluster("", "nodes");
+pub const PODS: AccessKind = AccessKind::namespaced("", "pods");
+pub const POD_LOGS: AccessKind = AccessKind::pod_subresource("log");
+pub const POD_EXEC: AccessKind = AccessKind::pod_subresource("exec");
+pub const POD_PORT_FORWARD: AccessKind = AccessKind::pod_subresource("portforward");
+pub const SERVICES: AccessKind = AccessKind::namespaced("", "services");
+pub const CONFIGMAPS: AccessKind = AccessKind::namespaced("", "configmaps");
+pub const SECRETS: AccessKind = AccessKind::namespaced("", "secrets");
+pub const PVCS: AccessKind = AccessKind::namespaced("", "persistentvolumeclaims");
+pub const DEPLOYMENTS: AccessKind = AccessKind::namespaced("apps", "deployments");
+pub const STATEFULSETS: AccessKind = AccessKind::namespaced("apps", "statefulsets");
+pub const DAEMONSETS: AccessKind = AccessKind::namespaced("apps", "daemonsets");
+pub const JOBS: AccessKind = AccessKind::namespaced("batch", "jobs");
+pub const CRONJOBS: AccessKind = AccessKind::namespaced("batch", "cronjobs");
+pub const INGRESSES: AccessKind = AccessKind::namespaced("networking.k8s.io", "ingresses");
+
+/// Kinds the UI browses and edits, each checked for these verbs
+const MANAGED_KINDS: [AccessKind; 13] = [
+    NAMESPACES, NODES, PODS, SERVICES, CONFIGMAPS, SECRETS, PVCS, DEPLOYMENTS, STATEFULSETS, DAEMONSETS, JOBS,
+    CRONJOBS, INGRESSES,
+];
+const MANAGED_VERBS: [&str; 4] = ["list", "create", "patch", "delete"];
+
+/// Every verb and kind checked across the cluster when connecting
+fn cluster_checks() -> Vec<(&'static str, AccessKind)> {
+    let mut checks: Vec<_> = MANAGED_KINDS
+        .iter()
+        .flat_map(|kind| MANAGED_VERBS.iter().map(move |verb| (*verb, *kind)))
+        .collect();
+    checks.extend([("get", POD_LOGS), ("create", POD_EXEC), ("create", POD_PORT_FORWARD)]);
+    checks
+}
+
+/// Rules the user has in one namespace, from a `SelfSubjectRulesReview`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NamespaceRules {
+    pub rules: Vec<ResourceRule>,
+    /// The authorizer couldn't enumerate every rule, e.g. a webhook authorizer
+    pub incomplete: bool,
+}
+
+impl NamespaceRules {
+    /// Whether any rule grants `verb` on `kind`. Incomplete rule sets allow everything,
+    /// since the missing rules may well grant it.
+    pub fn allows(&self, verb: &str, kind: AccessKind) -> bool {
+        self.incomplete || self.rules.iter().any(|rule| rule_allows(rule, verb, kind))
+    }
+}
+
+fn rule_allows(rule: &ResourceRule, verb: &str, kind: AccessKind) -> bool {
+    let path = kind.path();
+    // Rules limited to named objects never cover listing or creating
+    let unrestricted = rule.resource_names.as_deref().unwrap_or_default().is_empty();
+    let verb_matches = rule.verbs.iter().any(|v| v == "*" || v == verb);
+    let group_matches = rule.api_groups.iter().flatten().any(|g| g == "*" || g == kind.group);
+    let resource_matches = rule.resources.iter().flatten().any(|r| {
+        r == "*"
+            || *r == path
+            || kind.subresource.is_some_and(|sub| {
+                *r == format!("{}/*", kind.resource) || *r == format!("*/{}", sub)
+            })
+    });
+    unrestricted && verb_matches && group_matches && resource_matches
+}
+
+/// What the current user may do, filled in by access reviews after connecting.
+/// Anything not reviewed yet counts as allowed, so nothing is hidden while reviews are
+/// in flight or on clusters that don't serve the review APIs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Permissions {
+    /// `verb` on a kind across all namespaces
+    pub cluster: HashMap<(&'static str, AccessKind), bool>,
+    pub namespaces: HashMap<String, NamespaceRules>,
+}
+
+impl Permissions {
+    /// Whether `verb` is allowed on `kind` in `namespace`, or across all namespaces for `None` or "All"
+    pub fn can(&self, verb: &'static str, kind: AccessKind, namespace: Option<&str>) -> bool {
+        if self.cluster.get(&(verb, kind)).copied().unwrap_or(true) {
+            return true;
+        }
+        // Denied cluster-wide, but a role binding may still grant it in this namespace
+        namespace
+            .filter(|ns| kind.namespaced && *ns != "All")
+            .and_then(|ns| self.namespaces.get(ns))
+            .is_some_and(|rules| rules.allows(verb, kind))
+    }
+
+    /// Whether `verb` is allowed on `kind` across the cluster or in any reviewed namespace
+    pub fn can_anywhere(&self, verb: &'static str, kind: AccessKind) -> bool {
+        self.can(verb, kind, None) || (kind.namespaced && self.namespaces.values().any(|rules| rules.allows(verb, kind)))
+    }
+
+    /// Whether any cluster-wide check was denied, making per-namespace reviews worthwhile
+    pub fn is_restricted(&self) -> bool {
+        self.cluster.values().any(|allowed| !allowed)
+    }
+}
+
+async fn review_access(client: Client, verb: &'static str, kind: AccessKind) -> AppResult<bool> {
+    let review = SelfSubjectAccessReview {
+        spec: SelfSubjectAccessReviewSpec {
+            resource_attributes: Some(ResourceAttributes {
+                group: Some(kind.group.to_string()),
+                resource: Some(kind.resource.to_string()),
+                subresource: kind.subresource.map(str::to_string),
+                verb: Some(verb.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let reviews: Api<SelfSubjectAccessReview> = Api::all(client);
+    let result = reviews
+        .create(&PostParams::default(), &review)
+        .await
+        .map_err(|e| AppError::from_kube(e, "create", "selfsubjectaccessreviews"))?;
+    Ok(result.status.is_some_and(|status| status.allowed))
+}
+
+/// Check every managed verb and kind across all namespaces with `SelfSubjectAccessReview`s
+pub async fn review_cluster_access(client: &Client) -> AppResult<HashMap<(&'static str, AccessKind), bool>> {
+    stream::iter(cluster_checks())
+        .map(|(verb, kind)| {
+            let client = client.clone();
+            async move { review_access(client, verb, kind).await.map(|allowed| ((verb, kind), allowed)) }
+        })
+        .buffer_unordered(CONCURRENT_REVIEWS)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect()
+}
+
+/// Fetch everything the user may do in `namespace` with a `SelfSubjectRulesReview`
+pub async fn review_namespace_access(client: &Client, namespace: &str) -> AppResult<NamespaceRules> {
+    let review = SelfSubjectRulesReview {
+        spec: SelfSubjectRulesReviewSpec {
+            namespace: Some(namespace.to_string()),
+        },
+        ..Default::default()
+    };
+    let reviews: Api<SelfSubjectRulesReview> = Api::all(client.clone());
+    let result = reviews
+        .create(&PostParams::default(), &review)
+        .await
+        .map_err(|e| AppError::from_kube(e, "create", "selfsubjectrulesreviews"))?;
+    let status = result.status.unwrap_or_default();
+    Ok(NamespaceRules {
+        rules: status.resource_rules,
+        incomplete: status.incomplete,
+    })
+}
+
+/// Review each namespace in turn, a few at a time, yielding results as they arrive
+pub fn review_namespaces(
+    client: Client,
+    namespaces: Vec<String>,
+) -> impl futures::Stream<Item = (String, AppResult<NamespaceRules>)> {
+    stream::iter(namespaces)
+        .map(move |namespace| {
+            let client = client.clone();
+            async move {
+                let rules = review_namespace_access(&client, &namespace).await;
+                (namespace, rules)
+            }
+        })
+        .buffer_unordered(CONCURRENT_REVIEWS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(verbs: &[&str], groups: &[&str], resources: &[&str]) -> ResourceRule {
+        let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        ResourceRule {
+            verbs: strings(verbs),
+            api_groups: Some(strings(groups)),
+            resources: Some(strings(resources)),
+            resource_names: None,
+        }
+    }
+
+    #[test]
+    fn test_namespace_rules() {
+        let rules = NamespaceRules {
+            rules: vec![
+                rule(&["get", "list", "watch"], &[""], &["pods", "pods/log"]),
+                rule(&["*"], &["apps"], &["deployments"]),
+                rule(&["create"], &[""], &["pods/*"]),
+            ],
+            incomplete: false,
+        };
+        assert!(rules.allows("list", PODS));
+        assert!(rules.allows("get", POD_LOGS));
+        assert!(!rules.allows("delete", PODS));
+        assert!(rules.allows("delete", DEPLOYMENTS));
+        assert!(!rules.allows("list", STATEFULSETS));
+        assert!(rules.allows("create", POD_EXEC));
+        assert!(!rules.allows("list", SECRETS));
+
+        let mut named = rule(&["get", "list"], &[""], &["secrets"]);
+        named.resource_names = Some(vec!["only-this-one".to_string()]);
+        assert!(!NamespaceRules { rules: vec![named], incomplete: false }.allows("list", SECRETS));
+        assert!(NamespaceRules { rules: Vec::new(), incomplete: true }.allows("list", SECRETS));
+    }
+
+    #[test]
+    fn test_permissions() {
+        let mut permissions = Permissions::default();
+        // Nothing reviewed yet
+        assert!(permissions.can("list", PODS, None));
+
+        permissions.cluster.insert(("list", PODS), false);
+        permissions.cluster.insert(("list", SECRETS), false);
+        permissions.namespaces.insert(
+            "team-a".to_string(),
+            NamespaceRules { rules: vec![rule(&["list"], &[""], &["pods"])], incomplete: false },
+        );
+        assert!(!permissions.can("list", PODS, None));
+        assert!(!permissions.can("list", PODS, Some("All")));
+        assert!(permissions.can("list", PODS, Some("team-a")));
+        assert!(!permissions.can("list", PODS, Some("team-b")));
+        assert!(permissions.can_anywhere("list", PODS));
+        assert!(!permissions.can_anywhere("list", SECRETS));
+        assert!(permissions.is_restricted());
+    }
+}
@@ -0,0 +1,177 @@
+use futures::{Stream, StreamExt};
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use k8s_openapi::serde_json::Value;
+use kube::{
+    api::{Api, ApiResource, DynamicObject},
+    discovery::{verbs, Discovery, Scope},
+    runtime::{reflector::store::Writer, watcher, WatchStreamExt},
+    Client, ResourceExt,
+};
+
+use crate::contexts::error::{AppError, AppResult};
+use crate::k8s::dynamic_api;
+use crate::utils::{json_path, time_utils::calculate_age};
+
+/// A listable kind the cluster serves, built-in or custom
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiKind {
+    pub resource: ApiResource,
+    pub namespaced: bool,
+}
+
+impl ApiKind {
+    /// `group/version`, or just `version` for the core group
+    pub fn api_version(&self) -> &str {
+        &self.resource.api_version
+    }
+}
+
+/// Kinds of one API group, `core` for the legacy group
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiGroupKinds {
+    pub name: String,
+    pub kinds: Vec<ApiKind>,
+}
+
+/// A CRD `additionalPrinterColumns` entry
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrinterColumn {
+    pub name: String,
+    pub json_path: String,
+    pub column_type: String,
+    pub description: Option<String>,
+}
+
+impl PrinterColumn {
+    /// Value of this column for an object, dates shown as ages like kubectl does
+    pub fn value(&self, object: &DynamicObject) -> String {
+        let Ok(value) = k8s_openapi::serde_json::to_value(object) else { return String::new() };
+        match json_path::evaluate_to_string(&value, &self.json_path) {
+            Some(text) if self.column_type == "date" => calculate_age(&text),
+            Some(text) => text,
+            None => String::new(),
+        }
+    }
+}
+
+/// One entry of `status.conditions`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectCondition {
+    pub type_: String,
+    pub status: String,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    pub last_transition_time: Option<String>,
+}
+
+/// Every group and kind the cluster serves that can be listed and watched, groups
+/// alphabetical with `core` first and kinds sorted by name. Uses the preferred version of each group.
+pub async fn discover_api_kinds(client: Client) -> AppResult<Vec<ApiGroupKinds>> {
+    let discovery = Discovery::new(client)
+        .run()
+        .await
+        .map_err(|e| AppError::from_kube(e, "list", "API groups"))?;
+    let mut groups: Vec<ApiGroupKinds> = discovery
+        .groups()
+        .map(|group| {
+            let mut kinds: Vec<ApiKind> = group
+                .recommended_resources()
+                .into_iter()
+                .filter(|(_, caps)| caps.supports_operation(verbs::LIST) && caps.supports_operation(verbs::WATCH))
+                .map(|(resource, caps)| ApiKind {
+                    resource,
+                    namespaced: caps.scope == Scope::Namespaced,
+                })
+                .collect();
+            kinds.sort_by(|a, b| a.resource.kind.cmp(&b.resource.kind));
+            let name = if group.name().is_empty() { "core".to_string() } else { group.name().to_string() };
+            ApiGroupKinds { name, kinds }
+        })
+        .filter(|group| !group.kinds.is_empty())
+        .collect();
+    groups.sort_by(|a, b| (a.name != "core", &a.name).cmp(&(b.name != "core", &b.name)));
+    Ok(groups)
+}
+
+/// Printer columns a CRD declares for the served version, skipping the ones kubectl only
+/// shows with `-o wide`. Built-in kinds have no CRD and get no extra columns.
+pub async fn printer_columns(client: Client, resource: &ApiResource) -> Vec<PrinterColumn> {
+    if resource.group.is_empty() {
+        return Vec::new();
+    }
+    let crds: Api<CustomResourceDefinition> = Api::all(client);
+    let crd_name = format!("{}.{}", resource.plural, resource.group);
+    let Ok(Some(crd)) = crds.get_opt(&crd_name).await else { return Vec::new() };
+
+    crd.spec
+        .versions
+        .into_iter()
+        .find(|version| version.name == resource.version)
+        .and_then(|version| version.additional_printer_columns)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|column| column.priority.unwrap_or(0) == 0)
+        // Age is always shown, so skip CRDs' own copy of it
+        .filter(|column| column.json_path != ".metadata.creationTimestamp")
+        .map(|column| PrinterColumn {
+            name: column.name,
+            json_path: column.json_path,
+            column_type: column.type_,
+            description: column.description,
+        })
+        .collect()
+}
+
+/// `status.conditions` of any object that follows the usual condition shape
+pub fn object_conditions(object: &DynamicObject) -> Vec<ObjectCondition> {
+    let text = |condition: &Value, field: &str| condition.get(field).and_then(Value::as_str).map(str::to_string);
+    object
+        .data
+        .pointer("/status/conditions")
+        .and_then(Value::as_array)
+        .map(|conditions| {
+            conditions
+                .iter()
+                .filter_map(|condition| {
+                    Some(ObjectCondition {
+                        type_: text(condition, "type")?,
+                        status: text(condition, "status").unwrap_or_default(),
+                        reason: text(condition, "reason"),
+                        message: text(condition, "message"),
+                        last_transition_time: text(condition, "lastTransitionTime"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Objects of any kind in `namespace` (all namespaces for `None`), re-yielded every time the
+/// set changes. Like `ClusterCache::snapshots` but owned by the caller, since arbitrary kinds
+/// are browsed one at a time and shouldn't stay watched after the view moves on.
+pub fn dynamic_snapshots(
+    client: Client,
+    resource: ApiResource,
+    namespace: Option<String>,
+) -> impl Stream<Item = AppResult<Vec<DynamicObject>>> {
+    let api = dynamic_api(client, &resource, namespace.as_deref());
+    let plural = resource.plural.clone();
+    let writer = Writer::new(resource);
+    let store = writer.as_reader();
+    watcher(api, watcher::Config::default())
+        .default_backoff()
+        .reflect(writer)
+        .filter_map(move |event| {
+            let snapshot = match event {
+                // The store swaps in a (re-)list atomically once it is complete
+                Ok(watcher::Event::Init) | Ok(watcher::Event::InitApply(_)) => None,
+                Ok(_) => {
+                    let mut items: Vec<DynamicObject> = store.state().iter().map(|obj| (**obj).clone()).collect();
+                    items.sort_by_key(|obj| (obj.namespace(), obj.name_any()));
+                    Some(Ok(items))
+                }
+                Err(e) => Some(Err(AppError::from_watcher(e, &plural))),
+            };
+            async move { snapshot }
+        })
+}
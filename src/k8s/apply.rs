@@ -0,0 +1,150 @@
+use std::fmt;
+
+use k8s_openapi::serde_json::{self, Value};
+use kube::{
+    api::{Api, ApiResource, DynamicObject, Patch, PatchParams},
+    Client,
+};
+use serde::Serialize;
+
+use crate::utils::diff_utils::{diff_lines, DiffLine};
+
+/// Field manager recorded on everything applied from the app
+pub const FIELD_MANAGER: &str = "kontour";
+
+/// Metadata the API server owns; sending it back on apply is either rejected or ignored
+const SERVER_METADATA: [&str; 5] = ["managedFields", "uid", "creationTimestamp", "generation", "selfLink"];
+
+#[derive(Debug)]
+pub enum ApplyError {
+    Parse(String),
+    Mismatch(String),
+    /// The object changed since it was loaded, or another field manager owns a changed field
+    Conflict(String),
+    Kube(kube::Error),
+}
+
+impl fmt::Display for ApplyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApplyError::Parse(e) => write!(f, "Invalid YAML: {}", e),
+            ApplyError::Mismatch(msg) => write!(f, "{}", msg),
+            ApplyError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            ApplyError::Kube(e) => write!(f, "Kubernetes API error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ApplyError {}
+
+impl From<kube::Error> for ApplyError {
+    fn from(err: kube::Error) -> Self {
+        match err {
+            kube::Error::Api(response) if response.code == 409 => ApplyError::Conflict(response.message),
+            err => ApplyError::Kube(err),
+        }
+    }
+}
+
+pub(crate) fn dynamic_api(client: Client, resource: &ApiResource, namespace: Option<&str>) -> Api<DynamicObject> {
+    match namespace {
+        Some(ns) => Api::namespaced_with(client, ns, resource),
+        None => Api::all_with(client, resource),
+    }
+}
+
+/// Fetch the live object by its API resource
+pub async fn get_object(
+    client: Client,
+    resource: &ApiResource,
+    namespace: Option<&str>,
+    name: &str,
+) -> Result<DynamicObject, kube::Error> {
+    dynamic_api(client, resource, namespace).get(name).await
+}
+
+/// Render an object as YAML without `managedFields`, which is noise for reading and editing
+pub fn object_to_yaml<K: Serialize>(object: &K) -> Result<String, String> {
+    let mut value = serde_json::to_value(object).map_err(|e| e.to_string())?;
+    if let Some(metadata) = value.get_mut("metadata").and_then(Value::as_object_mut) {
+        metadata.remove("managedFields");
+    }
+    serde_yaml::to_string(&value).map_err(|e| e.to_string())
+}
+
+/// Line diff between the live object and a dry-run result. `resourceVersion` is left out
+/// because it differs on every write and says nothing about what changed.
+pub fn preview_diff<K: Serialize>(live: &K, preview: &K) -> Result<Vec<DiffLine>, String> {
+    let render = |object: &K| -> Result<String, String> {
+        let mut value = serde_json::to_value(object).map_err(|e| e.to_string())?;
+        if let Some(metadata) = value.get_mut("metadata").and_then(Value::as_object_mut) {
+            metadata.remove("managedFields");
+            metadata.remove("resourceVersion");
+        }
+        serde_yaml::to_string(&value).map_err(|e| e.to_string())
+    };
+    Ok(diff_lines(&render(live)?, &render(preview)?))
+}
+
+/// Server-side apply an edited copy of an existing object under the `kontour` field manager.
+/// `resourceVersion` is kept so edits made against a stale copy fail with a conflict;
+/// `force` takes over fields owned by other managers. With `dry_run` the server runs
+/// defaulting, validation and admission but persists nothing, and returns the would-be object.
+pub async fn apply_object_yaml(
+    client: Client,
+    resource: &ApiResource,
+    namespace: Option<&str>,
+    name: &str,
+    yaml: &str,
+    force: bool,
+    dry_run: bool,
+) -> Result<DynamicObject, ApplyError> {
+    let mut value: Value = serde_yaml::from_str(yaml).map_err(|e| ApplyError::Parse(e.to_string()))?;
+    if !value.is_object() {
+        return Err(ApplyError::Parse("expected a single object".to_string()));
+    }
+
+    let kind = value.get("kind").and_then(Value::as_str).unwrap_or_default();
+    if kind != resource.kind {
+        return Err(ApplyError::Mismatch(format!(
+            "Expected kind {} but the YAML describes {}",
+            resource.kind,
+            if kind.is_empty() { "nothing" } else { kind }
+        )));
+    }
+    let object_name = value.pointer("/metadata/name").and_then(Value::as_str).unwrap_or_default();
+    if object_name != name {
+        return Err(ApplyError::Mismatch(format!(
+            "metadata.name must stay {}; renaming would create a new object",
+            name
+        )));
+    }
+    let object_namespace = value.pointer("/metadata/namespace").and_then(Value::as_str);
+    if object_namespace.is_some() && object_namespace != namespace {
+        return Err(ApplyError::Mismatch(format!(
+            "metadata.namespace must stay {}",
+            namespace.unwrap_or("unset")
+        )));
+    }
+
+    if let Some(object) = value.as_object_mut() {
+        object.remove("status");
+    }
+    if let Some(metadata) = value.get_mut("metadata").and_then(Value::as_object_mut) {
+        for field in SERVER_METADATA {
+            metadata.remove(field);
+        }
+    }
+
+    let mut params = PatchParams::apply(FIELD_MANAGER);
+    if force {
+        params = params.force();
+    }
+    if dry_run {
+        params = params.dry_run();
+    }
+    let applied = dynamic_api(client, resource, namespace)
+        .patch(name, &params, &Patch::Apply(&value))
+        .await?;
+    Ok(applied)
+}
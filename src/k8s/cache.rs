@@ -0,0 +1,316 @@
+use dioxus::logger::tracing;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, Weak};
+
+use futures::{stream, stream::BoxStream, Stream, StreamExt};
+use kube::{
+    runtime::{
+        reflector::{self, Store},
+        watcher, WatchStreamExt,
+    },
+    core::NamespaceResourceScope,
+    Api, Client, Resource, ResourceExt,
+};
+use serde::de::DeserializeOwned;
+use tokio::{sync::watch, task::AbortHandle};
+
+use crate::contexts::error::{AppError, AppResult};
+
+/// Bounds a kind needs to be cached
+pub trait CachedResource:
+    Resource<DynamicType = ()> + Clone + DeserializeOwned + Debug + Send + Sync + 'static
+{
+}
+
+impl<K> CachedResource for K where
+    K: Resource<DynamicType = ()> + Clone + DeserializeOwned + Debug + Send + Sync + 'static
+{
+}
+
+/// Sync state of one kind, published whenever its store changes
+#[derive(Clone, Debug, Default)]
+struct KindState {
+    /// Bumped on every change once the initial list has completed
+    version: u64,
+    /// Last watch error, cleared by the next successful event
+    error: Option<AppError>,
+}
+
+#[derive(Clone)]
+struct KindCache<K: Resource<DynamicType = ()> + 'static>
+where
+    K::DynamicType: Eq + Hash + Clone,
+{
+    store: Store<K>,
+    state: watch::Receiver<KindState>,
+}
+
+/// A kind and, for kinds watched in a single namespace, that namespace
+type KindKey = (TypeId, Option<String>);
+
+struct CacheInner {
+    client: Client,
+    kinds: Mutex<HashMap<KindKey, Box<dyn Any + Send + Sync>>>,
+    tasks: Mutex<Vec<AbortHandle>>,
+    /// Streams using each namespace-scoped watch, and its task. Unlike cluster-wide watches,
+    /// these stop once nothing uses them, so switching namespaces doesn't pile up watches.
+    scoped: Mutex<HashMap<KindKey, (usize, AbortHandle)>>,
+}
+
+impl Drop for CacheInner {
+    fn drop(&mut self) {
+        if let Ok(tasks) = self.tasks.lock() {
+            for task in tasks.iter() {
+                task.abort();
+            }
+        }
+        if let Ok(scoped) = self.scoped.lock() {
+            for (_, task) in scoped.values() {
+                task.abort();
+            }
+        }
+    }
+}
+
+/// Held by each stream of a namespace-scoped watch; the watch stops when the last one drops
+struct ScopedLease {
+    cache: Weak<CacheInner>,
+    key: KindKey,
+}
+
+impl Drop for ScopedLease {
+    fn drop(&mut self) {
+        let Some(inner) = self.cache.upgrade() else { return };
+        let (Ok(mut kinds), Ok(mut scoped)) = (inner.kinds.lock(), inner.scoped.lock()) else { return };
+        let Some((users, _)) = scoped.get_mut(&self.key) else { return };
+        *users -= 1;
+        if *users == 0 {
+            if let Some((_, task)) = scoped.remove(&self.key) {
+                task.abort();
+            }
+            kinds.remove(&self.key);
+        }
+    }
+}
+
+/// Cluster-scoped informer cache: one reflector store per kind, started the first time the
+/// kind is requested and shared by every view and insight. Dropping the last clone stops
+/// all watches, so a new cache is created whenever the client changes.
+#[derive(Clone)]
+pub struct ClusterCache {
+    inner: Arc<CacheInner>,
+}
+
+impl PartialEq for ClusterCache {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl ClusterCache {
+    pub fn new(client: Client) -> Self {
+        Self {
+            inner: Arc::new(CacheInner {
+                client,
+                kinds: Mutex::new(HashMap::new()),
+                tasks: Mutex::new(Vec::new()),
+                scoped: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Client the cache watches with, for calls the cache doesn't cover (metrics, logs, writes)
+    pub fn client(&self) -> Client {
+        self.inner.client.clone()
+    }
+
+    fn kind<K: CachedResource>(&self) -> KindCache<K> {
+        let key = (TypeId::of::<K>(), None);
+        let mut kinds = self.inner.kinds.lock().expect("cache lock poisoned");
+        if let Some(existing) = kinds.get(&key) {
+            return downcast(&**existing);
+        }
+
+        let (entry, task) = watch_kind(Api::<K>::all(self.inner.client.clone()));
+        if let Ok(mut tasks) = self.inner.tasks.lock() {
+            tasks.push(task);
+        }
+        kinds.insert(key, Box::new(entry.clone()));
+        entry
+    }
+
+    fn scoped_kind<K>(&self, namespace: &str) -> (KindCache<K>, ScopedLease)
+    where
+        K: CachedResource + Resource<Scope = NamespaceResourceScope>,
+    {
+        let key = (TypeId::of::<K>(), Some(namespace.to_string()));
+        let mut kinds = self.inner.kinds.lock().expect("cache lock poisoned");
+        let mut scoped = self.inner.scoped.lock().expect("cache lock poisoned");
+        let entry = match kinds.get(&key) {
+            Some(existing) => downcast(&**existing),
+            None => {
+                let (entry, task) = watch_kind(Api::<K>::namespaced(self.inner.client.clone(), namespace));
+                kinds.insert(key.clone(), Box::new(entry.clone()));
+                scoped.insert(key.clone(), (0, task));
+                entry
+            }
+        };
+        if let Some((users, _)) = scoped.get_mut(&key) {
+            *users += 1;
+        }
+        let lease = ScopedLease {
+            cache: Arc::downgrade(&self.inner),
+            key,
+        };
+        (entry, lease)
+    }
+
+    /// All cached objects of a kind, waiting for the initial list on first use.
+    /// Fails if the kind can't be listed yet, e.g. for lack of permissions.
+    pub async fn list<K: CachedResource>(&self) -> AppResult<Vec<K>> {
+        let KindCache { store, mut state } = self.kind::<K>();
+        loop {
+            {
+                let current = state.borrow_and_update();
+                if current.version > 0 {
+                    return Ok(snapshot(&store));
+                }
+                if let Some(e) = &current.error {
+                    return Err(e.clone());
+                }
+            }
+            if state.changed().await.is_err() {
+                return Err(AppError::Other(format!("Watch for {} stopped", K::kind(&()))));
+            }
+        }
+    }
+
+    /// Cached objects of a kind, re-yielded every time the set changes. Watch errors are
+    /// yielded too, but the watch keeps retrying so the stream only ends with the cache.
+    pub fn snapshots<K: CachedResource>(&self) -> impl Stream<Item = AppResult<Vec<K>>> {
+        kind_snapshots(self.kind::<K>(), None)
+    }
+
+    /// Cached objects of a namespaced kind in `namespace`, "All" for every namespace. Users who
+    /// can't list the kind across the cluster get a watch on just that namespace instead.
+    pub fn scoped_snapshots<K>(&self, namespace: &str, cluster_wide: bool) -> BoxStream<'static, AppResult<Vec<K>>>
+    where
+        K: CachedResource + Resource<Scope = NamespaceResourceScope>,
+    {
+        if cluster_wide || namespace == "All" {
+            let namespace = namespace.to_string();
+            return self
+                .snapshots::<K>()
+                .map(move |snapshot| snapshot.map(|items| filter_namespace(items, &namespace)))
+                .boxed();
+        }
+        let (entry, lease) = self.scoped_kind::<K>(namespace);
+        kind_snapshots(entry, Some(lease)).boxed()
+    }
+}
+
+fn downcast<K: CachedResource>(entry: &(dyn Any + Send + Sync)) -> KindCache<K> {
+    entry
+        .downcast_ref::<KindCache<K>>()
+        .expect("cache entry matches its type id")
+        .clone()
+}
+
+/// Start a reflector for `api`, returning its store and sync state plus the task running it
+fn watch_kind<K: CachedResource>(api: Api<K>) -> (KindCache<K>, AbortHandle) {
+    let (store, writer) = reflector::store();
+    let (state_tx, state) = watch::channel(KindState::default());
+    let task = tokio::spawn(async move {
+        let mut events = Box::pin(watcher(api, watcher::Config::default()).default_backoff().reflect(writer));
+        while let Some(event) = events.next().await {
+            match event {
+                // The store swaps in a (re-)list atomically once it is complete
+                Ok(watcher::Event::Init) | Ok(watcher::Event::InitApply(_)) => {}
+                Ok(_) => state_tx.send_modify(|state| {
+                    state.version += 1;
+                    state.error = None;
+                }),
+                Err(e) => {
+                    tracing::warn!("Watch for {} failed: {}", K::kind(&()), e);
+                    let error = AppError::from_watcher(e, &K::plural(&()));
+                    state_tx.send_modify(|state| state.error = Some(error));
+                }
+            }
+        }
+    });
+    (KindCache { store, state }, task.abort_handle())
+}
+
+/// Snapshots of one cached kind. A scoped watch's `lease` lives as long as the stream.
+fn kind_snapshots<K: CachedResource>(
+    entry: KindCache<K>,
+    lease: Option<ScopedLease>,
+) -> impl Stream<Item = AppResult<Vec<K>>> {
+    let KindCache { store, mut state } = entry;
+    // Yield the current contents straight away if the kind is already synced
+    state.mark_changed();
+    stream::unfold((store, state, lease), |(store, mut state, lease)| async move {
+        loop {
+            state.changed().await.ok()?;
+            let current = state.borrow_and_update().clone();
+            if let Some(e) = current.error {
+                return Some((Err(e), (store, state, lease)));
+            }
+            if current.version > 0 {
+                return Some((Ok(snapshot(&store)), (store, state, lease)));
+            }
+        }
+    })
+}
+
+fn snapshot<K: CachedResource>(store: &Store<K>) -> Vec<K> {
+    let mut items: Vec<K> = store.state().iter().map(|obj| (**obj).clone()).collect();
+    items.sort_by_key(|obj| (obj.namespace(), obj.name_any()));
+    items
+}
+
+/// Keep only objects in `namespace`, where "All" (the namespace selector's default) keeps everything
+pub fn filter_namespace<K: Resource>(items: Vec<K>, namespace: &str) -> Vec<K> {
+    if namespace == "All" {
+        return items;
+    }
+    items
+        .into_iter()
+        .filter(|item| item.meta().namespace.as_deref() == Some(namespace))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+
+    #[tokio::test]
+    async fn test_scoped_watches_stop_when_unused() {
+        // Nothing listens here, so the watches just keep retrying
+        let config = kube::Config::new("http://127.0.0.1:9".parse().unwrap());
+        let cache = ClusterCache::new(Client::try_from(config).unwrap());
+        let watches = || cache.inner.kinds.lock().unwrap().len();
+
+        // A view switching namespaces drops its old stream before or after opening the next
+        let mut current = cache.scoped_snapshots::<Pod>("ns-0", false);
+        for i in 1..50 {
+            current = cache.scoped_snapshots::<Pod>(&format!("ns-{}", i), false);
+            assert!(watches() <= 2);
+        }
+        assert_eq!(watches(), 1);
+
+        // Streams on the same namespace share one watch, which outlives all but the last
+        let configs = cache.scoped_snapshots::<ConfigMap>("ns-49", false);
+        let pods = cache.scoped_snapshots::<Pod>("ns-49", false);
+        assert_eq!(watches(), 2);
+        drop(current);
+        assert_eq!(watches(), 2);
+        drop((pods, configs));
+        assert_eq!(watches(), 0);
+        assert!(cache.inner.scoped.lock().unwrap().is_empty());
+    }
+}
@@ -1,52 +1,11 @@
 use dioxus::logger::tracing;
 use k8s_openapi::api::core::v1::Node;
-use k8s_openapi::apimachinery::pkg::{api::resource::Quantity, apis::meta::v1::ObjectMeta};
-use kube::api::TypeMeta;
-use kube::{
-    api::{Api, ListParams},
-    core::{ObjectList, Resource},
-    Client,
-};
-use serde::Deserialize;
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 
-#[derive(Deserialize, Clone, Debug)]
-struct NodeMetrics {
-    metadata: ObjectMeta,
-    usage: std::collections::BTreeMap<String, Quantity>,
-}
-
-impl Resource for NodeMetrics {
-    type DynamicType = ();
-    type Scope = kube::core::NamespaceResourceScope;
-
-    fn group(_dt: &()) -> std::borrow::Cow<'static, str> {
-        "metrics.k8s.io".into()
-    }
-    
-    fn version(_dt: &()) -> std::borrow::Cow<'static, str> {
-        "v1beta1".into()
-    }
-    
-    fn kind(_dt: &()) -> std::borrow::Cow<'static, str> {
-        "NodeMetrics".into()
-    }
-    
-    fn plural(_dt: &()) -> std::borrow::Cow<'static, str> {
-        "nodes".into()
-    }
-
-    fn api_version(_dt: &()) -> std::borrow::Cow<'static, str> {
-        "metrics.k8s.io/v1beta1".into()
-    }
-
-    fn meta(&self) -> &ObjectMeta {
-        &self.metadata
-    }
-
-    fn meta_mut(&mut self) -> &mut ObjectMeta {
-        &mut self.metadata
-    }
-}
+use super::cache::ClusterCache;
+use super::metrics_history::{MetricsSeries, UsageSnapshot};
+use super::quantity::ParsedQuantity;
+use crate::contexts::error::AppResult;
 
 #[derive(Clone, Debug, Default)]
 pub struct ClusterStatus {
@@ -69,16 +28,17 @@ pub struct ClusterResourceUsage {
     pub cluster_status: ClusterStatus,
 }
 
-pub async fn get_cluster_resources(client: Client) -> ClusterResourceUsage {
-    let nodes: Api<Node> = Api::all(client.clone());
-    let pods: Api<k8s_openapi::api::core::v1::Pod> = Api::all(client.clone());
-    let namespaces: Api<k8s_openapi::api::core::v1::Namespace> = Api::all(client.clone());
-    let metrics_api: Api<NodeMetrics> = Api::all(client);
+/// Node capacity across the cluster, with usage from the background sampler's newest round.
+/// Usage stays at zero until there is one, but failing to list nodes is an error.
+pub async fn get_cluster_resources(
+    cache: &ClusterCache,
+    metrics: Option<&UsageSnapshot>,
+) -> AppResult<ClusterResourceUsage> {
     let mut usage = ClusterResourceUsage::default();
 
     // Get namespace count (excluding terminating ones)
-    if let Ok(ns_list) = namespaces.list(&ListParams::default()).await {
-        usage.namespace_count = ns_list.items.iter()
+    if let Ok(ns_list) = cache.list::<k8s_openapi::api::core::v1::Namespace>().await {
+        usage.namespace_count = ns_list.iter()
             .filter(|ns| {
                 ns.status.as_ref()
                     .and_then(|status| status.phase.as_ref())
@@ -89,29 +49,10 @@ pub async fn get_cluster_resources(client: Client) -> ClusterResourceUsage {
     }
 
     // Get all nodes and metrics
-    let node_list = match nodes.list(&ListParams::default()).await {
-        Ok(list) => {
-            usage.node_count = list.items.len();
-            list
-        },
-        Err(_) => return usage,
-    };
-    
-    let metrics_list = match metrics_api.list(&ListParams::default()).await {
-        Ok(list) => list,
-        Err(_) => ObjectList {
-            types: TypeMeta::default(),
-            metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ListMeta::default(),
-            items: vec![],
-        },
-    };
-
-    // Create metrics lookup map for efficient access
-    let metrics_map: std::collections::HashMap<String, &NodeMetrics> = metrics_list.items.iter()
-        .filter_map(|m| m.metadata.name.as_ref().map(|name| (name.clone(), m)))
-        .collect();
+    let node_list = cache.list::<Node>().await?;
+    usage.node_count = node_list.len();
 
-    for node in &node_list.items {
+    for node in &node_list {
         // Get node name
         let empty_string = String::new();
         let node_name = node.metadata.name.as_ref().unwrap_or(&empty_string);
@@ -120,58 +61,48 @@ pub async fn get_cluster_resources(client: Client) -> ClusterResourceUsage {
         if let Some(allocatable) = &node.status.as_ref().and_then(|s| s.allocatable.as_ref()) {
             // CPU
             if let Some(cpu) = allocatable.get("cpu") {
-                usage.cpu_total += parse_cpu_value(cpu);
+                usage.cpu_total += ParsedQuantity::from_quantity(cpu).as_f64();
             }
             // Memory
             if let Some(memory) = allocatable.get("memory") {
-                usage.memory_total += parse_memory_value(memory);
-            }
-            // Storage
-            if let Some(storage) = allocatable.get("ephemeral-storage") {
-                usage.storage_total += parse_storage_value(storage);
+                usage.memory_total += gibibytes(memory);
             }
         }
 
         // Get metrics for this node
-        if let Some(node_metrics) = metrics_map.get(node_name) {
-            // CPU usage from metrics
-            if let Some(cpu) = node_metrics.usage.get("cpu") {
-                let cpu_value = parse_cpu_value(cpu);
-                tracing::debug!("Node {} CPU usage: {}", node_name, cpu_value);
-                usage.cpu_used += cpu_value;
-            }
-            // Memory usage from metrics
-            if let Some(memory) = node_metrics.usage.get("memory") {
-                let memory_value = parse_memory_value(memory);
-                tracing::debug!("Node {} Memory usage: {}", node_name, memory_value);
-                usage.memory_used += memory_value;
-            }
+        let series = MetricsSeries::Node(node_name.clone());
+        if let Some(sample) = metrics.and_then(|m| m.samples.get(&series)) {
+            tracing::debug!("Node {} CPU usage: {}, memory usage: {}", node_name, sample.cpu, sample.memory);
+            usage.cpu_used += sample.cpu;
+            usage.memory_used += sample.memory / GIBIBYTE;
         } else {
             tracing::debug!("No metrics found for node {}", node_name);
         }
 
-        // Storage usage from node status (since it's not in metrics)
-        if let Some(fs) = node.status.as_ref()
-            .and_then(|s| s.capacity.as_ref())
-            .and_then(|a| a.get("ephemeral-storage")) 
-        {
-            usage.storage_used += parse_storage_value(fs);
+        // Storage is the node's root filesystem as the kubelet reports it, falling back to
+        // allocatable ephemeral storage with unknown usage
+        match metrics.and_then(|m| m.filesystems.as_ref()).and_then(|fs| fs.nodes.get(node_name)) {
+            Some(fs) => {
+                usage.storage_total += fs.capacity / GIBIBYTE;
+                usage.storage_used += fs.used / GIBIBYTE;
+            }
+            None => {
+                if let Some(storage) = node.status.as_ref()
+                    .and_then(|s| s.allocatable.as_ref())
+                    .and_then(|a| a.get("ephemeral-storage"))
+                {
+                    usage.storage_total += gibibytes(storage);
+                }
+            }
         }
     }
 
     // Get pod counts
-    let pod_list = match pods.list(&ListParams::default()).await {
-        Ok(list) => list,
-        Err(_) => ObjectList {
-            types: TypeMeta::default(),
-            metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ListMeta::default(),
-            items: vec![],
-        },
-    };
+    let pod_list = cache.list::<k8s_openapi::api::core::v1::Pod>().await.unwrap_or_default();
 
     // Count total pods and running pods
-    usage.pod_count = pod_list.items.len();
-    usage.running_pods = pod_list.items.iter()
+    usage.pod_count = pod_list.len();
+    usage.running_pods = pod_list.iter()
         .filter(|pod| {
             pod.status.as_ref()
                 .and_then(|status| status.phase.as_ref())
@@ -183,38 +114,16 @@ pub async fn get_cluster_resources(client: Client) -> ClusterResourceUsage {
     tracing::debug!("Pod counts: {}/{} (running/total)", usage.running_pods, usage.pod_count);
 
     // Calculate cluster status
-    usage.cluster_status = calculate_cluster_status(&node_list.items, &pod_list.items, &usage);
+    usage.cluster_status = calculate_cluster_status(&node_list, &pod_list, &usage);
 
-    usage
+    Ok(usage)
 }
 
-fn parse_cpu_value(cpu: &k8s_openapi::apimachinery::pkg::api::resource::Quantity) -> f64 {
-    let cpu_str = cpu.0.to_string();
-    if cpu_str.ends_with('m') {
-        // Convert millicores to cores
-        cpu_str.trim_end_matches('m').parse::<f64>().unwrap_or(0.0) / 1000.0
-    } else {
-        // Already in cores
-        cpu_str.parse::<f64>().unwrap_or(0.0)
-    }
-}
-
-fn parse_memory_value(memory: &k8s_openapi::apimachinery::pkg::api::resource::Quantity) -> f64 {
-    let mem_str = memory.0.to_string();
-    if mem_str.ends_with("Ki") {
-        mem_str.trim_end_matches("Ki").parse::<f64>().unwrap_or(0.0) * 1024.0 / (1024.0 * 1024.0 * 1024.0) // Convert to GB
-    } else if mem_str.ends_with("Mi") {
-        mem_str.trim_end_matches("Mi").parse::<f64>().unwrap_or(0.0) / 1024.0 // Convert to GB
-    } else if mem_str.ends_with("Gi") {
-        mem_str.trim_end_matches("Gi").parse::<f64>().unwrap_or(0.0) // Already in GB
-    } else {
-        // Assume bytes
-        mem_str.parse::<f64>().unwrap_or(0.0) / (1024.0 * 1024.0 * 1024.0) // Convert to GB
-    }
-}
+const GIBIBYTE: f64 = 1024.0 * 1024.0 * 1024.0;
 
-fn parse_storage_value(storage: &k8s_openapi::apimachinery::pkg::api::resource::Quantity) -> f64 {
-    parse_memory_value(storage) // Storage uses same format as memory
+/// Memory and storage are reported in GiB
+fn gibibytes(quantity: &Quantity) -> f64 {
+    ParsedQuantity::from_quantity(quantity).as_f64() / GIBIBYTE
 }
 
 fn calculate_cluster_status(
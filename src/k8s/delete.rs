@@ -0,0 +1,109 @@
+use std::fmt;
+
+use kube::{
+    api::{Api, ApiResource, DeleteParams, DynamicObject, PropagationPolicy},
+    Client,
+};
+
+/// What the API server does with objects owned by the one being deleted
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DeletePropagation {
+    /// Delete the object now and garbage-collect its dependents afterwards
+    #[default]
+    Background,
+    /// Keep the object around until all its dependents are gone
+    Foreground,
+    /// Delete only the object and leave its dependents running
+    Orphan,
+}
+
+impl DeletePropagation {
+    pub const ALL: [DeletePropagation; 3] = [
+        DeletePropagation::Background,
+        DeletePropagation::Foreground,
+        DeletePropagation::Orphan,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            DeletePropagation::Background => "Delete now, clean up dependents afterwards",
+            DeletePropagation::Foreground => "Wait for dependents to be deleted first",
+            DeletePropagation::Orphan => "Leave dependents running",
+        }
+    }
+}
+
+impl fmt::Display for DeletePropagation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeletePropagation::Background => write!(f, "Background"),
+            DeletePropagation::Foreground => write!(f, "Foreground"),
+            DeletePropagation::Orphan => write!(f, "Orphan"),
+        }
+    }
+}
+
+impl std::str::FromStr for DeletePropagation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DeletePropagation::ALL
+            .into_iter()
+            .find(|p| p.to_string() == s)
+            .ok_or_else(|| format!("Unknown propagation policy: {}", s))
+    }
+}
+
+impl From<DeletePropagation> for PropagationPolicy {
+    fn from(propagation: DeletePropagation) -> Self {
+        match propagation {
+            DeletePropagation::Background => PropagationPolicy::Background,
+            DeletePropagation::Foreground => PropagationPolicy::Foreground,
+            DeletePropagation::Orphan => PropagationPolicy::Orphan,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeleteOptions {
+    pub propagation: DeletePropagation,
+    /// Seconds to let pods shut down, `None` for the object's own default
+    pub grace_period_seconds: Option<u32>,
+}
+
+impl DeleteOptions {
+    /// Remove the object immediately without waiting for the kubelet to confirm termination,
+    /// the equivalent of `kubectl delete --force --grace-period=0`
+    pub fn force() -> Self {
+        Self {
+            propagation: DeletePropagation::Background,
+            grace_period_seconds: Some(0),
+        }
+    }
+
+    fn params(&self) -> DeleteParams {
+        DeleteParams {
+            propagation_policy: Some(self.propagation.into()),
+            grace_period_seconds: self.grace_period_seconds,
+            ..DeleteParams::default()
+        }
+    }
+}
+
+/// Delete any object by its API resource, e.g. `ApiResource::erase::<Pod>(&())`.
+/// `namespace` is `None` for cluster-scoped kinds such as namespaces.
+pub async fn delete_resource(
+    client: Client,
+    resource: &ApiResource,
+    namespace: Option<&str>,
+    name: &str,
+    options: &DeleteOptions,
+) -> Result<(), kube::Error> {
+    let api: Api<DynamicObject> = match namespace {
+        Some(ns) => Api::namespaced_with(client, ns, resource),
+        None => Api::all_with(client, resource),
+    };
+    // Either the object as it is being finalized or a status once it's gone; both mean success
+    api.delete(name, &options.params()).await?;
+    Ok(())
+}
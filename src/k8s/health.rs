@@ -0,0 +1,220 @@
+use std::time::{Duration, Instant};
+
+use k8s_openapi::chrono::{DateTime, Utc};
+use kube::Client;
+
+use crate::contexts::error::error_chain;
+
+/// How often a healthy connection is probed
+pub const HEALTH_PROBE_INTERVAL: Duration = Duration::from_secs(15);
+/// First retry delay after a failed probe, doubled on each further failure
+pub const HEALTH_RETRY_BASE: Duration = Duration::from_secs(2);
+/// Longest wait between retries
+pub const HEALTH_RETRY_MAX: Duration = Duration::from_secs(60);
+
+/// State of the connection to the API server
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConnectionStatus {
+    /// No probe has finished yet
+    Connecting,
+    Healthy,
+    /// The API server answers but `/readyz` reports it isn't ready
+    Degraded,
+    /// Credentials were rejected or could not be obtained, e.g. an expired token
+    Unauthorized,
+    /// A certificate in the TLS handshake has expired or is not yet valid
+    CertificateExpired,
+    /// The API server could not be reached at all
+    Unreachable,
+}
+
+impl ConnectionStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConnectionStatus::Connecting => "Connecting",
+            ConnectionStatus::Healthy => "Connected",
+            ConnectionStatus::Degraded => "Degraded",
+            ConnectionStatus::Unauthorized => "Unauthorized",
+            ConnectionStatus::CertificateExpired => "Certificate expired",
+            ConnectionStatus::Unreachable => "Unreachable",
+        }
+    }
+
+    /// CSS modifier for the status pill
+    pub fn class(&self) -> &'static str {
+        match self {
+            ConnectionStatus::Connecting => "connecting",
+            ConnectionStatus::Healthy => "healthy",
+            ConnectionStatus::Degraded => "degraded",
+            ConnectionStatus::Unauthorized | ConnectionStatus::CertificateExpired | ConnectionStatus::Unreachable => {
+                "failing"
+            }
+        }
+    }
+}
+
+/// Latest result of the background health probe
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterHealth {
+    pub status: ConnectionStatus,
+    /// `gitVersion` of the API server, kept from the last successful probe
+    pub server_version: Option<String>,
+    /// Round trip of the `/version` request in the last successful probe
+    pub latency: Option<Duration>,
+    pub last_error: Option<String>,
+    pub last_checked: Option<DateTime<Utc>>,
+    /// Failed probes in a row, driving the retry backoff
+    pub consecutive_failures: u32,
+}
+
+impl Default for ClusterHealth {
+    fn default() -> Self {
+        Self {
+            status: ConnectionStatus::Connecting,
+            server_version: None,
+            latency: None,
+            last_error: None,
+            last_checked: None,
+            consecutive_failures: 0,
+        }
+    }
+}
+
+impl ClusterHealth {
+    /// Record the outcome of a probe
+    pub fn record(&mut self, result: Result<(String, Duration), (ConnectionStatus, String)>) {
+        self.last_checked = Some(Utc::now());
+        match result {
+            Ok((version, latency)) => {
+                self.status = ConnectionStatus::Healthy;
+                self.server_version = Some(version);
+                self.latency = Some(latency);
+                self.consecutive_failures = 0;
+            }
+            Err((status, error)) => {
+                self.status = status;
+                self.last_error = Some(error);
+                self.consecutive_failures += 1;
+            }
+        }
+    }
+
+    /// How long to wait before the next probe: the regular interval while healthy,
+    /// exponential backoff while failing
+    pub fn next_probe_in(&self) -> Duration {
+        if self.consecutive_failures == 0 {
+            return HEALTH_PROBE_INTERVAL;
+        }
+        let factor = 2u32.saturating_pow(self.consecutive_failures - 1);
+        HEALTH_RETRY_BASE.saturating_mul(factor).min(HEALTH_RETRY_MAX)
+    }
+}
+
+/// Probe the API server: `/version` for the server version and credentials, then `/readyz`.
+/// Returns the version and the round trip of the `/version` request.
+pub async fn probe_health(client: &Client) -> Result<(String, Duration), (ConnectionStatus, String)> {
+    let started = Instant::now();
+    let info = client.apiserver_version().await.map_err(classify_error)?;
+    let latency = started.elapsed();
+
+    let request = http::Request::get("/readyz")
+        .body(Vec::new())
+        .map_err(|e| (ConnectionStatus::Unreachable, e.to_string()))?;
+    match client.request_text(request).await {
+        Ok(_) => {}
+        // Some clusters don't let every user read /readyz; /version already proved the connection
+        Err(kube::Error::Api(response)) if response.code == 401 || response.code == 403 => {}
+        Err(kube::Error::Api(response)) => {
+            return Err((ConnectionStatus::Degraded, format!("readyz: {}", response.message)));
+        }
+        Err(e) => return Err(classify_error(e)),
+    }
+
+    Ok((info.git_version, latency))
+}
+
+/// Tell credential and certificate problems apart from plain network failures
+fn classify_error(error: kube::Error) -> (ConnectionStatus, String) {
+    // TLS errors are nested a few levels deep, so look at the whole chain
+    let message = error_chain(&error);
+
+    let lowercase = message.to_lowercase();
+    let status = match &error {
+        kube::Error::Api(response) if response.code == 401 || response.code == 403 => ConnectionStatus::Unauthorized,
+        kube::Error::Auth(_) => ConnectionStatus::Unauthorized,
+        _ if lowercase.contains("certificate")
+            && (lowercase.contains("expired") || lowercase.contains("notvalidyet") || lowercase.contains("not yet valid")) =>
+        {
+            ConnectionStatus::CertificateExpired
+        }
+        _ => ConnectionStatus::Unreachable,
+    };
+    (status, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kube::core::ErrorResponse;
+    use std::fmt;
+
+    #[test]
+    fn test_retry_backoff() {
+        let mut health = ClusterHealth::default();
+        assert_eq!(health.next_probe_in(), HEALTH_PROBE_INTERVAL);
+
+        let mut delays = Vec::new();
+        for _ in 0..8 {
+            health.record(Err((ConnectionStatus::Unreachable, "connection refused".to_string())));
+            delays.push(health.next_probe_in().as_secs());
+        }
+        assert_eq!(delays, vec![2, 4, 8, 16, 32, 60, 60, 60]);
+
+        health.consecutive_failures = u32::MAX;
+        assert_eq!(health.next_probe_in(), HEALTH_RETRY_MAX);
+
+        health.record(Ok(("v1.31.0".to_string(), Duration::from_millis(20))));
+        assert_eq!(health.next_probe_in(), HEALTH_PROBE_INTERVAL);
+    }
+
+    /// An error with a cause, like the layers hyper and rustls wrap a TLS failure in
+    #[derive(Debug)]
+    struct Layer(&'static str, Option<Box<Layer>>);
+
+    impl fmt::Display for Layer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Layer {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.1.as_deref().map(|layer| layer as _)
+        }
+    }
+
+    #[test]
+    fn test_classify_error() {
+        let api_error = |code| {
+            kube::Error::Api(ErrorResponse {
+                status: "Failure".to_string(),
+                message: "denied".to_string(),
+                reason: "Unauthorized".to_string(),
+                code,
+            })
+        };
+        assert_eq!(classify_error(api_error(401)).0, ConnectionStatus::Unauthorized);
+        assert_eq!(classify_error(api_error(403)).0, ConnectionStatus::Unauthorized);
+
+        let expired = Layer(
+            "client error (Connect)",
+            Some(Box::new(Layer("invalid peer certificate: certificate has expired", None))),
+        );
+        let (status, message) = classify_error(kube::Error::Service(Box::new(expired)));
+        assert_eq!(status, ConnectionStatus::CertificateExpired);
+        assert!(message.contains("client error (Connect): invalid peer certificate"));
+
+        let refused = Layer("client error (Connect)", Some(Box::new(Layer("Connection refused (os error 111)", None))));
+        assert_eq!(classify_error(kube::Error::Service(Box::new(refused))).0, ConnectionStatus::Unreachable);
+    }
+}
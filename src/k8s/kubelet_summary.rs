@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use dioxus::logger::tracing;
+use futures::{stream, StreamExt};
+use k8s_openapi::{api::core::v1::Node, chrono::{DateTime, Utc}};
+use kube::{
+    api::{Api, ListParams},
+    Client,
+};
+use serde::Deserialize;
+
+use super::metrics_history::{FsUsage, MetricSample, MetricsSeries};
+use crate::contexts::error::{AppError, AppResult};
+
+/// Summaries requested at once, each going through the API server's node proxy
+const MAX_CONCURRENT_SUMMARIES: usize = 8;
+
+/// The kubelet's `/stats/summary` response, trimmed to what's shown. Every kubelet serves
+/// one, so it stands in for metrics-server, and it's the only source of filesystem usage.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Summary {
+    #[serde(default)]
+    pub node: NodeStats,
+    #[serde(default)]
+    pub pods: Vec<PodStats>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStats {
+    pub node_name: String,
+    pub cpu: Option<CpuStats>,
+    pub memory: Option<MemoryStats>,
+    pub fs: Option<FsStats>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PodStats {
+    pub pod_ref: ObjectRef,
+    #[serde(default)]
+    pub containers: Vec<ContainerStats>,
+    pub cpu: Option<CpuStats>,
+    pub memory: Option<MemoryStats>,
+    #[serde(default)]
+    pub volume: Vec<VolumeStats>,
+    #[serde(rename = "ephemeral-storage")]
+    pub ephemeral_storage: Option<FsStats>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ObjectRef {
+    pub name: String,
+    pub namespace: String,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ContainerStats {
+    pub name: String,
+    pub cpu: Option<CpuStats>,
+    pub memory: Option<MemoryStats>,
+    pub rootfs: Option<FsStats>,
+    pub logs: Option<FsStats>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuStats {
+    pub usage_nano_cores: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryStats {
+    /// What metrics-server reports as memory usage, and what the kubelet evicts on
+    pub working_set_bytes: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FsStats {
+    pub capacity_bytes: Option<u64>,
+    pub used_bytes: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeStats {
+    #[serde(flatten)]
+    pub fs: FsStats,
+    pub pvc_ref: Option<ObjectRef>,
+}
+
+impl FsStats {
+    fn usage(&self) -> Option<FsUsage> {
+        Some(FsUsage {
+            used: self.used_bytes? as f64,
+            capacity: self.capacity_bytes.unwrap_or_default() as f64,
+        })
+    }
+}
+
+fn sample(at: DateTime<Utc>, cpu: Option<&CpuStats>, memory: Option<&MemoryStats>, storage: Option<f64>) -> MetricSample {
+    MetricSample {
+        at,
+        cpu: cpu.and_then(|cpu| cpu.usage_nano_cores).unwrap_or_default() as f64 / 1e9,
+        memory: memory.and_then(|memory| memory.working_set_bytes).unwrap_or_default() as f64,
+        storage,
+    }
+}
+
+impl Summary {
+    /// Node, pod and container usage, with ephemeral storage for each
+    pub fn samples(&self, at: DateTime<Utc>) -> Vec<(MetricsSeries, MetricSample)> {
+        let node_storage = self.node.fs.as_ref().and_then(FsStats::usage).map(|fs| fs.used);
+        let mut samples = vec![(
+            MetricsSeries::Node(self.node.node_name.clone()),
+            sample(at, self.node.cpu.as_ref(), self.node.memory.as_ref(), node_storage),
+        )];
+        for pod in &self.pods {
+            let ObjectRef { namespace, name } = &pod.pod_ref;
+            for container in &pod.containers {
+                // A container's ephemeral storage is its writable layer plus its logs
+                let storage = [&container.rootfs, &container.logs]
+                    .into_iter()
+                    .filter_map(|fs| fs.as_ref().and_then(FsStats::usage))
+                    .map(|fs| fs.used)
+                    .reduce(|a, b| a + b);
+                let series = MetricsSeries::Container {
+                    namespace: namespace.clone(),
+                    pod: name.clone(),
+                    container: container.name.clone(),
+                };
+                samples.push((series, sample(at, container.cpu.as_ref(), container.memory.as_ref(), storage)));
+            }
+            let storage = pod.ephemeral_storage.as_ref().and_then(FsStats::usage).map(|fs| fs.used);
+            let series = MetricsSeries::Pod { namespace: namespace.clone(), name: name.clone() };
+            samples.push((series, sample(at, pod.cpu.as_ref(), pod.memory.as_ref(), storage)));
+        }
+        samples
+    }
+
+    /// Usage of the node's root filesystem
+    pub fn node_filesystem(&self) -> Option<FsUsage> {
+        self.node.fs.as_ref().and_then(FsStats::usage)
+    }
+
+    /// Usage of each mounted PVC, by namespace and claim name
+    pub fn volumes(&self) -> impl Iterator<Item = ((String, String), FsUsage)> + '_ {
+        self.pods.iter().flat_map(|pod| {
+            pod.volume.iter().filter_map(|volume| {
+                let claim = volume.pvc_ref.as_ref()?;
+                Some(((claim.namespace.clone(), claim.name.clone()), volume.fs.usage()?))
+            })
+        })
+    }
+}
+
+/// Read one node's stats summary through the API server's node proxy
+pub async fn fetch_summary(client: &Client, node: &str) -> AppResult<Summary> {
+    let request = http::Request::get(format!("/api/v1/nodes/{}/proxy/stats/summary", node))
+        .body(Vec::new())
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    client
+        .request::<Summary>(request)
+        .await
+        .map_err(|e| AppError::from_kube(e, "get", "nodes/proxy"))
+}
+
+/// Stats summaries of every node that answers. Unreachable kubelets are skipped, so this only
+/// fails if nodes can't be listed or none of them answer.
+pub async fn fetch_summaries(client: &Client) -> AppResult<Vec<Summary>> {
+    let nodes: Api<Node> = Api::all(client.clone());
+    let names: Vec<String> = nodes
+        .list_metadata(&ListParams::default())
+        .await
+        .map_err(|e| AppError::from_kube(e, "list", "nodes"))?
+        .items
+        .into_iter()
+        .filter_map(|node| node.metadata.name)
+        .collect();
+
+    let results: Vec<_> = stream::iter(names)
+        .map(|name| async move {
+            let result = fetch_summary(client, &name).await;
+            (name, result)
+        })
+        .buffer_unordered(MAX_CONCURRENT_SUMMARIES)
+        .collect()
+        .await;
+    let mut summaries = Vec::new();
+    let mut first_error = None;
+    for (name, result) in results {
+        match result {
+            Ok(summary) => summaries.push(summary),
+            Err(e) => {
+                tracing::debug!("No stats summary from node {}: {}", name, e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) if summaries.is_empty() => Err(e),
+        _ => Ok(summaries),
+    }
+}
+
+/// Node filesystem usage by node name
+pub fn node_filesystems(summaries: &[Summary]) -> HashMap<String, FsUsage> {
+    summaries
+        .iter()
+        .filter_map(|summary| Some((summary.node.node_name.clone(), summary.node_filesystem()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_samples() {
+        let summary: Summary = k8s_openapi::serde_json::from_str(
+            r#"{
+                "node": {
+                    "nodeName": "node-a",
+                    "cpu": {"time": "2024-01-01T00:00:00Z", "usageNanoCores": 1500000000},
+                    "memory": {"workingSetBytes": 2147483648, "usageBytes": 3000000000},
+                    "fs": {"availableBytes": 60, "capacityBytes": 100, "usedBytes": 40}
+                },
+                "pods": [{
+                    "podRef": {"name": "web", "namespace": "default", "uid": "1"},
+                    "containers": [{
+                        "name": "app",
+                        "cpu": {"usageNanoCores": 250000000},
+                        "memory": {"workingSetBytes": 1048576},
+                        "rootfs": {"usedBytes": 10, "capacityBytes": 100},
+                        "logs": {"usedBytes": 5, "capacityBytes": 100}
+                    }],
+                    "cpu": {"usageNanoCores": 250000000},
+                    "memory": {"workingSetBytes": 1048576},
+                    "volume": [
+                        {"name": "data", "usedBytes": 7, "capacityBytes": 20, "pvcRef": {"name": "data-web", "namespace": "default"}},
+                        {"name": "kube-api-access", "usedBytes": 1, "capacityBytes": 2}
+                    ],
+                    "ephemeral-storage": {"usedBytes": 16}
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let samples: HashMap<_, _> = summary.samples(at).into_iter().collect();
+        let node = samples[&MetricsSeries::Node("node-a".to_string())];
+        assert_eq!((node.cpu, node.memory, node.storage), (1.5, 2147483648.0, Some(40.0)));
+        let container = samples[&MetricsSeries::Container {
+            namespace: "default".to_string(),
+            pod: "web".to_string(),
+            container: "app".to_string(),
+        }];
+        assert_eq!((container.cpu, container.memory, container.storage), (0.25, 1048576.0, Some(15.0)));
+        let pod = samples[&MetricsSeries::Pod { namespace: "default".to_string(), name: "web".to_string() }];
+        assert_eq!(pod.storage, Some(16.0));
+
+        assert_eq!(summary.node_filesystem(), Some(FsUsage { used: 40.0, capacity: 100.0 }));
+        let volumes: Vec<_> = summary.volumes().collect();
+        assert_eq!(
+            volumes,
+            vec![(("default".to_string(), "data-web".to_string()), FsUsage { used: 7.0, capacity: 20.0 })]
+        );
+    }
+}
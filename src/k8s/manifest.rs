@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use k8s_openapi::serde_json::Value;
+use kube::{
+    api::{ApiResource, DynamicObject, Patch, PatchParams},
+    core::{GroupVersion, GroupVersionKind},
+    discovery::{pinned_kind, Scope},
+    Client,
+};
+use serde::Deserialize;
+
+use crate::k8s::{dynamic_api, object_to_yaml, preview_diff, FIELD_MANAGER};
+use crate::utils::diff_utils::DiffLine;
+
+/// Kinds that other objects depend on, applied in this order before everything else.
+/// Kinds not listed here (custom resources, mostly) go last.
+const APPLY_ORDER: [&str; 33] = [
+    "Namespace",
+    "CustomResourceDefinition",
+    "PriorityClass",
+    "StorageClass",
+    "NetworkPolicy",
+    "ResourceQuota",
+    "LimitRange",
+    "PodDisruptionBudget",
+    "ServiceAccount",
+    "Secret",
+    "ConfigMap",
+    "PersistentVolume",
+    "PersistentVolumeClaim",
+    "ClusterRole",
+    "ClusterRoleBinding",
+    "Role",
+    "RoleBinding",
+    "Service",
+    "DaemonSet",
+    "Pod",
+    "ReplicationController",
+    "ReplicaSet",
+    "Deployment",
+    "HorizontalPodAutoscaler",
+    "StatefulSet",
+    "Job",
+    "CronJob",
+    "IngressClass",
+    "Ingress",
+    "APIService",
+    "MutatingWebhookConfiguration",
+    "ValidatingWebhookConfiguration",
+    "ValidatingAdmissionPolicy",
+];
+
+/// One object taken from a manifest, not yet resolved against the cluster
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManifestObject {
+    /// Position of the document in the manifest, starting at 1
+    pub document: usize,
+    pub api_version: String,
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApplyOutcome {
+    Created,
+    Configured,
+    Unchanged,
+    Failed(String),
+}
+
+impl fmt::Display for ApplyOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApplyOutcome::Created => write!(f, "created"),
+            ApplyOutcome::Configured => write!(f, "configured"),
+            ApplyOutcome::Unchanged => write!(f, "unchanged"),
+            ApplyOutcome::Failed(_) => write!(f, "failed"),
+        }
+    }
+}
+
+/// Result of applying one manifest object; `namespace` is the one it actually landed in.
+/// Dry runs also carry what the server would persist: the full YAML for new objects,
+/// a diff against the live object otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManifestResult {
+    pub document: usize,
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub outcome: ApplyOutcome,
+    pub preview: Option<String>,
+    pub diff: Option<Vec<DiffLine>>,
+}
+
+impl ManifestResult {
+    /// `Kind/name`, or `Kind namespace/name` for namespaced objects
+    pub fn display_name(&self) -> String {
+        match &self.namespace {
+            Some(ns) => format!("{} {}/{}", self.kind, ns, self.name),
+            None => format!("{}/{}", self.kind, self.name),
+        }
+    }
+}
+
+/// Split multi-document YAML into objects. Empty documents are skipped and `List` kinds
+/// are expanded into their items, like kubectl does.
+pub fn parse_manifest(text: &str) -> Result<Vec<ManifestObject>, String> {
+    let mut objects = Vec::new();
+    for (index, document) in serde_yaml::Deserializer::from_str(text).enumerate() {
+        let document_number = index + 1;
+        let value = Value::deserialize(document).map_err(|e| format!("document {}: {}", document_number, e))?;
+        if value.is_null() {
+            continue;
+        }
+
+        let is_list = value.get("kind").and_then(Value::as_str).is_some_and(|k| k.ends_with("List"));
+        match value.get("items").and_then(Value::as_array) {
+            Some(items) if is_list => {
+                for item in items {
+                    objects.push(manifest_object(document_number, item.clone())?);
+                }
+            }
+            _ => objects.push(manifest_object(document_number, value)?),
+        }
+    }
+    Ok(objects)
+}
+
+fn manifest_object(document: usize, value: Value) -> Result<ManifestObject, String> {
+    let field = |pointer: &str| value.pointer(pointer).and_then(Value::as_str).map(str::to_string);
+    let api_version = field("/apiVersion").ok_or_else(|| format!("document {}: missing apiVersion", document))?;
+    let kind = field("/kind").ok_or_else(|| format!("document {}: missing kind", document))?;
+    let name = field("/metadata/name")
+        .ok_or_else(|| format!("document {}: {} is missing metadata.name", document, kind))?;
+    let namespace = field("/metadata/namespace");
+    Ok(ManifestObject {
+        document,
+        api_version,
+        kind,
+        name,
+        namespace,
+        value,
+    })
+}
+
+/// Stable-sort objects so Namespaces and CRDs come first, then config, then workloads
+pub fn sort_for_apply(objects: &mut [ManifestObject]) {
+    objects.sort_by_key(|object| {
+        APPLY_ORDER
+            .iter()
+            .position(|kind| *kind == object.kind)
+            .unwrap_or(APPLY_ORDER.len())
+    });
+}
+
+/// Resolves kinds through API discovery, remembering what it has already looked up.
+/// Lookups happen lazily so CRDs applied earlier in the same manifest can be found.
+pub struct KindResolver {
+    client: Client,
+    resolved: HashMap<GroupVersionKind, (ApiResource, Scope)>,
+}
+
+impl KindResolver {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            resolved: HashMap::new(),
+        }
+    }
+
+    async fn resolve(&mut self, gvk: &GroupVersionKind) -> Result<(ApiResource, Scope), kube::Error> {
+        if let Some(found) = self.resolved.get(gvk) {
+            return Ok(found.clone());
+        }
+        let (resource, capabilities) = pinned_kind(&self.client, gvk).await?;
+        let found = (resource, capabilities.scope);
+        self.resolved.insert(gvk.clone(), found.clone());
+        Ok(found)
+    }
+}
+
+/// Server-side apply one manifest object under the `kontour` field manager.
+/// Namespaced objects without a namespace go to `default_namespace`; the outcome
+/// compares the live object before and after to tell created, configured and unchanged apart.
+/// With `dry_run` nothing is persisted and the result carries a preview instead.
+pub async fn apply_manifest_object(
+    resolver: &mut KindResolver,
+    object: &ManifestObject,
+    default_namespace: &str,
+    force: bool,
+    dry_run: bool,
+) -> ManifestResult {
+    let mut result = ManifestResult {
+        document: object.document,
+        kind: object.kind.clone(),
+        name: object.name.clone(),
+        namespace: object.namespace.clone(),
+        outcome: ApplyOutcome::Unchanged,
+        preview: None,
+        diff: None,
+    };
+    if let Err(message) = apply_object(resolver, object, default_namespace, force, dry_run, &mut result).await {
+        result.outcome = ApplyOutcome::Failed(message);
+    }
+    result
+}
+
+async fn apply_object(
+    resolver: &mut KindResolver,
+    object: &ManifestObject,
+    default_namespace: &str,
+    force: bool,
+    dry_run: bool,
+    result: &mut ManifestResult,
+) -> Result<(), String> {
+    let gvk = GroupVersion::from_str(&object.api_version)
+        .map_err(|e| e.to_string())?
+        .with_kind(&object.kind);
+    let (resource, scope) = resolver.resolve(&gvk).await.map_err(|e| {
+        format!(
+            "{} {} is not served by this cluster ({}). If its CRD is in this manifest, apply again once it is established.",
+            object.api_version, object.kind, e
+        )
+    })?;
+
+    let mut value = object.value.clone();
+    match scope {
+        Scope::Namespaced => {
+            let ns = result.namespace.get_or_insert_with(|| default_namespace.to_string()).clone();
+            if let Some(metadata) = value.get_mut("metadata").and_then(Value::as_object_mut) {
+                metadata.insert("namespace".to_string(), Value::String(ns));
+            }
+        }
+        Scope::Cluster => {
+            result.namespace = None;
+            if let Some(metadata) = value.get_mut("metadata").and_then(Value::as_object_mut) {
+                metadata.remove("namespace");
+            }
+        }
+    }
+
+    let api = dynamic_api(resolver.client.clone(), &resource, result.namespace.as_deref());
+    let existing: Option<DynamicObject> = api.get_opt(&object.name).await.map_err(|e| e.to_string())?;
+
+    let mut params = PatchParams::apply(FIELD_MANAGER);
+    if force {
+        params = params.force();
+    }
+    if dry_run {
+        params = params.dry_run();
+    }
+    let applied = api
+        .patch(&object.name, &params, &Patch::Apply(&value))
+        .await
+        .map_err(|e| match e {
+            kube::Error::Api(response) => response.message,
+            e => e.to_string(),
+        })?;
+
+    result.outcome = match existing {
+        None => {
+            if dry_run {
+                result.preview = Some(object_to_yaml(&applied)?);
+            }
+            ApplyOutcome::Created
+        }
+        // A dry run doesn't bump resourceVersion, so compare the content instead
+        Some(before) if dry_run => {
+            let diff = preview_diff(&before, &applied)?;
+            let changed = diff.iter().any(DiffLine::is_change);
+            result.diff = Some(diff);
+            if changed { ApplyOutcome::Configured } else { ApplyOutcome::Unchanged }
+        }
+        Some(before) if before.metadata.resource_version == applied.metadata.resource_version => {
+            ApplyOutcome::Unchanged
+        }
+        Some(_) => ApplyOutcome::Configured,
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(objects: &[ManifestObject]) -> Vec<(&str, &str)> {
+        objects.iter().map(|o| (o.kind.as_str(), o.name.as_str())).collect()
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let text = r#"
+# leading comment
+---
+apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: settings
+  namespace: web
+---
+# only a comment
+---
+---
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web
+"#;
+        let objects = parse_manifest(text).unwrap();
+        assert_eq!(kinds(&objects), vec![("ConfigMap", "settings"), ("Deployment", "web")]);
+        assert_eq!(objects[0].namespace.as_deref(), Some("web"));
+        assert_eq!(objects[1].namespace, None);
+        assert!(objects[0].document < objects[1].document);
+
+        assert!(parse_manifest("").unwrap().is_empty());
+        assert!(parse_manifest("# nothing here\n---\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_manifest_errors() {
+        let text = "apiVersion: v1\nkind: Secret\nmetadata:\n  name: a\n---\nkind: Secret\nmetadata:\n  name: b\n";
+        assert_eq!(parse_manifest(text).unwrap_err(), "document 2: missing apiVersion");
+
+        let text = "apiVersion: v1\nkind: Secret\nmetadata:\n  name: a\n---\napiVersion: v1\nmetadata:\n  name: b\n";
+        assert_eq!(parse_manifest(text).unwrap_err(), "document 2: missing kind");
+
+        let text = "apiVersion: v1\nkind: Secret\n";
+        assert_eq!(parse_manifest(text).unwrap_err(), "document 1: Secret is missing metadata.name");
+    }
+
+    #[test]
+    fn test_parse_manifest_list() {
+        let text = r#"
+apiVersion: v1
+kind: List
+items:
+  - apiVersion: v1
+    kind: Service
+    metadata: { name: web }
+  - apiVersion: v1
+    kind: ServiceAccount
+    metadata: { name: web }
+"#;
+        let objects = parse_manifest(text).unwrap();
+        assert_eq!(kinds(&objects), vec![("Service", "web"), ("ServiceAccount", "web")]);
+        assert!(objects.iter().all(|o| o.document == 1));
+    }
+
+    #[test]
+    fn test_sort_for_apply() {
+        let object = |kind: &str, name: &str| format!("apiVersion: v1\nkind: {}\nmetadata:\n  name: {}\n", kind, name);
+        let text = [
+            object("Deployment", "api"),
+            object("Certificate", "tls"),
+            object("RoleBinding", "read"),
+            object("Deployment", "web"),
+            object("ServiceAccount", "runner"),
+            object("CustomResourceDefinition", "certificates.cert-manager.io"),
+            object("Role", "read"),
+            object("Namespace", "apps"),
+            object("ClusterRole", "admin"),
+        ]
+        .join("---\n");
+        let mut objects = parse_manifest(&text).unwrap();
+        sort_for_apply(&mut objects);
+        assert_eq!(
+            kinds(&objects),
+            vec![
+                ("Namespace", "apps"),
+                ("CustomResourceDefinition", "certificates.cert-manager.io"),
+                ("ServiceAccount", "runner"),
+                ("ClusterRole", "admin"),
+                ("Role", "read"),
+                ("RoleBinding", "read"),
+                ("Deployment", "api"),
+                ("Deployment", "web"),
+                ("Certificate", "tls"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use k8s_openapi::{
+    apimachinery::pkg::api::resource::Quantity,
+    chrono::{DateTime, Utc},
+};
+use kube::Client;
+
+use dioxus::logger::tracing;
+
+use super::kubelet_summary::{fetch_summaries, node_filesystems, Summary};
+use super::node_metrics::fetch_node_metrics;
+use super::quantity::ParsedQuantity;
+use super::resource_metrics::fetch_pod_metrics;
+use crate::contexts::error::{AppError, AppResult};
+
+/// How often the sampler polls for usage. metrics-server and the kubelet only refresh
+/// every 15s or so themselves.
+pub const METRICS_SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+/// How often filesystem usage is read from the kubelets while metrics-server supplies the rest.
+/// It takes a request per node through the API server and changes slowly.
+pub const FILESYSTEM_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+/// Samples kept per series: an hour at the sample interval
+const MAX_SAMPLES: usize = 240;
+
+/// Something whose usage is tracked over time
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MetricsSeries {
+    /// Every node added together
+    Cluster,
+    Node(String),
+    Pod { namespace: String, name: String },
+    Container { namespace: String, pod: String, container: String },
+}
+
+/// Usage at one point in time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MetricSample {
+    pub at: DateTime<Utc>,
+    /// Cores
+    pub cpu: f64,
+    /// Bytes
+    pub memory: f64,
+    /// Ephemeral storage in bytes, which only kubelet summaries report
+    pub storage: Option<f64>,
+}
+
+/// Where usage figures come from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricsSource {
+    MetricsServer,
+    /// Each node's kubelet, read through the API server when metrics-server isn't installed
+    KubeletSummary,
+}
+
+impl MetricsSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MetricsSource::MetricsServer => "metrics-server",
+            MetricsSource::KubeletSummary => "kubelet summary API",
+        }
+    }
+}
+
+/// Used and total bytes of a filesystem or volume
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FsUsage {
+    pub used: f64,
+    pub capacity: f64,
+}
+
+/// Filesystem usage, which only kubelet summaries report
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FilesystemUsage {
+    /// Ephemeral storage in bytes of each node, pod and container, and of the cluster
+    pub storage: HashMap<MetricsSeries, f64>,
+    /// Root filesystem of each node that answered, by node name
+    pub nodes: HashMap<String, FsUsage>,
+    /// Mounted PVCs by namespace and claim name
+    pub volumes: HashMap<(String, String), FsUsage>,
+}
+
+/// One round of usage across the cluster
+#[derive(Clone, Debug, PartialEq)]
+pub struct UsageSnapshot {
+    pub source: MetricsSource,
+    pub samples: HashMap<MetricsSeries, MetricSample>,
+    /// Read along with the samples when they come from the kubelets. metrics-server has no
+    /// filesystem figures, so they are read separately every `FILESYSTEM_SAMPLE_INTERVAL`.
+    pub filesystems: Option<FilesystemUsage>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MetricSummary {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+}
+
+impl MetricSummary {
+    /// Min, average and max of `values`, or `None` if there are none
+    pub fn of(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut count = 0;
+        let mut summary = Self { min: f64::INFINITY, avg: 0.0, max: f64::NEG_INFINITY };
+        for value in values {
+            count += 1;
+            summary.min = summary.min.min(value);
+            summary.max = summary.max.max(value);
+            summary.avg += value;
+        }
+        if count == 0 {
+            return None;
+        }
+        summary.avg /= count as f64;
+        Some(summary)
+    }
+}
+
+/// Recent usage samples per node, pod and container, since metrics-server only knows the
+/// current value. Each series keeps at most `MAX_SAMPLES`, dropping the oldest.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetricsHistory {
+    series: HashMap<MetricsSeries, VecDeque<MetricSample>>,
+    /// The newest round, without filesystems since those are kept apart
+    current: Option<UsageSnapshot>,
+    filesystems: Option<FilesystemUsage>,
+    /// Why the last round failed, cleared by the next one that succeeds
+    error: Option<AppError>,
+}
+
+impl MetricsHistory {
+    /// Add a snapshot from `fetch_usage`. Samples without storage take it from the newest
+    /// filesystem usage.
+    pub fn record_usage(&mut self, mut usage: UsageSnapshot) {
+        if let Some(filesystems) = usage.filesystems.take() {
+            self.filesystems = Some(filesystems);
+        }
+        if let Some(filesystems) = &self.filesystems {
+            for (series, sample) in usage.samples.iter_mut() {
+                sample.storage = sample.storage.or_else(|| filesystems.storage.get(series).copied());
+            }
+        }
+        self.record(usage.samples.iter().map(|(series, sample)| (series.clone(), *sample)).collect());
+        self.current = Some(usage);
+        self.error = None;
+    }
+
+    /// Replace the filesystem usage with a newer reading from `fetch_filesystems`
+    pub fn record_filesystems(&mut self, filesystems: FilesystemUsage) {
+        self.filesystems = Some(filesystems);
+    }
+
+    /// Note that a round failed; the samples so far are kept
+    pub fn record_error(&mut self, error: AppError) {
+        self.error = Some(error);
+    }
+
+    /// Add one round of samples, forgetting series that have stopped reporting, like
+    /// deleted pods and removed nodes, once their newest sample has aged out
+    fn record(&mut self, samples: Vec<(MetricsSeries, MetricSample)>) {
+        let Some(newest) = samples.iter().map(|(_, sample)| sample.at).max() else {
+            return;
+        };
+        for (series, sample) in samples {
+            let samples = self.series.entry(series).or_default();
+            if samples.len() == MAX_SAMPLES {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+        }
+        let retention = METRICS_SAMPLE_INTERVAL * MAX_SAMPLES as u32;
+        let cutoff = newest - chrono_duration(retention);
+        self.series
+            .retain(|_, samples| samples.back().is_some_and(|sample| sample.at > cutoff));
+    }
+
+    /// Where the newest samples came from, or `None` before the first
+    pub fn source(&self) -> Option<MetricsSource> {
+        self.current.as_ref().map(|usage| usage.source)
+    }
+
+    /// The newest round of usage with the newest filesystem usage, or `None` before the first
+    pub fn current(&self) -> Option<UsageSnapshot> {
+        let mut usage = self.current.clone()?;
+        usage.filesystems = self.filesystems.clone();
+        Some(usage)
+    }
+
+    /// Why sampling failed, if the last attempt did
+    pub fn error(&self) -> Option<&AppError> {
+        self.error.as_ref()
+    }
+
+    /// Latest usage of a PVC, if a running pod mounts it
+    pub fn volume(&self, namespace: &str, claim: &str) -> Option<FsUsage> {
+        let filesystems = self.filesystems.as_ref()?;
+        filesystems.volumes.get(&(namespace.to_string(), claim.to_string())).copied()
+    }
+
+    /// The sample of `series` from the newest round, or `None` if it wasn't reported then,
+    /// like a container that has restarted or gone away since
+    pub fn current_sample(&self, series: &MetricsSeries) -> Option<MetricSample> {
+        self.current.as_ref()?.samples.get(series).copied()
+    }
+
+    /// Samples of `series` taken within `window` of its newest one, oldest first
+    pub fn recent(&self, series: &MetricsSeries, window: Duration) -> Vec<MetricSample> {
+        let Some(samples) = self.series.get(series) else {
+            return Vec::new();
+        };
+        let Some(newest) = samples.back() else {
+            return Vec::new();
+        };
+        let cutoff = newest.at - chrono_duration(window);
+        samples.iter().filter(|sample| sample.at >= cutoff).copied().collect()
+    }
+}
+
+const MIB: f64 = 1024.0 * 1024.0;
+const GIB: f64 = 1024.0 * MIB;
+
+/// Cores as millicores below one core, like `250m`, otherwise as `1.50`
+pub fn format_cpu(cores: f64) -> String {
+    if cores >= 1.0 {
+        format!("{:.2}", cores)
+    } else {
+        format!("{:.0}m", cores * 1000.0)
+    }
+}
+
+/// Bytes as `Mi`, or as `Gi` from one gibibyte up
+pub fn format_memory(bytes: f64) -> String {
+    if bytes >= GIB {
+        format!("{:.1}Gi", bytes / GIB)
+    } else {
+        format!("{:.0}Mi", bytes / MIB)
+    }
+}
+
+fn chrono_duration(duration: Duration) -> k8s_openapi::chrono::Duration {
+    k8s_openapi::chrono::Duration::from_std(duration).unwrap_or_default()
+}
+
+fn usage_sample(at: DateTime<Utc>, cpu: Option<&Quantity>, memory: Option<&Quantity>) -> MetricSample {
+    let value = |quantity: Option<&Quantity>| quantity.map(ParsedQuantity::from_quantity).unwrap_or_default().as_f64();
+    MetricSample { at, cpu: value(cpu), memory: value(memory), storage: None }
+}
+
+/// Node, pod and container usage from metrics-server
+async fn metrics_server_samples(client: &Client, at: DateTime<Utc>) -> AppResult<Vec<(MetricsSeries, MetricSample)>> {
+    let (nodes, pods) = futures::try_join!(fetch_node_metrics(client), fetch_pod_metrics(client))?;
+    let mut samples = Vec::new();
+    for (name, metrics) in nodes {
+        let sample = usage_sample(at, metrics.usage.get("cpu"), metrics.usage.get("memory"));
+        samples.push((MetricsSeries::Node(name), sample));
+    }
+    for pod in pods {
+        let namespace = pod.metadata.namespace.unwrap_or_default();
+        let name = pod.metadata.name.unwrap_or_default();
+        let mut total = MetricSample { at, cpu: 0.0, memory: 0.0, storage: None };
+        for container in pod.containers {
+            let sample = usage_sample(at, Some(&container.usage.cpu), Some(&container.usage.memory));
+            total.cpu += sample.cpu;
+            total.memory += sample.memory;
+            let series = MetricsSeries::Container {
+                namespace: namespace.clone(),
+                pod: name.clone(),
+                container: container.name,
+            };
+            samples.push((series, sample));
+        }
+        samples.push((MetricsSeries::Pod { namespace, name }, total));
+    }
+    Ok(samples)
+}
+
+/// Add the cluster total, the sum of every node
+fn with_cluster_total(
+    at: DateTime<Utc>,
+    samples: impl IntoIterator<Item = (MetricsSeries, MetricSample)>,
+) -> HashMap<MetricsSeries, MetricSample> {
+    let mut samples: HashMap<_, _> = samples.into_iter().collect();
+    let mut cluster = MetricSample { at, cpu: 0.0, memory: 0.0, storage: None };
+    for (series, sample) in &samples {
+        if let MetricsSeries::Node(_) = series {
+            cluster.cpu += sample.cpu;
+            cluster.memory += sample.memory;
+            if let Some(storage) = sample.storage {
+                cluster.storage = Some(cluster.storage.unwrap_or_default() + storage);
+            }
+        }
+    }
+    samples.insert(MetricsSeries::Cluster, cluster);
+    samples
+}
+
+/// Usage read from kubelet summaries, filesystems included
+fn summary_usage(at: DateTime<Utc>, summaries: &[Summary]) -> UsageSnapshot {
+    let samples = with_cluster_total(at, summaries.iter().flat_map(|summary| summary.samples(at)));
+    let filesystems = FilesystemUsage {
+        storage: samples
+            .iter()
+            .filter_map(|(series, sample)| Some((series.clone(), sample.storage?)))
+            .collect(),
+        nodes: node_filesystems(summaries),
+        volumes: summaries.iter().flat_map(Summary::volumes).collect(),
+    };
+    UsageSnapshot {
+        source: MetricsSource::KubeletSummary,
+        samples,
+        filesystems: Some(filesystems),
+    }
+}
+
+/// Usage of every node, pod and container plus the cluster total. Only when metrics-server
+/// can't be read is every kubelet's summary API read instead.
+pub async fn fetch_usage(client: &Client) -> AppResult<UsageSnapshot> {
+    let at = Utc::now();
+    let error = match metrics_server_samples(client, at).await {
+        Ok(samples) => {
+            return Ok(UsageSnapshot {
+                source: MetricsSource::MetricsServer,
+                samples: with_cluster_total(at, samples),
+                filesystems: None,
+            });
+        }
+        Err(e) => e,
+    };
+    tracing::debug!("metrics-server unavailable, reading kubelet summaries: {}", error);
+    match fetch_summaries(client).await {
+        Ok(summaries) if !summaries.is_empty() => Ok(summary_usage(at, &summaries)),
+        _ => Err(error),
+    }
+}
+
+/// Node, pod, container and PVC filesystem usage from the kubelets
+pub async fn fetch_filesystems(client: &Client) -> AppResult<FilesystemUsage> {
+    let summaries = fetch_summaries(client).await?;
+    let usage = summary_usage(Utc::now(), &summaries);
+    Ok(usage.filesystems.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(seconds: i64, cpu: f64) -> MetricSample {
+        let at = DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap();
+        MetricSample { at, cpu, memory: cpu * 1024.0, storage: None }
+    }
+
+    #[test]
+    fn test_metrics_history() {
+        let node = MetricsSeries::Node("node-a".to_string());
+        let pod = MetricsSeries::Pod { namespace: "default".to_string(), name: "web".to_string() };
+        let mut history = MetricsHistory::default();
+        for i in 0..(MAX_SAMPLES as i64 + 10) {
+            let mut samples = vec![(node.clone(), sample(i * 15, i as f64))];
+            // The pod goes away after the first few samples
+            if i < 3 {
+                samples.push((pod.clone(), sample(i * 15, 1.0)));
+            }
+            history.record(samples);
+        }
+
+        let all = history.recent(&node, Duration::from_secs(24 * 60 * 60));
+        assert_eq!(all.len(), MAX_SAMPLES);
+        assert_eq!(all.first().unwrap().cpu, 10.0);
+
+        let last_minute = history.recent(&node, Duration::from_secs(60));
+        assert_eq!(last_minute.len(), 5);
+        let summary = MetricSummary::of(last_minute.iter().map(|s| s.cpu)).unwrap();
+        assert_eq!(summary.min, MAX_SAMPLES as f64 + 5.0);
+        assert_eq!(summary.max, MAX_SAMPLES as f64 + 9.0);
+        assert_eq!(summary.avg, MAX_SAMPLES as f64 + 7.0);
+
+        assert!(history.recent(&pod, Duration::from_secs(60)).is_empty());
+        assert_eq!(MetricSummary::of(Vec::new()), None);
+
+        // A series missing from the newest round keeps its history, but has no current sample
+        let round = |seconds, series: Vec<&MetricsSeries>| UsageSnapshot {
+            source: MetricsSource::MetricsServer,
+            samples: series.into_iter().map(|s| (s.clone(), sample(seconds, 1.0))).collect(),
+            filesystems: None,
+        };
+        let mut history = MetricsHistory::default();
+        history.record_usage(round(0, vec![&node, &pod]));
+        assert_eq!(history.current_sample(&pod), Some(sample(0, 1.0)));
+        history.record_usage(round(15, vec![&node]));
+        assert_eq!(history.recent(&pod, Duration::from_secs(60)), vec![sample(0, 1.0)]);
+        assert_eq!(history.current_sample(&pod), None);
+        assert_eq!(history.current_sample(&node), Some(sample(15, 1.0)));
+    }
+
+    #[test]
+    fn test_usage_with_filesystems() {
+        use super::super::kubelet_summary::{CpuStats, FsStats, NodeStats};
+
+        let summary = Summary {
+            node: NodeStats {
+                node_name: "node-a".to_string(),
+                cpu: Some(CpuStats { usage_nano_cores: Some(2_000_000_000) }),
+                memory: None,
+                fs: Some(FsStats { capacity_bytes: Some(100), used_bytes: Some(40) }),
+            },
+            pods: Vec::new(),
+        };
+        let node = MetricsSeries::Node("node-a".to_string());
+        let at = sample(0, 0.0).at;
+
+        let usage = summary_usage(at, std::slice::from_ref(&summary));
+        assert_eq!(usage.source, MetricsSource::KubeletSummary);
+        assert_eq!(usage.samples[&MetricsSeries::Cluster].cpu, 2.0);
+        let filesystems = usage.filesystems.clone().unwrap();
+        assert_eq!(filesystems.nodes["node-a"], FsUsage { used: 40.0, capacity: 100.0 });
+        assert_eq!(filesystems.storage[&MetricsSeries::Cluster], 40.0);
+
+        // metrics-server rounds have no storage, so it's taken from the last filesystem reading
+        let mut history = MetricsHistory::default();
+        history.record_filesystems(filesystems);
+        history.record_usage(UsageSnapshot {
+            source: MetricsSource::MetricsServer,
+            samples: with_cluster_total(at, [(node.clone(), sample(0, 0.5))]),
+            filesystems: None,
+        });
+        let current = history.current().unwrap();
+        assert_eq!(current.source, MetricsSource::MetricsServer);
+        assert_eq!(current.samples[&node].cpu, 0.5);
+        assert_eq!(history.current_sample(&node).unwrap().storage, Some(40.0));
+        assert_eq!(history.current_sample(&MetricsSeries::Cluster).unwrap().storage, Some(40.0));
+        assert_eq!(current.filesystems.unwrap().nodes["node-a"].used, 40.0);
+
+        let error = AppError::MetricsUnavailable("the server could not find the requested resource".to_string());
+        history.record_error(error.clone());
+        assert_eq!(history.error(), Some(&error));
+        assert!(history.current().is_some());
+    }
+}
@@ -1,16 +1,48 @@
+pub mod access;
+pub mod api_resources;
+pub mod apply;
+pub mod cache;
 pub mod cluster_stats;
 pub mod cluster_resources;
+pub mod delete;
 pub mod events;
+pub mod health;
+pub mod kubelet_summary;
+pub mod manifest;
+pub mod metrics_history;
 pub mod node_metrics;
+pub mod pod_exec;
+pub mod pod_logs;
+pub mod port_forward;
 pub mod problem_pod;
+pub mod prometheus;
+pub mod quantity;
 pub mod resource_limits;
 pub mod resource_metrics;
+pub mod rollout;
+pub mod template_diff;
 pub mod unused_resources;
 
+pub use access::*;
+pub use api_resources::*;
+pub use apply::*;
+pub use cache::*;
 pub use cluster_stats::*;
 pub use cluster_resources::*;
+pub use delete::*;
 pub use events::*;
+pub use health::*;
+pub use kubelet_summary::*;
+pub use manifest::*;
+pub use metrics_history::*;
 pub use node_metrics::*;
+pub use pod_exec::*;
+pub use pod_logs::*;
+pub use port_forward::*;
+pub use prometheus::*;
+pub use quantity::*;
 pub use resource_limits::*;
 pub use resource_metrics::*;
+pub use rollout::*;
+pub use template_diff::*;
 pub use unused_resources::*;
\ No newline at end of file
@@ -1,4 +1,3 @@
-use dioxus::logger::tracing;
 use k8s_openapi::{
     apimachinery::pkg::{
         api::resource::Quantity,
@@ -13,6 +12,8 @@ use kube::{
 use serde::Deserialize;
 use std::collections::{BTreeMap, HashMap};
 
+use crate::contexts::error::{AppError, AppResult};
+
 #[derive(Deserialize, Clone, Debug, Default)]
 pub struct NodeMetrics {
     pub metadata: ObjectMeta,
@@ -52,46 +53,15 @@ impl Resource for NodeMetrics {
     }
 }
 
-pub fn parse_resource_quantity(quantity: &str) -> f32 {
-    if quantity.is_empty() || quantity == "0" {
-        return 0.0;
-    }
-
-    // Parse CPU values
-    if quantity.ends_with('m') {
-        return quantity.trim_end_matches('m')
-            .parse::<f32>()
-            .map(|v| v / 1000.0)
-            .unwrap_or(0.0);
-    }
-
-    // Parse memory/storage values
-    if let Some(value) = quantity.strip_suffix("Ki") {
-        return value.parse::<f32>().map(|v| v / (1024.0 * 1024.0)).unwrap_or(0.0);
-    }
-    if let Some(value) = quantity.strip_suffix("Mi") {
-        return value.parse::<f32>().map(|v| v / 1024.0).unwrap_or(0.0);
-    }
-    if let Some(value) = quantity.strip_suffix("Gi") {
-        return value.parse::<f32>().ok().unwrap_or(0.0);
-    }
-
-    quantity.parse::<f32>().unwrap_or(0.0)
-}
-
-pub async fn fetch_node_metrics(client: &Client) -> HashMap<String, NodeMetrics> {
+pub async fn fetch_node_metrics(client: &Client) -> AppResult<HashMap<String, NodeMetrics>> {
     let metrics_api: Api<NodeMetrics> = Api::all(client.clone());
-    
-    match metrics_api.list(&ListParams::default()).await {
-        Ok(metrics_list) => {
-            metrics_list.items
-                .into_iter()
-                .filter_map(|m| m.metadata.name.clone().map(|name| (name, m)))
-                .collect()
-        }
-        Err(e) => {
-            tracing::error!("Failed to fetch metrics: {:?}", e);
-            HashMap::new()
-        }
-    }
+
+    let metrics_list = metrics_api
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| AppError::from_metrics(e, "nodes.metrics.k8s.io"))?;
+    Ok(metrics_list.items
+        .into_iter()
+        .filter_map(|m| m.metadata.name.clone().map(|name| (name, m)))
+        .collect())
 }
\ No newline at end of file
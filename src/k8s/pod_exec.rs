@@ -0,0 +1,166 @@
+use futures::{
+    channel::mpsc::{self, UnboundedSender},
+    stream::{self, BoxStream},
+    SinkExt, StreamExt,
+};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    api::{Api, AttachParams, AttachedProcess, TerminalSize},
+    Client,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+/// Shell command used when no explicit command is given; falls back to `sh`
+/// for minimal images that ship without bash
+pub const DEFAULT_SHELL: &[&str] = &["/bin/sh", "-c", "command -v bash >/dev/null && exec bash || exec sh"];
+
+/// Options for an exec session, mirroring `kubectl exec -it`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecOptions {
+    pub container: Option<String>,
+    pub command: Vec<String>,
+    /// Allocate a TTY. Required for resize support; the kubelet merges stderr into stdout.
+    pub tty: bool,
+}
+
+impl Default for ExecOptions {
+    fn default() -> Self {
+        Self {
+            container: None,
+            command: DEFAULT_SHELL.iter().map(|s| s.to_string()).collect(),
+            tty: true,
+        }
+    }
+}
+
+impl ExecOptions {
+    fn to_attach_params(&self) -> AttachParams {
+        let params = AttachParams::default()
+            .stdin(true)
+            .stdout(true)
+            .stderr(!self.tty)
+            .tty(self.tty);
+        match &self.container {
+            Some(container) => params.container(container.clone()),
+            None => params,
+        }
+    }
+}
+
+/// Input sent from the terminal view to the remote process
+#[derive(Clone, Debug, PartialEq)]
+pub enum TerminalInput {
+    Data(Vec<u8>),
+    Resize { cols: u16, rows: u16 },
+}
+
+/// Output received from the remote process
+#[derive(Clone, Debug, PartialEq)]
+pub enum TerminalOutput {
+    Stdout(Vec<u8>),
+    Stderr(Vec<u8>),
+    Exited(String),
+}
+
+/// Handle to a running exec session. Dropping it terminates the remote process.
+pub struct ExecSession {
+    input: UnboundedSender<TerminalInput>,
+    process: AttachedProcess,
+}
+
+impl ExecSession {
+    /// Queue input for the remote process, returning false once the session has ended
+    pub fn send(&self, input: TerminalInput) -> bool {
+        self.input.unbounded_send(input).is_ok()
+    }
+}
+
+impl Drop for ExecSession {
+    fn drop(&mut self) {
+        self.input.close_channel();
+        self.process.abort();
+    }
+}
+
+/// Whether the container is currently running and can accept an exec session
+pub fn is_container_running(pod: &Pod, container: &str) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|status| status.container_statuses.as_ref())
+        .and_then(|statuses| statuses.iter().find(|s| s.name == container))
+        .and_then(|status| status.state.as_ref())
+        .map(|state| state.running.is_some())
+        .unwrap_or(false)
+}
+
+/// Start an exec session in a pod container and return the session handle
+/// together with a stream of everything the process writes
+pub async fn start_exec_session(
+    client: Client,
+    namespace: &str,
+    pod_name: &str,
+    options: &ExecOptions,
+) -> Result<(ExecSession, BoxStream<'static, TerminalOutput>), kube::Error> {
+    let pods: Api<Pod> = Api::namespaced(client, namespace);
+    let mut process = pods
+        .exec(pod_name, options.command.clone(), &options.to_attach_params())
+        .await?;
+
+    let mut outputs: Vec<BoxStream<'static, TerminalOutput>> = Vec::new();
+    if let Some(stdout) = process.stdout() {
+        outputs.push(read_chunks(stdout).map(TerminalOutput::Stdout).boxed());
+    }
+    if let Some(stderr) = process.stderr() {
+        outputs.push(read_chunks(stderr).map(TerminalOutput::Stderr).boxed());
+    }
+
+    // The status only resolves once the process has exited, so it always comes last
+    let status = process.take_status();
+    let exited = stream::once(async move {
+        let status = match status {
+            Some(status) => status.await,
+            None => None,
+        };
+        let message = status
+            .and_then(|s| s.message.or(s.reason))
+            .unwrap_or_else(|| "Session closed".to_string());
+        TerminalOutput::Exited(message)
+    });
+    let output = stream::select_all(outputs).chain(exited).boxed();
+
+    let (input, mut input_rx) = mpsc::unbounded::<TerminalInput>();
+    let mut stdin = process.stdin();
+    let mut resize = process.terminal_size();
+    tokio::spawn(async move {
+        while let Some(message) = input_rx.next().await {
+            match message {
+                TerminalInput::Data(bytes) => {
+                    let Some(writer) = stdin.as_mut() else { continue };
+                    if writer.write_all(&bytes).await.is_err() {
+                        break;
+                    }
+                }
+                TerminalInput::Resize { cols, rows } => {
+                    if let Some(sender) = resize.as_mut() {
+                        let _ = sender.send(TerminalSize { width: cols, height: rows }).await;
+                    }
+                }
+            }
+        }
+    });
+
+    Ok((ExecSession { input, process }, output))
+}
+
+fn read_chunks<R: AsyncRead + Unpin + Send + 'static>(reader: R) -> impl futures::Stream<Item = Vec<u8>> {
+    stream::unfold(reader, |mut reader| async move {
+        let mut buf = vec![0u8; 4096];
+        match reader.read(&mut buf).await {
+            Ok(0) | Err(_) => None,
+            Ok(n) => {
+                buf.truncate(n);
+                Some((buf, reader))
+            }
+        }
+    })
+}
@@ -0,0 +1,91 @@
+use futures::{AsyncBufReadExt, Stream};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    api::{Api, LogParams},
+    Client,
+};
+
+/// Maximum number of log lines kept in memory by the log viewer
+pub const MAX_LOG_LINES: usize = 5000;
+
+/// Options for a pod log request, mirroring the `kubectl logs` flags
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LogOptions {
+    pub container: Option<String>,
+    pub follow: bool,
+    pub previous: bool,
+    pub timestamps: bool,
+    pub tail_lines: Option<i64>,
+    pub since_seconds: Option<i64>,
+}
+
+impl LogOptions {
+    pub fn to_log_params(&self) -> LogParams {
+        LogParams {
+            container: self.container.clone(),
+            // Following a terminated container's previous logs never yields new lines
+            follow: self.follow && !self.previous,
+            previous: self.previous,
+            timestamps: self.timestamps,
+            tail_lines: self.tail_lines.filter(|lines| *lines > 0),
+            since_seconds: self.since_seconds.filter(|secs| *secs > 0),
+            ..LogParams::default()
+        }
+    }
+}
+
+/// Open a log stream for a pod and return it line by line
+pub async fn stream_pod_logs(
+    client: Client,
+    namespace: &str,
+    pod_name: &str,
+    options: &LogOptions,
+) -> Result<impl Stream<Item = std::io::Result<String>> + Unpin, kube::Error> {
+    let pods: Api<Pod> = Api::namespaced(client, namespace);
+    let reader = pods.log_stream(pod_name, &options.to_log_params()).await?;
+    Ok(Box::pin(reader).lines())
+}
+
+/// Get the names of all containers in a pod, init containers first
+pub fn container_names(pod: &Pod) -> Vec<String> {
+    pod.spec
+        .as_ref()
+        .map(|spec| {
+            spec.init_containers
+                .iter()
+                .flatten()
+                .chain(spec.containers.iter())
+                .map(|c| c.name.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether the previous instance's logs are the interesting ones for a container,
+/// i.e. it has restarted and is currently waiting (CrashLoopBackOff) or terminated
+pub fn prefers_previous_logs(pod: &Pod, container: &str) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|status| {
+            status
+                .init_container_statuses
+                .iter()
+                .flatten()
+                .chain(status.container_statuses.iter().flatten())
+                .find(|c| c.name == container)
+        })
+        .map(|status| {
+            let has_previous = status
+                .last_state
+                .as_ref()
+                .and_then(|s| s.terminated.as_ref())
+                .is_some();
+            let is_running = status
+                .state
+                .as_ref()
+                .and_then(|s| s.running.as_ref())
+                .is_some();
+            has_previous && !is_running
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,234 @@
+use std::fmt;
+use std::net::SocketAddr;
+
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use k8s_openapi::api::core::v1::{Pod, Service};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::{
+    api::{Api, ListParams},
+    Client,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::{AbortHandle, JoinSet},
+};
+
+/// Resource a port-forward points at
+#[derive(Clone, Debug, PartialEq)]
+pub enum ForwardTarget {
+    Pod(String),
+    Service(String),
+}
+
+impl fmt::Display for ForwardTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForwardTarget::Pod(name) => write!(f, "pod/{}", name),
+            ForwardTarget::Service(name) => write!(f, "svc/{}", name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortForwardRequest {
+    pub namespace: String,
+    pub target: ForwardTarget,
+    /// Container port for pods, service port for services
+    pub remote_port: u16,
+    /// Local port to bind on 127.0.0.1, 0 to let the OS pick one
+    pub local_port: u16,
+}
+
+/// Connection activity reported by a running port-forward
+#[derive(Clone, Debug, PartialEq)]
+pub enum PortForwardEvent {
+    ConnectionOpened,
+    ConnectionClosed,
+    Error(String),
+}
+
+#[derive(Debug)]
+pub enum PortForwardError {
+    Kube(kube::Error),
+    Bind(std::io::Error),
+    PortNotFound(String),
+    NoReadyPod(String),
+}
+
+impl fmt::Display for PortForwardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortForwardError::Kube(e) => write!(f, "Kubernetes API error: {}", e),
+            PortForwardError::Bind(e) => write!(f, "Failed to bind local port: {}", e),
+            PortForwardError::PortNotFound(msg) => write!(f, "Port not found: {}", msg),
+            PortForwardError::NoReadyPod(service) => write!(f, "No ready pod backs service {}", service),
+        }
+    }
+}
+
+impl std::error::Error for PortForwardError {}
+
+impl From<kube::Error> for PortForwardError {
+    fn from(err: kube::Error) -> Self {
+        PortForwardError::Kube(err)
+    }
+}
+
+/// Handle to a running port-forward. Dropping it closes the listener and all connections.
+pub struct PortForwardHandle {
+    pub pod_name: String,
+    pub pod_port: u16,
+    pub local_addr: SocketAddr,
+    task: AbortHandle,
+}
+
+impl Drop for PortForwardHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Resolve a service port to a ready backing pod and the container port it targets
+pub async fn resolve_service_target(
+    client: Client,
+    namespace: &str,
+    service_name: &str,
+    service_port: u16,
+) -> Result<(String, u16), PortForwardError> {
+    let services: Api<Service> = Api::namespaced(client.clone(), namespace);
+    let service = services.get(service_name).await?;
+    let spec = service.spec.unwrap_or_default();
+
+    let port = spec
+        .ports
+        .unwrap_or_default()
+        .into_iter()
+        .find(|p| p.port == service_port as i32)
+        .ok_or_else(|| PortForwardError::PortNotFound(format!("{} on service {}", service_port, service_name)))?;
+    let selector = spec
+        .selector
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| PortForwardError::NoReadyPod(service_name.to_string()))?;
+
+    let label_selector = selector
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(",");
+    let pods: Api<Pod> = Api::namespaced(client, namespace);
+    let pod = pods
+        .list(&ListParams::default().labels(&label_selector))
+        .await?
+        .items
+        .into_iter()
+        .find(is_pod_ready)
+        .ok_or_else(|| PortForwardError::NoReadyPod(service_name.to_string()))?;
+
+    // targetPort defaults to the service port and may name a container port instead
+    let pod_port = match port.target_port {
+        Some(IntOrString::Int(p)) => p as u16,
+        Some(IntOrString::String(name)) => named_container_port(&pod, &name)
+            .ok_or_else(|| PortForwardError::PortNotFound(format!("{} on pod backing {}", name, service_name)))?,
+        None => service_port,
+    };
+
+    Ok((pod.metadata.name.unwrap_or_default(), pod_port))
+}
+
+fn is_pod_ready(pod: &Pod) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|status| status.conditions.as_ref())
+        .map(|conditions| conditions.iter().any(|c| c.type_ == "Ready" && c.status == "True"))
+        .unwrap_or(false)
+}
+
+fn named_container_port(pod: &Pod, name: &str) -> Option<u16> {
+    pod.spec
+        .as_ref()?
+        .containers
+        .iter()
+        .flat_map(|c| c.ports.iter().flatten())
+        .find(|p| p.name.as_deref() == Some(name))
+        .map(|p| p.container_port as u16)
+}
+
+/// Bind a local listener and forward every accepted connection to the target pod
+pub async fn start_port_forward(
+    client: Client,
+    request: &PortForwardRequest,
+) -> Result<(PortForwardHandle, UnboundedReceiver<PortForwardEvent>), PortForwardError> {
+    let (pod_name, pod_port) = match &request.target {
+        ForwardTarget::Pod(name) => (name.clone(), request.remote_port),
+        ForwardTarget::Service(name) => {
+            resolve_service_target(client.clone(), &request.namespace, name, request.remote_port).await?
+        }
+    };
+
+    let listener = TcpListener::bind(("127.0.0.1", request.local_port))
+        .await
+        .map_err(PortForwardError::Bind)?;
+    let local_addr = listener.local_addr().map_err(PortForwardError::Bind)?;
+
+    let (events, events_rx) = mpsc::unbounded();
+    let pods: Api<Pod> = Api::namespaced(client, &request.namespace);
+    let task = tokio::spawn({
+        let pod_name = pod_name.clone();
+        async move {
+            // Connections live in the set so aborting the listener task closes them too
+            let mut connections = JoinSet::new();
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let pods = pods.clone();
+                        let pod_name = pod_name.clone();
+                        let events = events.clone();
+                        connections.spawn(forward_connection(pods, pod_name, pod_port, stream, events));
+                    }
+                    Err(e) => {
+                        let _ = events.unbounded_send(PortForwardEvent::Error(format!("Accept failed: {}", e)));
+                    }
+                }
+                // Reap finished connections so the set doesn't grow unbounded
+                while connections.try_join_next().is_some() {}
+            }
+        }
+    });
+
+    Ok((
+        PortForwardHandle {
+            pod_name,
+            pod_port,
+            local_addr,
+            task: task.abort_handle(),
+        },
+        events_rx,
+    ))
+}
+
+async fn forward_connection(
+    pods: Api<Pod>,
+    pod_name: String,
+    port: u16,
+    mut stream: TcpStream,
+    events: UnboundedSender<PortForwardEvent>,
+) {
+    let _ = events.unbounded_send(PortForwardEvent::ConnectionOpened);
+    let result = async {
+        let mut forwarder = pods.portforward(&pod_name, &[port]).await.map_err(|e| e.to_string())?;
+        let mut upstream = forwarder
+            .take_stream(port)
+            .ok_or_else(|| format!("Port {} is not available on {}", port, pod_name))?;
+        tokio::io::copy_bidirectional(&mut stream, &mut upstream)
+            .await
+            .map_err(|e| e.to_string())?;
+        drop(upstream);
+        forwarder.join().await.map_err(|e| e.to_string())
+    }
+    .await;
+
+    if let Err(e) = result {
+        let _ = events.unbounded_send(PortForwardEvent::Error(e));
+    }
+    let _ = events.unbounded_send(PortForwardEvent::ConnectionClosed);
+}
@@ -7,6 +7,7 @@ pub struct ProblemPod {
     pub issue_type: String,
     pub details: String,
     pub severity: String,
+    pub container: Option<String>, // Container the issue was found in, if any
 }
 
 pub fn check_pod_status(pod: &Pod) -> Option<ProblemPod> {
@@ -29,6 +30,7 @@ pub fn check_pod_status(pod: &Pod) -> Option<ProblemPod> {
                             container.name, container.restart_count
                         ),
                         severity: "high".to_string(),
+                        container: Some(container.name.clone()),
                     });
                 }
                 
@@ -44,6 +46,7 @@ pub fn check_pod_status(pod: &Pod) -> Option<ProblemPod> {
                             waiting.message.as_deref().unwrap_or("No details available")
                         ),
                         severity: "high".to_string(),
+                        container: Some(container.name.clone()),
                     });
                 }
             }
@@ -62,6 +65,7 @@ pub fn check_pod_status(pod: &Pod) -> Option<ProblemPod> {
                             terminated.message.as_deref().unwrap_or("No details available")
                         ),
                         severity: "high".to_string(),
+                        container: Some(container.name.clone()),
                     });
                 }
             }
@@ -77,6 +81,7 @@ pub fn check_pod_status(pod: &Pod) -> Option<ProblemPod> {
                         container.name
                     ),
                     severity: "medium".to_string(),
+                    container: Some(container.name.clone()),
                 });
             }
 
@@ -91,6 +96,7 @@ pub fn check_pod_status(pod: &Pod) -> Option<ProblemPod> {
                         container.name, container.restart_count
                     ),
                     severity: "medium".to_string(),
+                    container: Some(container.name.clone()),
                 });
             }
         }
@@ -109,6 +115,7 @@ pub fn check_pod_status(pod: &Pod) -> Option<ProblemPod> {
                 issue_type: "Evicted".to_string(),
                 details: message,
                 severity: "high".to_string(),
+                container: None,
             });
         }
     }
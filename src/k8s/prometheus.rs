@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use k8s_openapi::chrono::{DateTime, Utc};
+use kube::{Client, Config};
+use serde::{Deserialize, Serialize};
+
+use crate::contexts::error::{AppError, AppResult};
+
+/// Where a cluster's Prometheus answers queries
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PrometheusEndpoint {
+    /// Reached directly, e.g. through an ingress or a port-forward
+    Url { url: String },
+    /// An in-cluster service, reached through the API server's service proxy
+    Service { namespace: String, name: String, port: String },
+}
+
+impl std::fmt::Display for PrometheusEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrometheusEndpoint::Url { url } => write!(f, "{}", url),
+            PrometheusEndpoint::Service { namespace, name, port } => {
+                write!(f, "service {}/{}:{} (via API server)", namespace, name, port)
+            }
+        }
+    }
+}
+
+/// How far back a range query looks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryWindow {
+    Hour,
+    Day,
+    Week,
+}
+
+impl QueryWindow {
+    pub const ALL: [QueryWindow; 3] = [QueryWindow::Hour, QueryWindow::Day, QueryWindow::Week];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            QueryWindow::Hour => "1h",
+            QueryWindow::Day => "24h",
+            QueryWindow::Week => "7d",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            QueryWindow::Hour => Duration::from_secs(60 * 60),
+            QueryWindow::Day => Duration::from_secs(24 * 60 * 60),
+            QueryWindow::Week => Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+
+    /// Resolution that keeps every window at about 240 points
+    pub fn step(&self) -> Duration {
+        self.duration() / 240
+    }
+}
+
+/// One labelled series from a range query
+#[derive(Clone, Debug, PartialEq)]
+pub struct PromSeries {
+    pub labels: BTreeMap<String, String>,
+    /// Timestamps with their values, oldest first; NaN and infinite points are dropped
+    pub points: Vec<(DateTime<Utc>, f64)>,
+}
+
+impl PromSeries {
+    /// The series in PromQL notation, like `up{job="api"}`
+    pub fn name(&self) -> String {
+        let metric = self.labels.get("__name__").cloned().unwrap_or_default();
+        let labels: Vec<String> = self.labels
+            .iter()
+            .filter(|(key, _)| *key != "__name__")
+            .map(|(key, value)| format!("{}=\"{}\"", key, value))
+            .collect();
+        if labels.is_empty() && !metric.is_empty() {
+            metric
+        } else {
+            format!("{}{{{}}}", metric, labels.join(", "))
+        }
+    }
+}
+
+/// Prometheus' response envelope
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryResponse {
+    status: String,
+    data: Option<QueryData>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryData {
+    result_type: String,
+    #[serde(default)]
+    result: Vec<MatrixSeries>,
+}
+
+#[derive(Deserialize)]
+struct MatrixSeries {
+    #[serde(default)]
+    metric: BTreeMap<String, String>,
+    #[serde(default)]
+    values: Vec<(f64, String)>,
+}
+
+/// A cluster's Prometheus HTTP API. Both kinds of endpoint go through a kube client, which
+/// brings TLS and, for the service proxy, the cluster's credentials.
+#[derive(Clone)]
+pub struct Prometheus {
+    client: Client,
+    /// Path the API lives under, empty for a plain URL
+    prefix: String,
+}
+
+impl Prometheus {
+    pub fn connect(endpoint: &PrometheusEndpoint, cluster: &Client) -> AppResult<Self> {
+        match endpoint {
+            PrometheusEndpoint::Url { url } => Self::from_url(url),
+            PrometheusEndpoint::Service { namespace, name, port } => Ok(Self {
+                client: cluster.clone(),
+                prefix: format!("/api/v1/namespaces/{}/services/{}:{}/proxy", namespace, name, port),
+            }),
+        }
+    }
+
+    pub fn from_url(url: &str) -> AppResult<Self> {
+        let uri = url
+            .trim_end_matches('/')
+            .parse::<http::Uri>()
+            .map_err(|e| AppError::Other(format!("Invalid Prometheus URL {}: {}", url, e)))?;
+        let client = Client::try_from(Config::new(uri)).map_err(|e| AppError::Other(e.to_string()))?;
+        Ok(Self { client, prefix: String::new() })
+    }
+
+    /// Run `query` over the last `window`, ending now
+    pub async fn query_range(&self, query: &str, window: QueryWindow) -> AppResult<Vec<PromSeries>> {
+        let end = Utc::now();
+        let start = end - k8s_openapi::chrono::Duration::from_std(window.duration()).unwrap_or_default();
+        let path = format!(
+            "{}/api/v1/query_range?query={}&start={}&end={}&step={}",
+            self.prefix,
+            encode_query_value(query),
+            start.timestamp(),
+            end.timestamp(),
+            window.step().as_secs(),
+        );
+        let request = http::Request::get(path)
+            .body(Vec::new())
+            .map_err(|e| AppError::Other(e.to_string()))?;
+        let text = self.client.request_text(request).await.map_err(|e| match e {
+            // Prometheus explains bad queries in its own envelope
+            kube::Error::Api(response) => match parse_error(&response.message) {
+                Some(message) => AppError::Other(message),
+                None => AppError::from(kube::Error::Api(response)),
+            },
+            e => AppError::from(e),
+        })?;
+        parse_matrix(&text)
+    }
+}
+
+fn parse_error(message: &str) -> Option<String> {
+    // kube keeps error bodies that aren't Kubernetes statuses as a quoted string
+    let body = k8s_openapi::serde_json::from_str::<String>(message).unwrap_or_else(|_| message.to_string());
+    k8s_openapi::serde_json::from_str::<QueryResponse>(&body).ok()?.error
+}
+
+fn parse_matrix(body: &str) -> AppResult<Vec<PromSeries>> {
+    let response: QueryResponse = k8s_openapi::serde_json::from_str(body)
+        .map_err(|e| AppError::Other(format!("Unexpected response from Prometheus: {}", e)))?;
+    if response.status != "success" {
+        return Err(AppError::Other(response.error.unwrap_or(response.status)));
+    }
+    let data = response.data.ok_or_else(|| AppError::Other("Prometheus returned no data".to_string()))?;
+    if data.result_type != "matrix" {
+        return Err(AppError::Other(format!("Expected a range vector, got a {}", data.result_type)));
+    }
+    Ok(data.result
+        .into_iter()
+        .map(|series| PromSeries {
+            labels: series.metric,
+            points: series.values
+                .into_iter()
+                .filter_map(|(at, value)| {
+                    let value = value.parse::<f64>().ok().filter(|value| value.is_finite())?;
+                    let at = DateTime::from_timestamp_millis((at * 1000.0) as i64)?;
+                    Some((at, value))
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// Percent-encode a query string value
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Quote a label value for a PromQL selector
+fn label_value(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// What a set of charts covers
+#[derive(Clone, Debug, PartialEq)]
+pub enum PromScope {
+    Cluster,
+    Node(String),
+    Pod { namespace: String, name: String },
+}
+
+/// How a chart's values are formatted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PromUnit {
+    Cores,
+    Bytes,
+    BytesPerSecond,
+    PerHour,
+    /// Whatever an ad-hoc query returns
+    Plain,
+}
+
+/// A chart of one or more queries
+#[derive(Clone, Debug, PartialEq)]
+pub struct PromPanel {
+    pub title: &'static str,
+    pub unit: PromUnit,
+    /// Legend label and query of each line
+    pub queries: Vec<(&'static str, String)>,
+}
+
+/// CPU, memory, restart and network charts for `scope`, from the cAdvisor and
+/// kube-state-metrics series a standard Prometheus install scrapes
+pub fn prom_panels(scope: &PromScope) -> Vec<PromPanel> {
+    let (selector, restarts) = match scope {
+        PromScope::Cluster => (
+            String::new(),
+            "sum(increase(kube_pod_container_status_restarts_total[1h]))".to_string(),
+        ),
+        PromScope::Node(node) => (
+            format!(", node={}", label_value(node)),
+            format!(
+                "sum(increase(kube_pod_container_status_restarts_total[1h]) \
+                 * on(namespace, pod) group_left(node) kube_pod_info{{node={}}})",
+                label_value(node)
+            ),
+        ),
+        PromScope::Pod { namespace, name } => {
+            let selector = format!("namespace={}, pod={}", label_value(namespace), label_value(name));
+            (
+                format!(", {}", selector),
+                format!("sum(increase(kube_pod_container_status_restarts_total{{{}}}[1h]))", selector),
+            )
+        }
+    };
+    let containers = format!("container!=\"\", image!=\"\"{}", selector);
+    let pods = selector.trim_start_matches(", ");
+
+    vec![
+        PromPanel {
+            title: "CPU",
+            unit: PromUnit::Cores,
+            queries: vec![("cores", format!("sum(rate(container_cpu_usage_seconds_total{{{}}}[5m]))", containers))],
+        },
+        PromPanel {
+            title: "Memory",
+            unit: PromUnit::Bytes,
+            queries: vec![("working set", format!("sum(container_memory_working_set_bytes{{{}}})", containers))],
+        },
+        PromPanel {
+            title: "Restarts",
+            unit: PromUnit::PerHour,
+            queries: vec![("restarts", restarts)],
+        },
+        PromPanel {
+            title: "Network I/O",
+            unit: PromUnit::BytesPerSecond,
+            queries: vec![
+                ("receive", format!("sum(rate(container_network_receive_bytes_total{{{}}}[5m]))", pods)),
+                ("transmit", format!("sum(rate(container_network_transmit_bytes_total{{{}}}[5m]))", pods)),
+            ],
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Answer one request with `body`, handing back the request line
+    async fn serve_once(listener: tokio::net::TcpListener, status: &'static str, body: &'static str) -> String {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+        }
+        let response = format!(
+            "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request).lines().next().unwrap_or_default().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_query_range_against_stub() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_once(
+            listener,
+            "200 OK",
+            r#"{"status":"success","data":{"resultType":"matrix","result":[
+                {"metric":{"__name__":"up","job":"api"},"values":[[1700000000,"1"],[1700000015.5,"0.5"],[1700000030,"NaN"]]}
+            ]}}"#,
+        ));
+
+        let prometheus = Prometheus::from_url(&url).unwrap();
+        let series = prometheus.query_range("sum(rate(x{a=\"b\"}[5m]))", QueryWindow::Hour).await.unwrap();
+        let request_line = server.await.unwrap();
+        assert!(request_line.starts_with("GET /api/v1/query_range?query=sum%28rate%28x%7Ba%3D%22b%22%7D%5B5m%5D%29%29&start="));
+        assert!(request_line.contains("&step=15 "));
+
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].name(), "up{job=\"api\"}");
+        let values: Vec<f64> = series[0].points.iter().map(|(_, value)| *value).collect();
+        assert_eq!(values, vec![1.0, 0.5]);
+        assert_eq!(series[0].points[1].0.timestamp_millis(), 1_700_000_015_500);
+    }
+
+    #[tokio::test]
+    async fn test_query_error_from_stub() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_once(
+            listener,
+            "400 Bad Request",
+            r#"{"status":"error","errorType":"bad_data","error":"parse error: unexpected end of input"}"#,
+        ));
+
+        let prometheus = Prometheus::from_url(&url).unwrap();
+        let error = prometheus.query_range("sum(", QueryWindow::Week).await.unwrap_err();
+        server.await.unwrap();
+        assert_eq!(error.to_string(), "parse error: unexpected end of input");
+    }
+
+    #[test]
+    fn test_prom_panels() {
+        let panels = prom_panels(&PromScope::Pod { namespace: "shop".to_string(), name: "web-\"1\"".to_string() });
+        assert_eq!(panels.len(), 4);
+        assert_eq!(
+            panels[0].queries[0].1,
+            "sum(rate(container_cpu_usage_seconds_total{container!=\"\", image!=\"\", namespace=\"shop\", pod=\"web-\\\"1\\\"\"}[5m]))"
+        );
+        assert_eq!(
+            panels[3].queries[0].1,
+            "sum(rate(container_network_receive_bytes_total{namespace=\"shop\", pod=\"web-\\\"1\\\"\"}[5m]))"
+        );
+
+        let cluster = prom_panels(&PromScope::Cluster);
+        assert_eq!(cluster[1].queries[0].1, "sum(container_memory_working_set_bytes{container!=\"\", image!=\"\"})");
+        assert_eq!(cluster[3].queries[1].1, "sum(rate(container_network_transmit_bytes_total{}[5m]))");
+    }
+}
@@ -0,0 +1,556 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+
+const NANOS_PER_UNIT: i128 = 1_000_000_000;
+/// The apiserver caps quantities at `i64::MAX` units, so parsing does too
+const MAX_NANOS: i128 = i64::MAX as i128 * NANOS_PER_UNIT;
+
+/// How a quantity was written, which decides how it's formatted back
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum QuantityFormat {
+    /// `500m`, `2`, `1.5G`
+    #[default]
+    DecimalSI,
+    /// `128Mi`, `1Gi`
+    BinarySI,
+    /// `1e3`, `5E-3`
+    DecimalExponent,
+}
+
+/// A string that isn't a valid Kubernetes quantity
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuantityError(String);
+
+impl fmt::Display for QuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid quantity {:?}", self.0)
+    }
+}
+
+impl std::error::Error for QuantityError {}
+
+/// A Kubernetes resource quantity held exactly, in billionths of a unit (nanocores for CPU,
+/// nanobytes for memory), following the apiserver's parsing and formatting rules.
+///
+/// Like the apiserver, anything finer than `1n` is rounded up and anything beyond
+/// `i64::MAX` units is capped. Equality and ordering compare values, ignoring format.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ParsedQuantity {
+    nanos: i128,
+    format: QuantityFormat,
+}
+
+impl ParsedQuantity {
+    pub fn parse(value: &str) -> Result<Self, QuantityError> {
+        let invalid = || QuantityError(value.to_string());
+
+        let (negative, unsigned) = match value.as_bytes().first() {
+            Some(b'-') => (true, &value[1..]),
+            Some(b'+') => (false, &value[1..]),
+            _ => (false, value),
+        };
+        let number_len = unsigned
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(unsigned.len());
+        let (number, suffix) = unsigned.split_at(number_len);
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if (whole.is_empty() && fraction.is_empty()) || fraction.contains('.') {
+            return Err(invalid());
+        }
+        let (exponent, binary_exponent, mut format) = parse_suffix(suffix).ok_or_else(invalid)?;
+
+        // value = digits * 10^(exponent - fraction digits) * 2^binary_exponent
+        let digits = format!("{}{}", whole, fraction);
+        let scale = exponent - fraction.len() as i32;
+        let magnitude = scale_to_nanos(digits.trim_start_matches('0'), scale, binary_exponent);
+
+        if format == QuantityFormat::BinarySI && magnitude > 0 && magnitude < NANOS_PER_UNIT {
+            // Fractions of a byte read better in decimal
+            format = QuantityFormat::DecimalSI;
+        }
+        let nanos = if negative { -magnitude } else { magnitude };
+        Ok(Self { nanos, format })
+    }
+
+    /// Read a quantity from the API, counting one that doesn't parse as zero
+    pub fn from_quantity(quantity: &Quantity) -> Self {
+        Self::parse(&quantity.0).unwrap_or_default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.nanos == 0
+    }
+
+    /// The value in whole units (cores or bytes), rounded up like `Quantity.Value()`
+    pub fn value(&self) -> i64 {
+        div_round_up(self.nanos, NANOS_PER_UNIT) as i64
+    }
+
+    /// The value in units as a float, for ratios and charts
+    pub fn as_f64(&self) -> f64 {
+        self.nanos as f64 / NANOS_PER_UNIT as f64
+    }
+
+    /// This quantity as a percentage of `total`, or zero when `total` is zero
+    pub fn percent_of(&self, total: &Self) -> f64 {
+        if total.nanos == 0 {
+            0.0
+        } else {
+            self.nanos as f64 / total.nanos as f64 * 100.0
+        }
+    }
+}
+
+/// Decimal and binary exponents, and the format, for a quantity suffix
+fn parse_suffix(suffix: &str) -> Option<(i32, u32, QuantityFormat)> {
+    let decimal = |exponent| Some((exponent, 0, QuantityFormat::DecimalSI));
+    let binary = |exponent| Some((0, exponent, QuantityFormat::BinarySI));
+    match suffix {
+        "" => decimal(0),
+        "n" => decimal(-9),
+        "u" => decimal(-6),
+        "m" => decimal(-3),
+        "k" => decimal(3),
+        "M" => decimal(6),
+        "G" => decimal(9),
+        "T" => decimal(12),
+        "P" => decimal(15),
+        "E" => decimal(18),
+        "Ki" => binary(10),
+        "Mi" => binary(20),
+        "Gi" => binary(30),
+        "Ti" => binary(40),
+        "Pi" => binary(50),
+        "Ei" => binary(60),
+        _ => {
+            let exponent = suffix.strip_prefix(['e', 'E'])?;
+            let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            // Anything this large is capped or rounded away regardless
+            let exponent = exponent.parse::<i64>().unwrap_or(if exponent.starts_with('-') { -100 } else { 100 });
+            Some((exponent.clamp(-100, 100) as i32, 0, QuantityFormat::DecimalExponent))
+        }
+    }
+}
+
+/// `digits * 10^scale * 2^binary_exponent` in nanounits, rounded up and capped at `MAX_NANOS`
+fn scale_to_nanos(digits: &str, scale: i32, binary_exponent: u32) -> i128 {
+    // Trailing zeros only shift the scale
+    let significant = digits.trim_end_matches('0');
+    if significant.is_empty() {
+        return 0;
+    }
+    let mut scale = scale + 9 + (digits.len() - significant.len()) as i32;
+
+    // Thirty digits is already past the cap unless the scale is negative, in which case
+    // anything dropped lies below a nanounit and only matters for rounding up
+    let kept = significant.len().min(30);
+    let mut inexact = kept < significant.len();
+    scale += (significant.len() - kept) as i32;
+    let mut mantissa: i128 = significant[..kept].parse().unwrap_or(0);
+
+    for _ in 0..binary_exponent {
+        while mantissa > i128::MAX / 2 {
+            inexact |= mantissa % 10 != 0;
+            mantissa /= 10;
+            scale += 1;
+        }
+        mantissa *= 2;
+    }
+
+    let magnitude = if scale >= 0 {
+        10i128
+            .checked_pow(scale as u32)
+            .and_then(|factor| mantissa.checked_mul(factor))
+            .unwrap_or(MAX_NANOS)
+    } else if scale < -38 {
+        // Smaller than a nanounit, which rounds up to one
+        1
+    } else {
+        let divisor = 10i128.pow(scale.unsigned_abs());
+        let quotient = mantissa / divisor;
+        if mantissa % divisor != 0 || inexact {
+            quotient + 1
+        } else {
+            quotient
+        }
+    };
+    magnitude.min(MAX_NANOS)
+}
+
+/// Divide, rounding away from zero
+fn div_round_up(value: i128, divisor: i128) -> i128 {
+    let quotient = value / divisor;
+    if value % divisor == 0 {
+        quotient
+    } else {
+        quotient + value.signum()
+    }
+}
+
+impl FromStr for ParsedQuantity {
+    type Err = QuantityError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+/// Formats canonically, as the apiserver and kubectl print quantities: decimal values use
+/// the largest SI suffix that keeps an integer, and binary ones only when it's exact.
+impl fmt::Display for ParsedQuantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.nanos == 0 {
+            return write!(f, "0");
+        }
+        let binary = self.format == QuantityFormat::BinarySI
+            && self.nanos.abs() >= 1024 * NANOS_PER_UNIT
+            && self.nanos % NANOS_PER_UNIT == 0;
+        if binary {
+            let mut units = self.nanos / NANOS_PER_UNIT;
+            let mut power = 0;
+            while power < 6 && units % 1024 == 0 {
+                units /= 1024;
+                power += 1;
+            }
+            let suffix = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"][power];
+            return write!(f, "{}{}", units, suffix);
+        }
+
+        let mut mantissa = self.nanos;
+        let mut exponent: i32 = -9;
+        while mantissa % 10 == 0 {
+            mantissa /= 10;
+            exponent += 1;
+        }
+        // Suffixes step in thousands, so move the exponent down to a multiple of three
+        let remainder = exponent.rem_euclid(3);
+        mantissa *= 10i128.pow(remainder as u32);
+        exponent -= remainder;
+
+        let suffix = match (self.format, exponent) {
+            (QuantityFormat::DecimalExponent, 0) => String::new(),
+            (QuantityFormat::DecimalExponent, exponent) => format!("e{}", exponent),
+            (_, -9) => "n".to_string(),
+            (_, -6) => "u".to_string(),
+            (_, -3) => "m".to_string(),
+            (_, 0) => String::new(),
+            (_, 3) => "k".to_string(),
+            (_, 6) => "M".to_string(),
+            (_, 9) => "G".to_string(),
+            (_, 12) => "T".to_string(),
+            (_, 15) => "P".to_string(),
+            (_, 18) => "E".to_string(),
+            (_, exponent) => format!("e{}", exponent),
+        };
+        write!(f, "{}{}", mantissa, suffix)
+    }
+}
+
+impl PartialEq for ParsedQuantity {
+    fn eq(&self, other: &Self) -> bool {
+        self.nanos == other.nanos
+    }
+}
+
+impl Eq for ParsedQuantity {}
+
+impl PartialOrd for ParsedQuantity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ParsedQuantity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.nanos.cmp(&other.nanos)
+    }
+}
+
+impl Add for ParsedQuantity {
+    type Output = Self;
+
+    /// Sums keep the left-hand format, unless it's zero
+    fn add(self, rhs: Self) -> Self {
+        let format = if self.nanos == 0 { rhs.format } else { self.format };
+        let nanos = (self.nanos + rhs.nanos).clamp(-MAX_NANOS, MAX_NANOS);
+        Self { nanos, format }
+    }
+}
+
+impl Sub for ParsedQuantity {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + Self { nanos: -rhs.nanos, format: rhs.format }
+    }
+}
+
+impl AddAssign for ParsedQuantity {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for ParsedQuantity {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for ParsedQuantity {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use QuantityFormat::*;
+
+    /// `mantissa * 10^exponent` in nanounits, as upstream's `decQuantity` builds them
+    fn dec(mantissa: i128, exponent: i32, format: QuantityFormat) -> ParsedQuantity {
+        let nanos = if exponent + 9 >= 0 {
+            mantissa * 10i128.pow((exponent + 9) as u32)
+        } else {
+            mantissa / 10i128.pow((-exponent - 9) as u32)
+        };
+        ParsedQuantity { nanos, format }
+    }
+
+    fn parse(value: &str) -> ParsedQuantity {
+        ParsedQuantity::parse(value).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Vectors from apimachinery's TestQuantityParse
+    #[test]
+    fn test_parse() {
+        let max = ParsedQuantity { nanos: MAX_NANOS, format: BinarySI };
+        let cases = [
+            ("0", dec(0, 0, DecimalSI)),
+            ("0n", dec(0, 0, DecimalSI)),
+            ("0Ki", dec(0, 0, BinarySI)),
+            ("0e10", dec(0, 0, DecimalExponent)),
+            // Binary suffixes
+            ("1Ki", dec(1024, 0, BinarySI)),
+            ("8Ki", dec(8 * 1024, 0, BinarySI)),
+            ("7Mi", dec(7 * 1024 * 1024, 0, BinarySI)),
+            ("6Gi", dec(6 * 1024 * 1024 * 1024, 0, BinarySI)),
+            ("5Ti", dec(5 * 1024i128.pow(4), 0, BinarySI)),
+            ("4Pi", dec(4 * 1024i128.pow(5), 0, BinarySI)),
+            ("3Ei", dec(3 * 1024i128.pow(6), 0, BinarySI)),
+            ("10Ti", dec(10 * 1024i128.pow(4), 0, BinarySI)),
+            // Decimal suffixes
+            ("5n", dec(5, -9, DecimalSI)),
+            ("4u", dec(4, -6, DecimalSI)),
+            ("3m", dec(3, -3, DecimalSI)),
+            ("9", dec(9, 0, DecimalSI)),
+            ("8k", dec(8, 3, DecimalSI)),
+            ("50k", dec(5, 4, DecimalSI)),
+            ("7M", dec(7, 6, DecimalSI)),
+            ("6G", dec(6, 9, DecimalSI)),
+            ("5T", dec(5, 12, DecimalSI)),
+            ("40T", dec(4, 13, DecimalSI)),
+            ("300T", dec(3, 14, DecimalSI)),
+            ("2P", dec(2, 15, DecimalSI)),
+            ("1E", dec(1, 18, DecimalSI)),
+            // Decimal exponents
+            ("1E-3", dec(1, -3, DecimalExponent)),
+            ("1e3", dec(1, 3, DecimalExponent)),
+            ("1E6", dec(1, 6, DecimalExponent)),
+            ("1e9", dec(1, 9, DecimalExponent)),
+            ("1E12", dec(1, 12, DecimalExponent)),
+            ("1e15", dec(1, 15, DecimalExponent)),
+            ("1E18", dec(1, 18, DecimalExponent)),
+            ("1e14", dec(1, 14, DecimalExponent)),
+            ("100.035k", dec(100035, 0, DecimalSI)),
+            // Things that look like floating point
+            ("0.001", dec(1, -3, DecimalSI)),
+            ("0.0005k", dec(5, -1, DecimalSI)),
+            ("0.005", dec(5, -3, DecimalSI)),
+            ("0.05", dec(5, -2, DecimalSI)),
+            ("0.5", dec(5, -1, DecimalSI)),
+            ("0.00050k", dec(5, -1, DecimalSI)),
+            ("0.00500", dec(5, -3, DecimalSI)),
+            ("0.5e0", dec(5, -1, DecimalExponent)),
+            ("0.5e-1", dec(5, -2, DecimalExponent)),
+            ("0.5e-2", dec(5, -3, DecimalExponent)),
+            ("10.035M", dec(10035, 3, DecimalSI)),
+            ("1.2e3", dec(12, 2, DecimalExponent)),
+            ("1.3E+6", dec(13, 5, DecimalExponent)),
+            ("1.40e9", dec(14, 8, DecimalExponent)),
+            ("1.53E12", dec(153, 10, DecimalExponent)),
+            ("1.6e15", dec(16, 14, DecimalExponent)),
+            ("1.7E18", dec(17, 17, DecimalExponent)),
+            ("9.01", dec(901, -2, DecimalSI)),
+            ("8.1k", dec(81, 2, DecimalSI)),
+            ("7.123456M", dec(7123456, 0, DecimalSI)),
+            ("6.987654321G", dec(6987654321, 0, DecimalSI)),
+            ("5.444T", dec(5444, 9, DecimalSI)),
+            ("40.1T", dec(401, 11, DecimalSI)),
+            ("300.2T", dec(3002, 11, DecimalSI)),
+            ("2.5P", dec(25, 14, DecimalSI)),
+            ("1.01E", dec(101, 16, DecimalSI)),
+            // Things that saturate or round
+            ("3.001n", dec(4, -9, DecimalSI)),
+            ("1.1E-9", dec(2, -9, DecimalExponent)),
+            ("0.0000000001", dec(1, -9, DecimalSI)),
+            ("0.0000000005", dec(1, -9, DecimalSI)),
+            ("0.00000000050", dec(1, -9, DecimalSI)),
+            ("0.5e-9", dec(1, -9, DecimalExponent)),
+            ("0.9n", dec(1, -9, DecimalSI)),
+            ("0.00000012345", dec(124, -9, DecimalSI)),
+            ("0.00000012354", dec(124, -9, DecimalSI)),
+            ("9Ei", max),
+            ("9223372036854775807Ki", max),
+            // Upstream's int64 fast path lets this one past the cap; we cap it like the rest
+            ("12E", ParsedQuantity { nanos: MAX_NANOS, format: DecimalSI }),
+            // Fractional binary quantities
+            ("100.035Ki", dec(10243584, -2, BinarySI)),
+            ("0.5Mi", dec(512 * 1024, 0, BinarySI)),
+            ("0.05Gi", dec(536870912, -1, BinarySI)),
+            ("0.025Ti", dec(274877906944, -1, BinarySI)),
+            // Things written by trolls
+            ("0.000000000001Ki", dec(2, -9, DecimalSI)),
+            (".001", dec(1, -3, DecimalSI)),
+            (".0001k", dec(100, -3, DecimalSI)),
+            ("1.", dec(1, 0, DecimalSI)),
+            ("1.G", dec(1, 9, DecimalSI)),
+            // Signs
+            ("-5m", dec(-5, -3, DecimalSI)),
+            ("+2Ki", dec(2048, 0, BinarySI)),
+        ];
+        for (input, expected) in cases {
+            let parsed = parse(input);
+            assert_eq!(parsed, expected, "value of {}", input);
+            assert_eq!(parsed.format, expected.format, "format of {}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for input in [
+            "", " 1", "1 ", "-", "+", ".", "1.1.1", "1i", "1K", "1ki", "1mi", "1e", "1E+", "1e1.0", "1.5Mi.", "--1",
+            "Ki", "1Kib", "1e3Ki",
+        ] {
+            assert!(ParsedQuantity::parse(input).is_err(), "{:?} should not parse", input);
+        }
+    }
+
+    // Vectors from apimachinery's TestQuantityString
+    #[test]
+    fn test_format() {
+        let cases = [
+            (dec(1024 * 1024 * 1024, 0, BinarySI), "1Gi"),
+            (dec(300 * 1024 * 1024, 0, BinarySI), "300Mi"),
+            (dec(6 * 1024, 0, BinarySI), "6Ki"),
+            (dec(1001 * 1024 * 1024 * 1024, 0, BinarySI), "1001Gi"),
+            (dec(1024i128.pow(4), 0, BinarySI), "1Ti"),
+            (dec(5, 0, BinarySI), "5"),
+            (dec(500, -3, BinarySI), "500m"),
+            (dec(1, 9, DecimalSI), "1G"),
+            (dec(1000, 6, DecimalSI), "1G"),
+            (dec(1000000, 3, DecimalSI), "1G"),
+            (dec(1, -3, DecimalSI), "1m"),
+            (dec(80, -3, DecimalSI), "80m"),
+            (dec(1080, -3, DecimalSI), "1080m"),
+            (dec(108, -2, DecimalSI), "1080m"),
+            (dec(10800, -4, DecimalSI), "1080m"),
+            (dec(300, 6, DecimalSI), "300M"),
+            (dec(1, 12, DecimalSI), "1T"),
+            (dec(1234567, 6, DecimalSI), "1234567M"),
+            (dec(1234567, -3, BinarySI), "1234567m"),
+            (dec(3, 3, DecimalSI), "3k"),
+            (dec(1025, 0, BinarySI), "1025"),
+            (dec(0, 0, DecimalSI), "0"),
+            (dec(0, 0, BinarySI), "0"),
+            (dec(1, 9, DecimalExponent), "1e9"),
+            (dec(1, -3, DecimalExponent), "1e-3"),
+            (dec(1, -9, DecimalExponent), "1e-9"),
+            (dec(80, -3, DecimalExponent), "80e-3"),
+            (dec(300, 6, DecimalExponent), "300e6"),
+            (dec(1, 12, DecimalExponent), "1e12"),
+            (dec(1, 3, DecimalExponent), "1e3"),
+            (dec(3, 3, DecimalExponent), "3e3"),
+            (dec(0, 0, DecimalExponent), "0"),
+            (dec(1, -9, DecimalSI), "1n"),
+            (dec(80, -9, DecimalSI), "80n"),
+            (dec(1080, -9, DecimalSI), "1080n"),
+            (dec(108, -8, DecimalSI), "1080n"),
+            (dec(1, -6, DecimalSI), "1u"),
+            (dec(80, -6, DecimalSI), "80u"),
+            (dec(1080, -6, DecimalSI), "1080u"),
+            (dec(-1080, -3, DecimalSI), "-1080m"),
+            (dec(-2048, 0, BinarySI), "-2Ki"),
+        ];
+        for (quantity, expected) in cases {
+            assert_eq!(quantity.to_string(), expected);
+        }
+    }
+
+    // Vectors from apimachinery's TestQuantityParseEmit
+    #[test]
+    fn test_parse_emit() {
+        for (input, expected) in [
+            ("1Ki", "1Ki"),
+            ("1Mi", "1Mi"),
+            ("1Gi", "1Gi"),
+            ("1024Mi", "1Gi"),
+            ("1000M", "1G"),
+            (".001Ki", "1024m"),
+            (".000001Ki", "1024u"),
+            (".000000001Ki", "1024n"),
+            (".000000000001Ki", "2n"),
+        ] {
+            assert_eq!(parse(input).to_string(), expected, "canonical form of {}", input);
+        }
+    }
+
+    /// Formatting then parsing any quantity gives back the same value
+    #[test]
+    fn test_format_round_trip() {
+        // A fixed xorshift sequence, so failures reproduce
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let formats = [DecimalSI, BinarySI, DecimalExponent];
+        for _ in 0..10_000 {
+            let magnitude = next() as i128 * 10i128.pow((next() % 10) as u32) / 10i128.pow((next() % 19) as u32);
+            let nanos = if next() % 2 == 0 { magnitude } else { -magnitude };
+            let quantity = ParsedQuantity { nanos: nanos.clamp(-MAX_NANOS, MAX_NANOS), format: formats[(next() % 3) as usize] };
+            let formatted = quantity.to_string();
+            assert_eq!(parse(&formatted), quantity, "{:?} formatted as {}", quantity, formatted);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let total: ParsedQuantity = ["250m", "1", "1500m"].iter().map(|q| parse(q)).sum();
+        assert_eq!(total.to_string(), "2750m");
+
+        let memory = parse("1Gi") - parse("512Mi");
+        assert_eq!(memory.to_string(), "512Mi");
+        assert_eq!(memory.value(), 512 * 1024 * 1024);
+        assert!(parse("1G") < parse("1Gi"));
+        assert!(parse("1000m") == parse("1"));
+        assert_eq!(parse("0") + parse("2Ki"), parse("2048"));
+        assert_eq!((parse("0") + parse("2Ki")).to_string(), "2Ki");
+
+        // Rounded up, like Value()
+        assert_eq!(parse("123456789n").value(), 1);
+        assert_eq!(parse("1.5").value(), 2);
+        assert_eq!(parse("250m").percent_of(&parse("1")), 25.0);
+    }
+}
@@ -1,5 +1,8 @@
 use k8s_openapi::api::core::v1::Pod;
 
+use super::cache::ClusterCache;
+use crate::contexts::error::AppResult;
+
 #[derive(Clone)]
 pub struct PodResourceIssue {
     pub name: String,
@@ -80,33 +83,7 @@ pub fn check_pod_resource_limits(pod: &Pod) -> Option<PodResourceIssue> {
     }
 }
 
-pub async fn find_pods_without_limits(client: kube::Client) -> Vec<PodResourceIssue> {
-    let pods: kube::Api<Pod> = kube::Api::all(client);
-    let mut pods_without_limits = Vec::new();
-    let mut params = kube::api::ListParams::default().limit(100); // Fetch pods in batches of 100
-
-    loop {
-        match pods.list(&params).await {
-            Ok(pod_list) => {
-                // Process this batch of pods
-                pods_without_limits.extend(
-                    pod_list.items
-                        .iter()
-                        .filter_map(check_pod_resource_limits)
-                );
-
-                // Check if we need to fetch more pods
-                if let Some(continue_token) = pod_list.metadata.continue_ {
-                    params = params.continue_token(&continue_token);
-                } else {
-                    break;
-                }
-            }
-            Err(_e) => {
-                break;
-            }
-        }
-    }
-
-    pods_without_limits
+pub async fn find_pods_without_limits(cache: &ClusterCache) -> AppResult<Vec<PodResourceIssue>> {
+    let pods = cache.list::<Pod>().await?;
+    Ok(pods.iter().filter_map(check_pod_resource_limits).collect())
 }
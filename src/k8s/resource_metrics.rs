@@ -1,13 +1,17 @@
-use dioxus::{logger::tracing};
 use k8s_openapi::api::core::v1::Pod;
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use kube::{
     api::{Api, ListParams},
-    Resource,
+    Client, Resource,
 };
 use serde::Deserialize;
 
+use super::cache::ClusterCache;
+use super::metrics_history::{MetricsSeries, UsageSnapshot};
+use super::quantity::ParsedQuantity;
+use crate::contexts::error::{AppError, AppResult};
+
 #[derive(Clone)]
 pub struct ResourceHotspot {
     pub name: String,
@@ -19,22 +23,23 @@ pub struct ResourceHotspot {
 }
 
 #[derive(Deserialize, Clone, Debug)]
-struct MetricsContainerUsage {
-    cpu: Quantity,
-    memory: Quantity,
+pub struct MetricsContainerUsage {
+    pub cpu: Quantity,
+    pub memory: Quantity,
 }
 
 #[derive(Deserialize, Clone, Debug)]
-struct MetricsContainer {
-    usage: MetricsContainerUsage,
+pub struct MetricsContainer {
+    pub name: String,
+    pub usage: MetricsContainerUsage,
 }
 
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
-struct PodMetrics {
-    metadata: ObjectMeta,
+pub struct PodMetrics {
+    pub metadata: ObjectMeta,
     #[serde(default)]
-    containers: Vec<MetricsContainer>,
+    pub containers: Vec<MetricsContainer>,
 }
 
 impl Resource for PodMetrics {
@@ -70,114 +75,69 @@ impl Resource for PodMetrics {
     }
 }
 
-fn parse_cpu_value(cpu: &Quantity) -> f64 {
-    let cpu_str = cpu.0.to_string();
-    if cpu_str.ends_with('m') {
-        // Convert millicores to cores
-        cpu_str.trim_end_matches('m').parse::<f64>().unwrap_or(0.0) / 1000.0
-    } else {
-        // Already in cores
-        cpu_str.parse::<f64>().unwrap_or(0.0)
-    }
+/// Current usage of every pod from metrics-server. Metrics can't be watched, so they aren't cached.
+pub async fn fetch_pod_metrics(client: &Client) -> AppResult<Vec<PodMetrics>> {
+    let metrics_api: Api<PodMetrics> = Api::all(client.clone());
+    Ok(metrics_api
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| AppError::from_metrics(e, "pods.metrics.k8s.io"))?
+        .items)
 }
 
-fn parse_memory_value(memory: &Quantity) -> f64 {
-    let mem_str = memory.0.to_string();
-    if mem_str.ends_with("Ki") {
-        mem_str.trim_end_matches("Ki").parse::<f64>().unwrap_or(0.0) * 1024.0
-    } else if mem_str.ends_with("Mi") {
-        mem_str.trim_end_matches("Mi").parse::<f64>().unwrap_or(0.0) * 1024.0 * 1024.0
-    } else if mem_str.ends_with("Gi") {
-        mem_str.trim_end_matches("Gi").parse::<f64>().unwrap_or(0.0) * 1024.0 * 1024.0 * 1024.0
-    } else {
-        // Assume bytes
-        mem_str.parse::<f64>().unwrap_or(0.0)
-    }
-}
-
-pub async fn find_resource_hotspots(client: kube::Client) -> Vec<ResourceHotspot> {
+/// Pods using much or little of their limits, going by the background sampler's newest round
+pub async fn find_resource_hotspots(cache: &ClusterCache, usage: &UsageSnapshot) -> AppResult<Vec<ResourceHotspot>> {
     let mut hotspots = Vec::new();
-    
-    // Create an API for the metrics endpoint
-    let metrics_api: Api<PodMetrics> = Api::all(client.clone());    
 
     // Get all pods first to access their resource limits
-    let pods: Api<Pod> = Api::all(client);
-    let pod_list = match pods.list(&ListParams::default()).await {
-        Ok(list) => list.items,
-        Err(_e) => {
-            tracing::error!("Failed to fetch pods for resource hotspots: {}", _e);
-            return Vec::new();
-        }
-    };
+    let pod_list = cache.list::<Pod>().await?;
 
-    // Get pod metrics
-    let pod_metrics = match metrics_api.list(&ListParams::default()).await {
-        Ok(metrics) => metrics.items,
-        Err(e) => {
-            tracing::error!("Failed to fetch pod metrics: {}", e);
-            return Vec::new();
-        }
-    };
-
-    // Build a map of pod name to its limits
+    // Build a map of pod namespace and name to its limits
     let mut pod_limits = std::collections::HashMap::new();
     for pod in &pod_list {
-        if let Some(name) = &pod.metadata.name {
+        if let (Some(namespace), Some(name)) = (&pod.metadata.namespace, &pod.metadata.name) {
             if let Some(spec) = &pod.spec {
-                let mut total_cpu_limit = 0.0;
-                let mut total_memory_limit = 0.0;
+                let mut total_cpu_limit = ParsedQuantity::default();
+                let mut total_memory_limit = ParsedQuantity::default();
 
                 for container in &spec.containers {
                     if let Some(resources) = &container.resources {
                         if let Some(limits) = &resources.limits {
                             if let Some(cpu) = limits.get("cpu") {
-                                total_cpu_limit += parse_cpu_value(cpu);
+                                total_cpu_limit += ParsedQuantity::from_quantity(cpu);
                             }
                             if let Some(memory) = limits.get("memory") {
-                                total_memory_limit += parse_memory_value(memory);
+                                total_memory_limit += ParsedQuantity::from_quantity(memory);
                             }
                         }
                     }
                 }
 
-                pod_limits.insert(name.clone(), (total_cpu_limit, total_memory_limit));
+                pod_limits.insert((namespace.clone(), name.clone()), (total_cpu_limit, total_memory_limit));
             }
         }
     }
 
+    // Usage as a percentage of a limit, or zero without one
+    let percent_of = |used: f64, limit: &ParsedQuantity| {
+        if limit.is_zero() { 0.0 } else { used / limit.as_f64() * 100.0 }
+    };
+
     // Process metrics and compare against limits
-    for metric in pod_metrics {
-        let name = metric.metadata.name.clone().unwrap_or_default();
-        let namespace = metric.metadata.namespace.clone().unwrap_or_default();
+    for (series, sample) in &usage.samples {
+        let MetricsSeries::Pod { namespace, name } = series.clone() else {
+            continue;
+        };
 
         // Skip system namespaces
         if namespace == "kube-system" || namespace == "kube-public" {
             continue;
         }
 
-        let mut total_cpu_usage = 0.0;
-        let mut total_memory_usage = 0.0;
-
-        // Sum up container metrics
-        for container in &metric.containers {
-            total_cpu_usage += parse_cpu_value(&container.usage.cpu);
-            total_memory_usage += parse_memory_value(&container.usage.memory);
-        }
-
         // Compare with limits if available
-        if let Some((cpu_limit, memory_limit)) = pod_limits.get(&name) {
-            let cpu_usage_percent = if *cpu_limit > 0.0 {
-                (total_cpu_usage / cpu_limit) * 100.0
-            } else {
-                0.0
-            };
-
-            let memory_usage_percent = if *memory_limit > 0.0 {
-                (total_memory_usage / memory_limit) * 100.0
-            } else {
-                0.0
-            };
+        if let Some((cpu_limit, memory_limit)) = pod_limits.get(&(namespace.clone(), name.clone())) {
+            let cpu_usage_percent = percent_of(sample.cpu, cpu_limit);
+            let memory_usage_percent = percent_of(sample.memory, memory_limit);
 
             // Check for high usage (over 80%) and low usage (under 10%)
             if cpu_usage_percent >= 20.0 {
@@ -201,7 +161,7 @@ pub async fn find_resource_hotspots(client: kube::Client) -> Vec<ResourceHotspot
             }
 
             // Check for underutilization
-            if cpu_usage_percent <= 10.0 && *cpu_limit > 0.0 {
+            if cpu_usage_percent <= 10.0 && !cpu_limit.is_zero() {
                 hotspots.push(ResourceHotspot {
                     name: name.clone(),
                     namespace: namespace.clone(),
@@ -211,7 +171,7 @@ pub async fn find_resource_hotspots(client: kube::Client) -> Vec<ResourceHotspot
                     severity: "low".to_string(),
                 });
             } 
-            if memory_usage_percent <= 10.0 && *memory_limit > 0.0 {
+            if memory_usage_percent <= 10.0 && !memory_limit.is_zero() {
                 hotspots.push(ResourceHotspot {
                     name,
                     namespace,
@@ -231,5 +191,5 @@ pub async fn find_resource_hotspots(client: kube::Client) -> Vec<ResourceHotspot
         b_max.partial_cmp(&a_max).unwrap_or(std::cmp::Ordering::Equal)
     });
 
-    hotspots
+    Ok(hotspots)
 }
@@ -0,0 +1,608 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Debug;
+
+use k8s_openapi::api::apps::v1::{ControllerRevision, DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::core::v1::PodTemplateSpec;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use k8s_openapi::{chrono::Utc, serde_json::{self, json}, NamespaceResourceScope};
+use kube::{
+    api::{Api, ListParams, Patch, PatchParams, PostParams},
+    Client, Resource, ResourceExt,
+};
+use serde::de::DeserializeOwned;
+
+/// Template annotation `kubectl rollout restart` bumps to roll every pod
+pub const RESTARTED_AT_ANNOTATION: &str = "kubectl.kubernetes.io/restartedAt";
+/// Revision number the deployment controller stamps on its ReplicaSets
+pub const REVISION_ANNOTATION: &str = "deployment.kubernetes.io/revision";
+pub const CHANGE_CAUSE_ANNOTATION: &str = "kubernetes.io/change-cause";
+
+/// Workloads that support rollout actions
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WorkloadKind {
+    Deployment,
+    StatefulSet,
+    DaemonSet,
+}
+
+impl fmt::Display for WorkloadKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkloadKind::Deployment => write!(f, "Deployment"),
+            WorkloadKind::StatefulSet => write!(f, "StatefulSet"),
+            WorkloadKind::DaemonSet => write!(f, "DaemonSet"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RolloutError {
+    Kube(kube::Error),
+    RevisionNotFound(i64),
+    InvalidRevision(String),
+    Unsupported(String),
+}
+
+impl fmt::Display for RolloutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RolloutError::Kube(e) => write!(f, "Kubernetes API error: {}", e),
+            RolloutError::RevisionNotFound(revision) => write!(f, "Revision {} not found", revision),
+            RolloutError::InvalidRevision(msg) => write!(f, "Invalid revision: {}", msg),
+            RolloutError::Unsupported(msg) => write!(f, "Not supported: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RolloutError {}
+
+impl From<kube::Error> for RolloutError {
+    fn from(err: kube::Error) -> Self {
+        RolloutError::Kube(err)
+    }
+}
+
+/// An earlier (or the current) version of a workload's pod template
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkloadRevision {
+    pub revision: i64,
+    /// ReplicaSet or ControllerRevision holding this revision
+    pub name: String,
+    pub change_cause: Option<String>,
+    pub created: Option<Time>,
+    /// Ready and desired replicas; only ReplicaSets run pods of their own
+    pub replicas: Option<(i32, i32)>,
+    pub template: Option<PodTemplateSpec>,
+}
+
+/// Rollout state derived from a workload's status, following `kubectl rollout status`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RolloutProgress {
+    /// Generation the controller has acted on; progress is stale until it catches up
+    pub observed_generation: i64,
+    pub desired: i32,
+    pub updated: i32,
+    pub ready: i32,
+    pub done: bool,
+    pub failed: bool,
+    pub message: String,
+}
+
+impl RolloutProgress {
+    /// Whether the rollout started by a change at `generation` has finished successfully
+    pub fn has_converged(&self, generation: i64) -> bool {
+        self.observed_generation >= generation && self.done
+    }
+
+    pub fn percent(&self) -> f32 {
+        if self.desired <= 0 {
+            return 100.0;
+        }
+        (self.updated.min(self.ready) as f32 / self.desired as f32 * 100.0).clamp(0.0, 100.0)
+    }
+}
+
+pub fn deployment_rollout_progress(deployment: &Deployment) -> RolloutProgress {
+    let generation = deployment.metadata.generation.unwrap_or(0);
+    let spec = deployment.spec.as_ref();
+    let status = deployment.status.clone().unwrap_or_default();
+    let desired = spec.and_then(|s| s.replicas).unwrap_or(1);
+    let updated = status.updated_replicas.unwrap_or(0);
+    let available = status.available_replicas.unwrap_or(0);
+    let total = status.replicas.unwrap_or(0);
+    let observed_generation = status.observed_generation.unwrap_or(0);
+
+    let mut progress = RolloutProgress {
+        observed_generation,
+        desired,
+        updated,
+        ready: available,
+        done: false,
+        failed: false,
+        message: String::new(),
+    };
+
+    if generation > observed_generation {
+        progress.message = "Waiting for deployment spec update to be observed".to_string();
+        return progress;
+    }
+    let deadline_exceeded = status
+        .conditions
+        .iter()
+        .flatten()
+        .any(|c| c.type_ == "Progressing" && c.reason.as_deref() == Some("ProgressDeadlineExceeded"));
+
+    if deadline_exceeded {
+        progress.failed = true;
+        progress.message = "Rollout exceeded its progress deadline".to_string();
+    } else if spec.and_then(|s| s.paused).unwrap_or(false) {
+        // A paused rollout won't move until resumed, so there is nothing to wait for
+        progress.done = true;
+        progress.message = "Rollout paused".to_string();
+    } else if updated < desired {
+        progress.message = format!("{} of {} new replicas have been updated", updated, desired);
+    } else if total > updated {
+        progress.message = format!("{} old replicas are pending termination", total - updated);
+    } else if available < updated {
+        progress.message = format!("{} of {} updated replicas are available", available, updated);
+    } else {
+        progress.done = true;
+        progress.message = "Successfully rolled out".to_string();
+    }
+    progress
+}
+
+pub fn statefulset_rollout_progress(statefulset: &StatefulSet) -> RolloutProgress {
+    let generation = statefulset.metadata.generation.unwrap_or(0);
+    let spec = statefulset.spec.as_ref();
+    let status = statefulset.status.clone().unwrap_or_default();
+    let desired = spec.and_then(|s| s.replicas).unwrap_or(1);
+    let updated = status.updated_replicas.unwrap_or(0);
+    let ready = status.ready_replicas.unwrap_or(0);
+    let observed_generation = status.observed_generation.unwrap_or(0);
+    let strategy = spec.and_then(|s| s.update_strategy.as_ref());
+
+    let mut progress = RolloutProgress {
+        observed_generation,
+        desired,
+        updated,
+        ready,
+        done: false,
+        failed: false,
+        message: String::new(),
+    };
+
+    if observed_generation == 0 || generation > observed_generation {
+        progress.message = "Waiting for statefulset spec update to be observed".to_string();
+        return progress;
+    }
+    if strategy.and_then(|s| s.type_.as_deref()) == Some("OnDelete") {
+        // Pods only pick up the new template when deleted, so the controller never converges
+        progress.done = true;
+        progress.message = "OnDelete strategy: pods update when they are deleted".to_string();
+        return progress;
+    }
+    if ready < desired {
+        progress.message = format!("Waiting for {} pods to be ready", desired - ready);
+        return progress;
+    }
+
+    let partition = strategy
+        .and_then(|s| s.rolling_update.as_ref())
+        .and_then(|r| r.partition)
+        .unwrap_or(0);
+    if partition > 0 {
+        let expected = desired - partition;
+        if updated < expected {
+            progress.message = format!("Partitioned rollout: {} of {} new pods have been updated", updated, expected);
+        } else {
+            progress.done = true;
+            progress.message = "Partitioned rollout complete".to_string();
+        }
+        return progress;
+    }
+
+    if status.update_revision.is_some() && status.update_revision != status.current_revision {
+        progress.message = format!(
+            "Rolling update in progress: {} of {} pods at revision {}",
+            updated,
+            desired,
+            status.update_revision.unwrap_or_default()
+        );
+    } else {
+        progress.done = true;
+        progress.message = "Rolling update complete".to_string();
+    }
+    progress
+}
+
+/// Set the replica count through the `/scale` subresource and return the new generation
+pub async fn scale_workload(
+    client: Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+    replicas: i32,
+) -> Result<i64, RolloutError> {
+    match kind {
+        WorkloadKind::Deployment => scale::<Deployment>(client, namespace, name, replicas).await,
+        WorkloadKind::StatefulSet => scale::<StatefulSet>(client, namespace, name, replicas).await,
+        WorkloadKind::DaemonSet => Err(RolloutError::Unsupported(
+            "DaemonSets run one pod per node and can't be scaled".to_string(),
+        )),
+    }
+}
+
+async fn scale<K>(client: Client, namespace: &str, name: &str, replicas: i32) -> Result<i64, RolloutError>
+where
+    K: Resource<Scope = NamespaceResourceScope, DynamicType = ()> + Clone + DeserializeOwned + Debug,
+{
+    let api: Api<K> = Api::namespaced(client, namespace);
+    let patch = json!({ "spec": { "replicas": replicas } });
+    api.patch_scale(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    // The scale subresource doesn't carry the workload's generation
+    Ok(api.get(name).await?.meta().generation.unwrap_or(0))
+}
+
+/// Roll every pod the way `kubectl rollout restart` does, by stamping the pod template
+pub async fn restart_workload(
+    client: Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+) -> Result<i64, RolloutError> {
+    let patch = json!({
+        "spec": {
+            "template": {
+                "metadata": {
+                    "annotations": { RESTARTED_AT_ANNOTATION: Utc::now().to_rfc3339() }
+                }
+            }
+        }
+    });
+    match kind {
+        WorkloadKind::Deployment => merge_patch::<Deployment>(client, namespace, name, &patch).await,
+        WorkloadKind::StatefulSet => merge_patch::<StatefulSet>(client, namespace, name, &patch).await,
+        WorkloadKind::DaemonSet => merge_patch::<DaemonSet>(client, namespace, name, &patch).await,
+    }
+}
+
+/// Pause or resume a deployment's rollout
+pub async fn set_deployment_paused(
+    client: Client,
+    namespace: &str,
+    name: &str,
+    paused: bool,
+) -> Result<i64, RolloutError> {
+    let patch = json!({ "spec": { "paused": paused } });
+    merge_patch::<Deployment>(client, namespace, name, &patch).await
+}
+
+async fn merge_patch<K>(
+    client: Client,
+    namespace: &str,
+    name: &str,
+    patch: &serde_json::Value,
+) -> Result<i64, RolloutError>
+where
+    K: Resource<Scope = NamespaceResourceScope, DynamicType = ()> + Clone + DeserializeOwned + Debug,
+{
+    let api: Api<K> = Api::namespaced(client, namespace);
+    let patched = api.patch(name, &PatchParams::default(), &Patch::Merge(patch)).await?;
+    Ok(patched.meta().generation.unwrap_or(0))
+}
+
+/// Revisions of a workload, newest first
+pub async fn list_revisions(
+    client: Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+) -> Result<Vec<WorkloadRevision>, RolloutError> {
+    let mut revisions = match kind {
+        WorkloadKind::Deployment => owned_replica_sets(client, namespace, name)
+            .await?
+            .into_iter()
+            .filter_map(|rs| {
+                let spec = rs.spec.as_ref();
+                let replicas = (
+                    rs.status.as_ref().and_then(|s| s.ready_replicas).unwrap_or(0),
+                    spec.and_then(|s| s.replicas).unwrap_or(0),
+                );
+                Some(WorkloadRevision {
+                    revision: replica_set_revision(&rs)?,
+                    change_cause: rs.annotations().get(CHANGE_CAUSE_ANNOTATION).cloned(),
+                    created: rs.metadata.creation_timestamp.clone(),
+                    replicas: Some(replicas),
+                    template: spec.and_then(|s| s.template.clone()),
+                    name: rs.name_any(),
+                })
+            })
+            .collect::<Vec<_>>(),
+        WorkloadKind::StatefulSet | WorkloadKind::DaemonSet => {
+            owned_controller_revisions(client, kind, namespace, name)
+                .await?
+                .into_iter()
+                .map(|cr| WorkloadRevision {
+                    revision: cr.revision,
+                    change_cause: cr.annotations().get(CHANGE_CAUSE_ANNOTATION).cloned(),
+                    created: cr.metadata.creation_timestamp.clone(),
+                    replicas: None,
+                    template: controller_revision_template(&cr),
+                    name: cr.name_any(),
+                })
+                .collect()
+        }
+    };
+    revisions.sort_by_key(|r| std::cmp::Reverse(r.revision));
+    Ok(revisions)
+}
+
+/// Put the pod template of an earlier revision back, like `kubectl rollout undo --to-revision`
+pub async fn rollback_workload(
+    client: Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+    revision: i64,
+) -> Result<i64, RolloutError> {
+    match kind {
+        WorkloadKind::Deployment => {
+            let replica_set = owned_replica_sets(client.clone(), namespace, name)
+                .await?
+                .into_iter()
+                .find(|rs| replica_set_revision(rs) == Some(revision))
+                .ok_or(RolloutError::RevisionNotFound(revision))?;
+            let mut template = replica_set
+                .spec
+                .and_then(|spec| spec.template)
+                .ok_or_else(|| RolloutError::InvalidRevision(format!("ReplicaSet for revision {} has no template", revision)))?;
+            // The controller adds the hash itself; keeping it would pin the old ReplicaSet's selector
+            if let Some(labels) = template.metadata.as_mut().and_then(|m| m.labels.as_mut()) {
+                labels.remove("pod-template-hash");
+            }
+
+            // Replace rather than merge so fields removed since that revision are dropped too
+            let api: Api<Deployment> = Api::namespaced(client, namespace);
+            let mut deployment = api.get(name).await?;
+            if let Some(spec) = deployment.spec.as_mut() {
+                spec.template = template;
+            }
+            let replaced = api.replace(name, &PostParams::default(), &deployment).await?;
+            Ok(replaced.metadata.generation.unwrap_or(0))
+        }
+        WorkloadKind::StatefulSet | WorkloadKind::DaemonSet => {
+            let controller_revision = owned_controller_revisions(client.clone(), kind, namespace, name)
+                .await?
+                .into_iter()
+                .find(|cr| cr.revision == revision)
+                .ok_or(RolloutError::RevisionNotFound(revision))?;
+            // ControllerRevision data is already a strategic merge patch of the template
+            let data = controller_revision
+                .data
+                .ok_or_else(|| RolloutError::InvalidRevision(format!("Revision {} has no data", revision)))?;
+            if kind == WorkloadKind::StatefulSet {
+                strategic_patch::<StatefulSet>(client, namespace, name, &data.0).await
+            } else {
+                strategic_patch::<DaemonSet>(client, namespace, name, &data.0).await
+            }
+        }
+    }
+}
+
+async fn strategic_patch<K>(
+    client: Client,
+    namespace: &str,
+    name: &str,
+    patch: &serde_json::Value,
+) -> Result<i64, RolloutError>
+where
+    K: Resource<Scope = NamespaceResourceScope, DynamicType = ()> + Clone + DeserializeOwned + Debug,
+{
+    let api: Api<K> = Api::namespaced(client, namespace);
+    let patched = api.patch(name, &PatchParams::default(), &Patch::Strategic(patch)).await?;
+    Ok(patched.meta().generation.unwrap_or(0))
+}
+
+/// Pod template stored in a ControllerRevision, which keeps it as `{"spec": {"template": ...}}`
+fn controller_revision_template(revision: &ControllerRevision) -> Option<PodTemplateSpec> {
+    let template = revision.data.as_ref()?.0.get("spec")?.get("template")?.clone();
+    serde_json::from_value(template).ok()
+}
+
+fn replica_set_revision(replica_set: &ReplicaSet) -> Option<i64> {
+    replica_set.annotations().get(REVISION_ANNOTATION)?.parse().ok()
+}
+
+async fn owned_replica_sets(client: Client, namespace: &str, name: &str) -> Result<Vec<ReplicaSet>, RolloutError> {
+    let deployment: Deployment = Api::namespaced(client.clone(), namespace).get(name).await?;
+    let selector = deployment
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.selector.match_labels.as_ref());
+    let uid = deployment.metadata.uid.clone();
+
+    let replica_sets: Api<ReplicaSet> = Api::namespaced(client, namespace);
+    Ok(replica_sets
+        .list(&label_params(selector))
+        .await?
+        .items
+        .into_iter()
+        .filter(|rs| is_owned_by(rs, uid.as_deref()))
+        .collect())
+}
+
+async fn owned_controller_revisions(
+    client: Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+) -> Result<Vec<ControllerRevision>, RolloutError> {
+    let (selector, uid) = match kind {
+        WorkloadKind::StatefulSet => {
+            let statefulset: StatefulSet = Api::namespaced(client.clone(), namespace).get(name).await?;
+            (statefulset.spec.and_then(|spec| spec.selector.match_labels), statefulset.metadata.uid)
+        }
+        WorkloadKind::DaemonSet => {
+            let daemonset: DaemonSet = Api::namespaced(client.clone(), namespace).get(name).await?;
+            (daemonset.spec.and_then(|spec| spec.selector.match_labels), daemonset.metadata.uid)
+        }
+        WorkloadKind::Deployment => {
+            return Err(RolloutError::Unsupported(
+                "Deployments keep their history in ReplicaSets".to_string(),
+            ))
+        }
+    };
+
+    let revisions: Api<ControllerRevision> = Api::namespaced(client, namespace);
+    Ok(revisions
+        .list(&label_params(selector.as_ref()))
+        .await?
+        .items
+        .into_iter()
+        .filter(|cr| is_owned_by(cr, uid.as_deref()))
+        .collect())
+}
+
+fn label_params(match_labels: Option<&BTreeMap<String, String>>) -> ListParams {
+    let selector = match_labels
+        .map(|labels| {
+            labels
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .unwrap_or_default();
+    if selector.is_empty() {
+        ListParams::default()
+    } else {
+        ListParams::default().labels(&selector)
+    }
+}
+
+fn is_owned_by<K: Resource>(obj: &K, owner_uid: Option<&str>) -> bool {
+    let Some(owner_uid) = owner_uid else { return false };
+    obj.meta()
+        .owner_references
+        .iter()
+        .flatten()
+        .any(|owner| owner.uid == owner_uid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::serde_json::Value;
+
+    fn deployment(generation: i64, spec: Value, status: Value) -> Deployment {
+        let mut spec = spec;
+        spec["selector"] = json!({});
+        serde_json::from_value(json!({
+            "metadata": { "name": "web", "generation": generation },
+            "spec": spec,
+            "status": status,
+        }))
+        .unwrap()
+    }
+
+    fn statefulset(generation: i64, spec: Value, status: Value) -> StatefulSet {
+        let mut spec = spec;
+        spec["selector"] = json!({});
+        spec["serviceName"] = json!("db");
+        serde_json::from_value(json!({
+            "metadata": { "name": "db", "generation": generation },
+            "spec": spec,
+            "status": status,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_deployment_rollout_progress() {
+        let status = |updated: i32, available: i32, total: i32| {
+            json!({
+                "observedGeneration": 2,
+                "replicas": total,
+                "updatedReplicas": updated,
+                "availableReplicas": available,
+            })
+        };
+        let progress = |generation, spec, status| deployment_rollout_progress(&deployment(generation, spec, status));
+
+        let stale = progress(3, json!({ "replicas": 3 }), status(3, 3, 3));
+        assert!(!stale.done && !stale.has_converged(3));
+        assert_eq!(stale.message, "Waiting for deployment spec update to be observed");
+
+        let mut exceeded = status(1, 1, 3);
+        exceeded["conditions"] =
+            json!([{ "type": "Progressing", "status": "False", "reason": "ProgressDeadlineExceeded" }]);
+        let failed = progress(2, json!({ "replicas": 3 }), exceeded);
+        assert!(failed.failed && !failed.done);
+
+        let paused = progress(2, json!({ "replicas": 3, "paused": true }), status(1, 1, 3));
+        assert!(paused.done);
+        assert_eq!(paused.message, "Rollout paused");
+
+        let cases = [
+            (status(1, 1, 3), "1 of 3 new replicas have been updated"),
+            (status(3, 3, 4), "1 old replicas are pending termination"),
+            (status(3, 2, 3), "2 of 3 updated replicas are available"),
+        ];
+        for (status, message) in cases {
+            let waiting = progress(2, json!({ "replicas": 3 }), status);
+            assert!(!waiting.done && !waiting.failed);
+            assert_eq!(waiting.message, message);
+        }
+
+        let complete = progress(2, json!({ "replicas": 3 }), status(3, 3, 3));
+        assert!(complete.has_converged(2));
+        assert_eq!(complete.percent(), 100.0);
+    }
+
+    #[test]
+    fn test_statefulset_rollout_progress() {
+        let status = |ready: i32, updated: i32, current: &str, update: &str| {
+            json!({
+                "observedGeneration": 2,
+                "replicas": 3,
+                "readyReplicas": ready,
+                "updatedReplicas": updated,
+                "currentRevision": current,
+                "updateRevision": update,
+            })
+        };
+        let progress = |generation, spec, status| statefulset_rollout_progress(&statefulset(generation, spec, status));
+        let rolling = json!({ "replicas": 3 });
+
+        let stale = progress(3, rolling.clone(), status(3, 3, "db-1", "db-1"));
+        assert_eq!(stale.message, "Waiting for statefulset spec update to be observed");
+        assert!(!progress(1, rolling.clone(), json!({})).done);
+
+        let on_delete_spec = json!({ "replicas": 3, "updateStrategy": { "type": "OnDelete" } });
+        let on_delete = progress(2, on_delete_spec, status(1, 0, "db-1", "db-2"));
+        assert!(on_delete.done);
+
+        let unready = progress(2, rolling.clone(), status(1, 1, "db-1", "db-2"));
+        assert!(!unready.done);
+        assert_eq!(unready.message, "Waiting for 2 pods to be ready");
+
+        let partitioned = json!({
+            "replicas": 3,
+            "updateStrategy": { "type": "RollingUpdate", "rollingUpdate": { "partition": 2 } },
+        });
+        let partial = progress(2, partitioned.clone(), status(3, 0, "db-1", "db-2"));
+        assert!(!partial.done);
+        assert_eq!(partial.message, "Partitioned rollout: 0 of 1 new pods have been updated");
+        assert!(progress(2, partitioned, status(3, 1, "db-1", "db-2")).done);
+
+        let updating = progress(2, rolling.clone(), status(3, 2, "db-1", "db-2"));
+        assert!(!updating.done);
+        assert_eq!(updating.message, "Rolling update in progress: 2 of 3 pods at revision db-2");
+
+        let complete = progress(2, rolling, status(3, 3, "db-2", "db-2"));
+        assert!(complete.has_converged(2));
+        assert_eq!(complete.message, "Rolling update complete");
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use k8s_openapi::api::core::v1::{Container, EnvVar, PodTemplateSpec, Probe};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
+/// Part of a container a change belongs to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangeCategory {
+    Container,
+    Image,
+    Env,
+    Resources,
+    Probes,
+}
+
+impl fmt::Display for ChangeCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeCategory::Container => write!(f, "Container"),
+            ChangeCategory::Image => write!(f, "Image"),
+            ChangeCategory::Env => write!(f, "Env"),
+            ChangeCategory::Resources => write!(f, "Resources"),
+            ChangeCategory::Probes => write!(f, "Probes"),
+        }
+    }
+}
+
+/// A single field that differs between two pod templates.
+/// `before`/`after` are `None` when the field is absent on that side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateChange {
+    pub container: String,
+    pub category: ChangeCategory,
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Compare the images, env, resources and probes of every container in two pod templates
+pub fn diff_pod_templates(before: &PodTemplateSpec, after: &PodTemplateSpec) -> Vec<TemplateChange> {
+    let before = template_containers(before);
+    let after = template_containers(after);
+
+    // Keep the old template's container order and append containers that are new
+    let mut names: Vec<&String> = before.iter().map(|(name, _)| name).collect();
+    for (name, _) in &after {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let mut changes = Vec::new();
+    for name in names {
+        let old = before.iter().find(|(n, _)| n == name).map(|(_, c)| *c);
+        let new = after.iter().find(|(n, _)| n == name).map(|(_, c)| *c);
+        match (old, new) {
+            (Some(old), Some(new)) => diff_container(name, old, new, &mut changes),
+            (old, new) => changes.push(TemplateChange {
+                container: name.clone(),
+                category: ChangeCategory::Container,
+                field: if old.is_some() { "removed" } else { "added" }.to_string(),
+                before: old.map(|c| c.image.clone().unwrap_or_default()),
+                after: new.map(|c| c.image.clone().unwrap_or_default()),
+            }),
+        }
+    }
+    changes
+}
+
+fn template_containers(template: &PodTemplateSpec) -> Vec<(String, &Container)> {
+    let Some(spec) = template.spec.as_ref() else { return Vec::new() };
+    spec.init_containers
+        .iter()
+        .flatten()
+        .map(|c| (format!("{} (init)", c.name), c))
+        .chain(spec.containers.iter().map(|c| (c.name.clone(), c)))
+        .collect()
+}
+
+fn diff_container(name: &str, old: &Container, new: &Container, changes: &mut Vec<TemplateChange>) {
+    let mut push = |category, field: &str, before: Option<String>, after: Option<String>| {
+        if before != after {
+            changes.push(TemplateChange {
+                container: name.to_string(),
+                category,
+                field: field.to_string(),
+                before,
+                after,
+            });
+        }
+    };
+
+    push(ChangeCategory::Image, "image", old.image.clone(), new.image.clone());
+
+    let old_env = env_map(old);
+    let new_env = env_map(new);
+    for key in old_env.keys().chain(new_env.keys().filter(|k| !old_env.contains_key(*k))) {
+        push(ChangeCategory::Env, key, old_env.get(key).cloned(), new_env.get(key).cloned());
+    }
+
+    let old_resources = resource_map(old);
+    let new_resources = resource_map(new);
+    for key in old_resources.keys().chain(new_resources.keys().filter(|k| !old_resources.contains_key(*k))) {
+        push(ChangeCategory::Resources, key, old_resources.get(key).cloned(), new_resources.get(key).cloned());
+    }
+
+    let probes = [
+        ("liveness", &old.liveness_probe, &new.liveness_probe),
+        ("readiness", &old.readiness_probe, &new.readiness_probe),
+        ("startup", &old.startup_probe, &new.startup_probe),
+    ];
+    for (field, old_probe, new_probe) in probes {
+        if old_probe != new_probe {
+            push(ChangeCategory::Probes, field, old_probe.as_ref().map(describe_probe), new_probe.as_ref().map(describe_probe));
+        }
+    }
+}
+
+fn env_map(container: &Container) -> BTreeMap<String, String> {
+    container
+        .env
+        .iter()
+        .flatten()
+        .map(|var| (var.name.clone(), describe_env_var(var)))
+        .collect()
+}
+
+fn describe_env_var(var: &EnvVar) -> String {
+    if let Some(value) = &var.value {
+        return value.clone();
+    }
+    let Some(source) = &var.value_from else { return String::new() };
+    if let Some(secret) = &source.secret_key_ref {
+        format!("from secret {}/{}", secret.name, secret.key)
+    } else if let Some(config_map) = &source.config_map_key_ref {
+        format!("from configmap {}/{}", config_map.name, config_map.key)
+    } else if let Some(field) = &source.field_ref {
+        format!("from field {}", field.field_path)
+    } else if let Some(resource) = &source.resource_field_ref {
+        format!("from resource {}", resource.resource)
+    } else {
+        "from unknown source".to_string()
+    }
+}
+
+/// Requests and limits keyed as `requests.cpu`, `limits.memory`, ...
+fn resource_map(container: &Container) -> BTreeMap<String, String> {
+    let Some(resources) = container.resources.as_ref() else { return BTreeMap::new() };
+    let requests = resources.requests.iter().flatten().map(|(k, v)| (format!("requests.{}", k), v.0.clone()));
+    let limits = resources.limits.iter().flatten().map(|(k, v)| (format!("limits.{}", k), v.0.clone()));
+    requests.chain(limits).collect()
+}
+
+/// Every setting of a probe, so that any two probes that differ are described differently
+fn describe_probe(probe: &Probe) -> String {
+    let mut handler = if let Some(http) = &probe.http_get {
+        let mut handler = format!(
+            "{} {}:{}{}",
+            http.scheme.as_deref().unwrap_or("HTTP"),
+            http.host.as_deref().unwrap_or(""),
+            port_string(&http.port),
+            http.path.clone().unwrap_or_else(|| "/".to_string())
+        );
+        for header in http.http_headers.iter().flatten() {
+            handler.push_str(&format!(" {}={:?}", header.name, header.value));
+        }
+        handler
+    } else if let Some(tcp) = &probe.tcp_socket {
+        format!("tcp {}:{}", tcp.host.as_deref().unwrap_or(""), port_string(&tcp.port))
+    } else if let Some(exec) = &probe.exec {
+        // Quoted so that where one argument ends and the next starts is visible
+        format!("exec {:?}", exec.command.clone().unwrap_or_default())
+    } else if let Some(grpc) = &probe.grpc {
+        match &grpc.service {
+            Some(service) => format!("grpc :{} service={}", grpc.port, service),
+            None => format!("grpc :{}", grpc.port),
+        }
+    } else {
+        "no handler".to_string()
+    };
+    if let Some(grace) = probe.termination_grace_period_seconds {
+        handler.push_str(&format!(" grace={}s", grace));
+    }
+
+    format!(
+        "{} delay={}s period={}s timeout={}s success={} failure={}",
+        handler,
+        probe.initial_delay_seconds.unwrap_or(0),
+        probe.period_seconds.unwrap_or(10),
+        probe.timeout_seconds.unwrap_or(1),
+        probe.success_threshold.unwrap_or(1),
+        probe.failure_threshold.unwrap_or(3),
+    )
+}
+
+fn port_string(port: &IntOrString) -> String {
+    match port {
+        IntOrString::Int(p) => p.to_string(),
+        IntOrString::String(name) => name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::serde_json::{from_value, json};
+
+    fn template(container: k8s_openapi::serde_json::Value) -> PodTemplateSpec {
+        from_value(json!({ "spec": { "containers": [container] } })).unwrap()
+    }
+
+    fn fields(changes: &[TemplateChange]) -> Vec<(ChangeCategory, &str)> {
+        changes.iter().map(|c| (c.category, c.field.as_str())).collect()
+    }
+
+    #[test]
+    fn test_diff_pod_templates() {
+        let before = template(json!({
+            "name": "app",
+            "image": "nginx:1.25",
+            "env": [{ "name": "MODE", "value": "a" }, { "name": "OLD", "value": "x" }],
+            "resources": { "requests": { "cpu": "100m" }, "limits": { "memory": "128Mi" } },
+            "readinessProbe": { "httpGet": { "path": "/ready", "port": 8080 } },
+        }));
+        let after = template(json!({
+            "name": "app",
+            "image": "nginx:1.27",
+            "env": [
+                { "name": "MODE", "value": "b" },
+                { "name": "TOKEN", "valueFrom": { "secretKeyRef": { "name": "creds", "key": "token" } } },
+            ],
+            "resources": { "requests": { "cpu": "250m" }, "limits": { "memory": "128Mi" } },
+            "readinessProbe": { "httpGet": { "path": "/ready", "port": 8080 }, "periodSeconds": 5 },
+        }));
+
+        let changes = diff_pod_templates(&before, &after);
+        assert_eq!(
+            fields(&changes),
+            vec![
+                (ChangeCategory::Image, "image"),
+                (ChangeCategory::Env, "MODE"),
+                (ChangeCategory::Env, "OLD"),
+                (ChangeCategory::Env, "TOKEN"),
+                (ChangeCategory::Resources, "requests.cpu"),
+                (ChangeCategory::Probes, "readiness"),
+            ]
+        );
+        assert_eq!(changes[2].after, None);
+        assert_eq!(changes[3].after.as_deref(), Some("from secret creds/token"));
+        assert_eq!(changes[4].before.as_deref(), Some("100m"));
+        assert!(diff_pod_templates(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_diff_probe_details() {
+        let probe = |probe| template(json!({ "name": "app", "livenessProbe": probe }));
+
+        // Changes outside the handler's path and port still show up, with differing descriptions
+        let pairs = [
+            (
+                json!({ "httpGet": { "path": "/", "port": 80 } }),
+                json!({ "httpGet": { "path": "/", "port": 80, "httpHeaders": [{ "name": "Host", "value": "a" }] } }),
+            ),
+            (
+                json!({ "tcpSocket": { "port": 80 } }),
+                json!({ "tcpSocket": { "port": 80 }, "terminationGracePeriodSeconds": 5 }),
+            ),
+            (
+                json!({ "exec": { "command": ["sh", "-c", "test -f /ready"] } }),
+                json!({ "exec": { "command": ["sh", "-c", "test -f /healthy"] } }),
+            ),
+            (
+                json!({ "exec": { "command": ["check", "a b"] } }),
+                json!({ "exec": { "command": ["check", "a", "b"] } }),
+            ),
+        ];
+        for (old, new) in pairs {
+            let changes = diff_pod_templates(&probe(old), &probe(new));
+            assert_eq!(fields(&changes), vec![(ChangeCategory::Probes, "liveness")]);
+            assert_ne!(changes[0].before, changes[0].after);
+        }
+    }
+}
@@ -1,17 +1,14 @@
 use k8s_openapi::api::core::v1::{ConfigMap, PersistentVolumeClaim, Pod};
-use kube::{api::ListParams, Api, Client};
+
+use super::cache::ClusterCache;
+use crate::contexts::error::AppResult;
 
 /// Find unused ConfigMaps in the cluster
-pub async fn find_unused_configmaps(client: Client) -> Vec<(ConfigMap, String)> {
-    let pods: Api<Pod> = Api::all(client.clone());
-    let configmaps: Api<ConfigMap> = Api::all(client);
+pub async fn find_unused_configmaps(cache: &ClusterCache) -> AppResult<Vec<(ConfigMap, String)>> {
     let mut unused_configmaps = Vec::new();
 
     // Get all pods first
-    let pod_list = match pods.list(&ListParams::default()).await {
-        Ok(list) => list.items,
-        Err(_) => return Vec::new(),
-    };
+    let pod_list = cache.list::<Pod>().await?;
 
     // Build a set of used ConfigMaps for efficient lookup
     let mut used_configmaps = std::collections::HashSet::new();
@@ -58,51 +55,40 @@ pub async fn find_unused_configmaps(client: Client) -> Vec<(ConfigMap, String)>
     }
 
     // Get and check all configmaps
-    if let Ok(configmap_list) = configmaps.list(&ListParams::default()).await {
-        for configmap in configmap_list.items {
-            let name = configmap.metadata.name.clone().unwrap_or_default();
-            let namespace = configmap.metadata.namespace.clone().unwrap_or_default();
-
-            // Skip kube-root-ca.crt and system namespaces
-            if name == "kube-root-ca.crt"
-                || namespace == "kube-system"
-                || namespace == "kube-public"
-            {
-                continue;
-            }
+    for configmap in cache.list::<ConfigMap>().await? {
+        let name = configmap.metadata.name.clone().unwrap_or_default();
+        let namespace = configmap.metadata.namespace.clone().unwrap_or_default();
+
+        // Skip kube-root-ca.crt and system namespaces
+        if name == "kube-root-ca.crt"
+            || namespace == "kube-system"
+            || namespace == "kube-public"
+        {
+            continue;
+        }
 
-            let key = format!("{}:{}", namespace, name);
-            if !used_configmaps.contains(&key) {
-                unused_configmaps.push((
-                    configmap,
-                    format!(
-                        "ConfigMap '{}' in namespace '{}' is not mounted by any pods",
-                        name, namespace
-                    ),
-                ));
-            }
+        let key = format!("{}:{}", namespace, name);
+        if !used_configmaps.contains(&key) {
+            unused_configmaps.push((
+                configmap,
+                format!(
+                    "ConfigMap '{}' in namespace '{}' is not mounted by any pods",
+                    name, namespace
+                ),
+            ));
         }
     }
 
-    unused_configmaps
+    Ok(unused_configmaps)
 }
 
 /// Find unused PersistentVolumeClaims (PVCs) in the cluster
-pub async fn find_unused_pvcs(client: Client) -> Vec<(PersistentVolumeClaim, String)> {
-    let pvcs: Api<PersistentVolumeClaim> = Api::all(client.clone());
-    let pods: Api<Pod> = Api::all(client);
+pub async fn find_unused_pvcs(cache: &ClusterCache) -> AppResult<Vec<(PersistentVolumeClaim, String)>> {
     let mut unused_pvcs = Vec::new();
 
     // Get all resources
-    let pvc_list = match pvcs.list(&ListParams::default()).await {
-        Ok(list) => list.items,
-        Err(_) => return Vec::new(),
-    };
-
-    let pod_list = match pods.list(&ListParams::default()).await {
-        Ok(list) => list.items,
-        Err(_) => return Vec::new(),
-    };
+    let pvc_list = cache.list::<PersistentVolumeClaim>().await?;
+    let pod_list = cache.list::<Pod>().await?;
 
     // Build a set of used PVCs for efficient lookup
     let mut used_pvcs = std::collections::HashSet::new();
@@ -137,5 +123,5 @@ pub async fn find_unused_pvcs(client: Client) -> Vec<(PersistentVolumeClaim, Str
         }
     }
 
-    unused_pvcs
+    Ok(unused_pvcs)
 }
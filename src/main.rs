@@ -1,10 +1,10 @@
-use dioxus::prelude::*;
+use dioxus::{logger::tracing, prelude::*};
 use dioxus_desktop::{Config, WindowBuilder};
 use kube::Client;
 use views::{
     ConfigMaps, CreatePod, CronJobs, DaemonSets, Deployments, Home, Ingresses, Jobs, Namespaces, Navbar,
     Nodes, Pods, Pvcs, Secrets, Services, StatefulSets, CreateNamespace, CreateDeployment, CreateStatefulSet,
-    CreateDaemonSet, CreateCronJob, Insights
+    CreateDaemonSet, CreateCronJob, Insights, PodLogs, ApplyManifest, ApiResources
 };
 
 mod components;
@@ -13,7 +13,17 @@ mod k8s;
 mod views;
 mod utils;
 
-use contexts::{FilePathsContext, KubeconfigStorage, ClientReloadContext, create_client_from_path};
+use contexts::{
+    AccessContext, FilePathsContext, KubeconfigStorage, ClientReloadContext, PortForwardContext, PrometheusContext,
+    PrometheusSettings, context_key, create_client_from_path, kubeconfig_changes, kubeconfig_watch_paths,
+};
+use futures::StreamExt;
+use k8s::{
+    fetch_filesystems, fetch_usage, probe_health, review_cluster_access, review_namespaces, ClusterCache,
+    ClusterHealth, ConnectionStatus, MetricsHistory, Permissions, Prometheus, PrometheusEndpoint,
+    FILESYSTEM_SAMPLE_INTERVAL, METRICS_SAMPLE_INTERVAL,
+};
+use k8s_openapi::api::core::v1::Namespace;
 use utils::config;
 
 #[derive(Debug, Clone, Routable, PartialEq)]
@@ -30,10 +40,16 @@ enum Route {
         CreateNamespace {},
         #[route("/insights")]
         Insights {},
+        #[route("/apply")]
+        ApplyManifest {},
+        #[route("/resources")]
+        ApiResources {},
         #[route("/pods")]
         Pods {},
         #[route("/pods/create")]
         CreatePod {},
+        #[route("/pods/:namespace/:name/logs/:container")]
+        PodLogs { namespace: String, name: String, container: String },
         #[route("/deployments")]
         Deployments {},
         #[route("/deployments/create")]
@@ -81,31 +97,56 @@ fn main() {
 fn App() -> Element {
     // Signal to track the current kubeconfig path
     let current_kubeconfig_path = use_signal(|| config::DEFAULT_KUBECONFIG.to_string());
+    // Context inside that kubeconfig, `None` until one is picked
+    let current_kube_context = use_signal(|| None::<String>);
+    let mut kubeconfig_revision = use_signal(|| 0u32);
     
     // Create kubeconfig storage - ALWAYS at top level
-    let kubeconfig_storage = use_context_provider(|| KubeconfigStorage::default());
+    let kubeconfig_storage = use_context_provider(KubeconfigStorage::persisted);
     
     // ALWAYS provide client reload context - BEFORE any conditional logic
     use_context_provider(|| ClientReloadContext {
         current_path: current_kubeconfig_path,
+        revision: kubeconfig_revision,
+        current_context: current_kube_context,
     });
     
     // ALWAYS provide file paths context - BEFORE any conditional logic  
-    let kubeconfig_paths = use_signal(|| vec![config::DEFAULT_KUBECONFIG.to_string()]);
+    // Starts with the default kubeconfig followed by everything stored in the registry
+    let kubeconfig_paths = use_signal({
+        let storage = kubeconfig_storage.clone();
+        move || {
+            let mut paths = vec![config::DEFAULT_KUBECONFIG.to_string()];
+            paths.extend(storage.names().unwrap_or_default());
+            paths
+        }
+    });
     use_context_provider(|| FilePathsContext {
         kubeconfig_paths,
     });
 
+    // Port-forwards live at the top level so they keep running across route changes
+    let port_forwards = use_signal(Vec::new);
+    let port_forward_handles = use_signal(std::collections::HashMap::new);
+    let port_forward_next_id = use_signal(|| 1u64);
+    use_context_provider(|| PortForwardContext {
+        forwards: port_forwards,
+        handles: port_forward_handles,
+        next_id: port_forward_next_id,
+    });
+
     // Resource for managing the Kubernetes client based on the current path
     let client_resource = use_resource({
         let storage = kubeconfig_storage.clone();
         move || {
             let current_path = current_kubeconfig_path();
+            let current_context = current_kube_context();
+            let _ = kubeconfig_revision();
             println!("DEBUG: Client resource triggering with path: '{}'", current_path);
             let storage = storage.clone();
             async move { 
                 println!("DEBUG: About to call create_client_from_path with: '{}'", current_path);
-                let result = create_client_from_path(&current_path, &storage).await
+                let result = create_client_from_path(&current_path, current_context.as_deref(), &storage).await
                     .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>);
                 match &result {
                     Ok(_) => println!("DEBUG: create_client_from_path result: Ok(client)"),
@@ -116,8 +157,37 @@ fn App() -> Element {
         }
     });
 
+    // Watch the files behind the active kubeconfig and rebuild the client when they are rewritten,
+    // e.g. when a cloud CLI rotates credentials. Restarted after every reload to pick up moved files.
+    let mut kubeconfig_watch = use_signal(|| None::<Task>);
+    use_effect({
+        let storage = kubeconfig_storage.clone();
+        move || {
+            let current_path = current_kubeconfig_path();
+            let _ = kubeconfig_revision();
+            if let Some(task) = kubeconfig_watch.write().take() {
+                task.cancel();
+            }
+            let paths = kubeconfig_watch_paths(&current_path, &storage);
+            if paths.is_empty() {
+                return;
+            }
+            let interval = std::time::Duration::from_secs(config::KUBECONFIG_POLL_INTERVAL_SECS);
+            let task = spawn(async move {
+                let mut changes = Box::pin(kubeconfig_changes(paths, interval));
+                if changes.next().await.is_some() {
+                    tracing::info!("Kubeconfig {} changed on disk, reloading client", current_path);
+                    kubeconfig_revision += 1;
+                }
+            });
+            kubeconfig_watch.set(Some(task));
+        }
+    });
+
     // Create a reactive client signal that updates when the resource state changes
     let client_signal = use_signal(|| None::<Client>);
+    // Kubeconfig and context the current client was built from
+    let mut client_source = use_signal(|| None::<(String, Option<String>)>);
     
     // Update the client signal whenever the resource state changes
     use_effect({
@@ -125,17 +195,209 @@ fn App() -> Element {
         let client_resource = client_resource.clone();
         move || {
             let client_ref = client_resource.read();
+            let source = Some((current_kubeconfig_path.peek().clone(), current_kube_context.peek().clone()));
             let client_option: Option<Client> = match &*client_ref {
-                None => None, // Still loading
+                // Still loading; a reload of the same kubeconfig keeps the old client meanwhile
+                // so views keep their data instead of flashing empty
+                None if *client_source.peek() == source => return,
+                None => None,
                 Some(Err(_)) => None, // Error - no client available
                 Some(Ok(client)) => Some(client.clone()), // Success - client available
             };
+            client_source.set(client_option.as_ref().and(source));
             client_signal.set(client_option);
         }
     });
     
     use_context_provider(move || client_signal);
 
+    // Shared informer cache for the current cluster, rebuilt whenever the client changes
+    let mut cache_signal = use_signal(|| None::<ClusterCache>);
+    use_effect(move || {
+        cache_signal.set(client_signal().map(ClusterCache::new));
+    });
+    use_context_provider(move || cache_signal);
+
+    // Background health probe feeding the navbar status pill, retrying with backoff while failing
+    let mut cluster_health = use_signal(ClusterHealth::default);
+    let mut health_probe = use_signal(|| None::<Task>);
+    use_effect(move || {
+        let client = client_signal();
+        if let Some(task) = health_probe.write().take() {
+            task.cancel();
+        }
+        let Some(client) = client else {
+            cluster_health.set(ClusterHealth::default());
+            return;
+        };
+        let task = spawn(async move {
+            loop {
+                let result = probe_health(&client).await;
+                let was_unauthorized = cluster_health.peek().status == ConnectionStatus::Unauthorized;
+                if let Err((status, error)) = &result {
+                    tracing::warn!("Cluster health probe failed ({}): {}", status.label(), error);
+                }
+                let reauthorized = was_unauthorized && result.is_ok();
+                cluster_health.write().record(result);
+                if reauthorized {
+                    // Watchers retry through outages on their own, but requests that were
+                    // rejected may have left views and access reviews stale after credentials rotated
+                    tracing::info!("Credentials accepted again, reconnecting");
+                    kubeconfig_revision += 1;
+                    return;
+                }
+                let wait = cluster_health.peek().next_probe_in();
+                tokio::time::sleep(wait).await;
+            }
+        });
+        health_probe.set(Some(task));
+    });
+    use_context_provider(move || cluster_health);
+
+    // Sample usage in the background so views can chart it; metrics-server and the kubelets
+    // only report current values, so history starts when the client connects
+    let mut metrics_history = use_signal(MetricsHistory::default);
+    let mut metrics_sampler = use_signal(|| None::<Task>);
+    use_effect(move || {
+        let client = client_signal();
+        if let Some(task) = metrics_sampler.write().take() {
+            task.cancel();
+        }
+        metrics_history.set(MetricsHistory::default());
+        let Some(client) = client else { return };
+        let task = spawn(async move {
+            let mut failing = false;
+            let mut filesystems_read = None::<std::time::Instant>;
+            loop {
+                match fetch_usage(&client).await {
+                    Ok(usage) => {
+                        if usage.filesystems.is_some() {
+                            filesystems_read = Some(std::time::Instant::now());
+                        }
+                        metrics_history.write().record_usage(usage);
+                        failing = false;
+                    }
+                    Err(e) => {
+                        // Say so once rather than every interval while no source answers
+                        if !failing {
+                            tracing::warn!("Metrics sampling failed: {}", e);
+                            failing = true;
+                        }
+                        if metrics_history.peek().error() != Some(&e) {
+                            metrics_history.write().record_error(e);
+                        }
+                    }
+                }
+                // metrics-server has no filesystem usage, so that is read from the kubelets, less often
+                if filesystems_read.is_none_or(|at| at.elapsed() >= FILESYSTEM_SAMPLE_INTERVAL) {
+                    filesystems_read = Some(std::time::Instant::now());
+                    match fetch_filesystems(&client).await {
+                        Ok(filesystems) => metrics_history.write().record_filesystems(filesystems),
+                        Err(e) => tracing::debug!("No filesystem usage from the kubelets: {}", e),
+                    }
+                }
+                tokio::time::sleep(METRICS_SAMPLE_INTERVAL).await;
+            }
+        });
+        metrics_sampler.set(Some(task));
+    });
+    use_context_provider(move || metrics_history);
+
+    // Prometheus is optional and set per context: look up the connected context's endpoint,
+    // then build a client for it once the cluster client is ready
+    let mut prometheus_key = use_signal(|| None::<String>);
+    let mut prometheus_endpoint = use_signal(|| None::<PrometheusEndpoint>);
+    let mut prometheus_client = use_signal(|| None::<Prometheus>);
+    let prometheus_settings = use_context_provider(|| PrometheusContext {
+        settings: PrometheusSettings::persisted(),
+        key: prometheus_key,
+        endpoint: prometheus_endpoint,
+        client: prometheus_client,
+    })
+    .settings;
+    use_effect({
+        let storage = kubeconfig_storage.clone();
+        move || {
+            let key = client_source()
+                .and_then(|(path, context)| context_key(&path, context.as_deref(), &storage));
+            prometheus_endpoint.set(key.as_deref().and_then(|key| prometheus_settings.get(key)));
+            prometheus_key.set(key);
+        }
+    });
+    use_effect(move || {
+        let (Some(client), Some(endpoint)) = (client_signal(), prometheus_endpoint()) else {
+            prometheus_client.set(None);
+            return;
+        };
+        // Built in a task since a client for a plain URL starts background work
+        spawn(async move {
+            match Prometheus::connect(&endpoint, &client) {
+                Ok(prometheus) => prometheus_client.set(Some(prometheus)),
+                Err(e) => {
+                    tracing::warn!("Can't use Prometheus at {}: {}", endpoint, e);
+                    prometheus_client.set(None);
+                }
+            }
+        });
+    });
+
+    // Review what the user may do once connected. Cluster-wide checks come first; only if some
+    // are denied is each namespace reviewed, since a role binding may grant them there.
+    let mut permissions = use_signal(Permissions::default);
+    let mut default_namespace = use_signal(|| None::<String>);
+    let manual_namespaces = use_signal(Vec::<String>::new);
+    let access_context = use_context_provider(|| AccessContext {
+        permissions,
+        default_namespace,
+        manual_namespaces,
+    });
+    let mut access_review = use_signal(|| None::<Task>);
+    use_effect(move || {
+        let client = client_signal();
+        if let Some(task) = access_review.write().take() {
+            task.cancel();
+        }
+        permissions.set(Permissions::default());
+        default_namespace.set(client.as_ref().map(|client| client.default_namespace().to_string()));
+        let Some(client) = client else { return };
+        let task = spawn(async move {
+            match review_cluster_access(&client).await {
+                Ok(cluster) => permissions.write().cluster = cluster,
+                Err(e) => {
+                    tracing::warn!("Access review failed, assuming full access: {}", e);
+                    return;
+                }
+            }
+            if !permissions.peek().is_restricted() {
+                return;
+            }
+            let namespaces = match cache_signal.peek().clone() {
+                Some(cache) if access_context.can_list_namespaces() => cache
+                    .list::<Namespace>()
+                    .await
+                    .map(|items| items.into_iter().filter_map(|ns| ns.metadata.name).collect())
+                    .unwrap_or_default(),
+                _ => access_context.known_namespaces(),
+            };
+            record_namespace_reviews(permissions, client, namespaces).await;
+        });
+        access_review.set(Some(task));
+    });
+
+    // Review namespaces entered by hand as they are added
+    use_effect(move || {
+        let added = manual_namespaces();
+        let Some(client) = client_signal.peek().clone() else { return };
+        let pending: Vec<String> = {
+            let reviewed = permissions.peek();
+            added.into_iter().filter(|ns| !reviewed.namespaces.contains_key(ns)).collect()
+        };
+        if pending.is_empty() || !permissions.peek().is_restricted() {
+            return;
+        }
+        spawn(record_namespace_reviews(permissions, client, pending));
+    });
+
     // Read the current client resource state for conditional rendering
     let client_ref = client_resource.read();
     match &*client_ref {
@@ -149,7 +411,7 @@ fn App() -> Element {
         document::Link { rel: "stylesheet", href: MAIN_CSS }
 
         // Show loading or error banners based on client state
-        if let None = &*client_ref {
+        if client_ref.is_none() && client_signal.read().is_none() {
             // Loading banner
             div {
                 class: "loading-banner",
@@ -200,10 +462,23 @@ fn App() -> Element {
         }
         
         // Add top margin when banner is shown
-        if !matches!(&*client_ref, Some(Ok(_))) {
+        if !matches!(&*client_ref, Some(Ok(_))) && client_signal.read().is_none() {
             div { style: "margin-top: 6rem;" }
         }
 
         Router::<Route> {}
     }
 }
+
+/// Review each namespace, storing the rules as the reviews complete
+async fn record_namespace_reviews(mut permissions: Signal<Permissions>, client: Client, namespaces: Vec<String>) {
+    let mut reviews = Box::pin(review_namespaces(client, namespaces));
+    while let Some((namespace, rules)) = reviews.next().await {
+        match rules {
+            Ok(rules) => {
+                permissions.write().namespaces.insert(namespace, rules);
+            }
+            Err(e) => tracing::warn!("Access review for namespace {} failed: {}", namespace, e),
+        }
+    }
+}
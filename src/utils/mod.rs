@@ -5,6 +5,21 @@ pub mod config {
     
     /// Directory to store persistent kubeconfig files
     pub const KUBECONFIG_STORAGE_DIR: &str = ".kontour/kubeconfigs";
+
+    /// Index of stored kubeconfigs (name -> file path), loaded at startup
+    pub const KUBECONFIG_INDEX_FILE: &str = ".kontour/kubeconfigs.yaml";
+
+    /// Prometheus endpoint of each cluster context
+    pub const PROMETHEUS_SETTINGS_FILE: &str = ".kontour/prometheus.yaml";
+
+    /// Salt and passphrase check for encrypted kubeconfigs, next to the index file
+    pub const KUBECONFIG_VAULT_FILE: &str = "vault.yaml";
+
+    /// Extension of kubeconfigs encrypted at rest
+    pub const ENCRYPTED_KUBECONFIG_EXTENSION: &str = "enc";
+
+    /// How often the active kubeconfig is checked for changes, in seconds
+    pub const KUBECONFIG_POLL_INTERVAL_SECS: u64 = 2;
     
     /// Characters to replace in file names for safety
     pub const UNSAFE_FILENAME_CHARS: &[char] = &['/', '\\', ':'];
@@ -34,6 +49,32 @@ pub mod file_utils {
         Ok(storage_dir)
     }
     
+    /// Get the path of the kubeconfig index file, creating its directory if needed
+    pub fn get_kubeconfig_index_path() -> Result<PathBuf, std::io::Error> {
+        let home_dir = dirs::home_dir()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Home directory not found"))?;
+
+        let index_path = home_dir.join(KUBECONFIG_INDEX_FILE);
+        if let Some(parent) = index_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        Ok(index_path)
+    }
+
+    /// Get the path of the Prometheus settings file, creating its directory if needed
+    pub fn get_prometheus_settings_path() -> Result<PathBuf, std::io::Error> {
+        let home_dir = dirs::home_dir()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Home directory not found"))?;
+
+        let settings_path = home_dir.join(PROMETHEUS_SETTINGS_FILE);
+        if let Some(parent) = settings_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        Ok(settings_path)
+    }
+
     /// Save uploaded file content to persistent storage and return the file path
     pub fn save_kubeconfig_file(name: &str, content: &str) -> Result<String, std::io::Error> {
         let storage_dir = get_kubeconfig_storage_dir()?;
@@ -47,6 +88,17 @@ pub mod file_utils {
         Ok(file_path.to_string_lossy().to_string())
     }
     
+    /// Save encrypted kubeconfig data to persistent storage and return the file path
+    pub fn save_encrypted_kubeconfig_file(name: &str, data: &[u8]) -> Result<String, std::io::Error> {
+        let storage_dir = get_kubeconfig_storage_dir()?;
+        let filename = format!("{}.{}", sanitize_filename(name), ENCRYPTED_KUBECONFIG_EXTENSION);
+        let file_path = storage_dir.join(filename);
+
+        fs::write(&file_path, data)?;
+
+        Ok(file_path.to_string_lossy().to_string())
+    }
+
     /// Sanitize a filename by replacing unsafe characters
     pub fn sanitize_filename(name: &str) -> String {
         name.chars()
@@ -97,6 +149,424 @@ pub mod time_utils {
     }
 }
 
+/// Utility functions for rendering exec session output
+pub mod terminal_utils {
+    /// Maximum number of lines kept in the terminal scrollback
+    pub const MAX_TERMINAL_LINES: usize = 2000;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    enum EscapeState {
+        #[default]
+        None,
+        Escape,
+        Csi(String),
+        Osc,
+    }
+
+    /// Minimal terminal emulator: interprets carriage returns, backspaces and the
+    /// line-editing escape sequences shells use, and strips all other escape codes
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct TerminalBuffer {
+        lines: Vec<String>,
+        cursor: usize,
+        escape: EscapeState,
+        pending: Vec<u8>,
+    }
+
+    impl Default for TerminalBuffer {
+        fn default() -> Self {
+            Self {
+                lines: vec![String::new()],
+                cursor: 0,
+                escape: EscapeState::None,
+                pending: Vec::new(),
+            }
+        }
+    }
+
+    impl TerminalBuffer {
+        /// Feed raw output bytes, keeping incomplete UTF-8 sequences for the next call
+        pub fn feed(&mut self, bytes: &[u8]) {
+            self.pending.extend_from_slice(bytes);
+            let pending = std::mem::take(&mut self.pending);
+            let (text, rest) = match std::str::from_utf8(&pending) {
+                Ok(text) => (text.to_string(), Vec::new()),
+                Err(e) if e.error_len().is_none() => {
+                    let valid = e.valid_up_to();
+                    (String::from_utf8_lossy(&pending[..valid]).to_string(), pending[valid..].to_vec())
+                }
+                Err(_) => (String::from_utf8_lossy(&pending).to_string(), Vec::new()),
+            };
+            self.pending = rest;
+            for c in text.chars() {
+                self.feed_char(c);
+            }
+        }
+
+        fn feed_char(&mut self, c: char) {
+            match std::mem::take(&mut self.escape) {
+                EscapeState::Escape => {
+                    self.escape = match c {
+                        '[' => EscapeState::Csi(String::new()),
+                        ']' => EscapeState::Osc,
+                        _ => EscapeState::None,
+                    };
+                }
+                EscapeState::Csi(mut params) => {
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        self.apply_csi(&params, c);
+                    } else {
+                        params.push(c);
+                        self.escape = EscapeState::Csi(params);
+                    }
+                }
+                EscapeState::Osc => {
+                    // OSC sequences (window titles) end with BEL or ESC \
+                    if c == '\u{1b}' {
+                        self.escape = EscapeState::Escape;
+                    } else if c != '\u{07}' {
+                        self.escape = EscapeState::Osc;
+                    }
+                }
+                EscapeState::None => match c {
+                    '\u{1b}' => self.escape = EscapeState::Escape,
+                    '\r' => self.cursor = 0,
+                    '\n' => self.new_line(),
+                    '\u{08}' => self.cursor = self.cursor.saturating_sub(1),
+                    '\t' => {
+                        let spaces = 8 - self.cursor % 8;
+                        for _ in 0..spaces {
+                            self.put(' ');
+                        }
+                    }
+                    c if c.is_control() => {}
+                    c => self.put(c),
+                },
+            }
+        }
+
+        fn apply_csi(&mut self, params: &str, command: char) {
+            let line = self.lines.last_mut().expect("terminal always has a line");
+            match command {
+                // Erase in line: 0 (the default) to the end, 1 to the start including the
+                // cursor, 2 the whole line. The cursor stays put either way.
+                'K' => match params {
+                    "1" => {
+                        let rest: String = line.chars().skip(self.cursor + 1).collect();
+                        let erased = (self.cursor + 1).min(line.chars().count());
+                        *line = " ".repeat(erased) + &rest;
+                    }
+                    "2" => line.clear(),
+                    _ => {
+                        let keep: String = line.chars().take(self.cursor).collect();
+                        *line = keep;
+                    }
+                },
+                'J' if params == "2" || params == "3" => {
+                    self.lines = vec![String::new()];
+                    self.cursor = 0;
+                }
+                'C' => self.cursor += params.parse::<usize>().unwrap_or(1),
+                'D' => self.cursor = self.cursor.saturating_sub(params.parse::<usize>().unwrap_or(1)),
+                _ => {}
+            }
+        }
+
+        fn put(&mut self, c: char) {
+            let line = self.lines.last_mut().expect("terminal always has a line");
+            let mut chars: Vec<char> = line.chars().collect();
+            if self.cursor < chars.len() {
+                chars[self.cursor] = c;
+            } else {
+                chars.resize(self.cursor, ' ');
+                chars.push(c);
+            }
+            *line = chars.into_iter().collect();
+            self.cursor += 1;
+        }
+
+        fn new_line(&mut self) {
+            self.lines.push(String::new());
+            self.cursor = 0;
+            if self.lines.len() > MAX_TERMINAL_LINES {
+                let overflow = self.lines.len() - MAX_TERMINAL_LINES;
+                self.lines.drain(..overflow);
+            }
+        }
+
+        /// Append a line of out-of-band text, e.g. a status message
+        pub fn push_message(&mut self, message: &str) {
+            if !self.lines.last().map(|l| l.is_empty()).unwrap_or(true) {
+                self.new_line();
+            }
+            for c in message.chars() {
+                self.put(c);
+            }
+            self.new_line();
+        }
+
+        pub fn text(&self) -> String {
+            self.lines.join("\n")
+        }
+    }
+}
+
+/// Utility functions for comparing text
+pub mod diff_utils {
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum DiffLine {
+        Same(String),
+        Added(String),
+        Removed(String),
+    }
+
+    impl DiffLine {
+        pub fn is_change(&self) -> bool {
+            !matches!(self, DiffLine::Same(_))
+        }
+    }
+
+    /// Largest LCS table `diff_lines` builds, in cells: 16 MB of counters
+    const MAX_DIFF_CELLS: usize = 4_000_000;
+
+    /// Line-by-line diff based on the longest common subsequence, removals before additions.
+    /// Lines shared at the start and end are matched up front; if what's left between them is
+    /// still too large to compare, all of it is shown as removed and then added.
+    pub fn diff_lines(before: &str, after: &str) -> Vec<DiffLine> {
+        let old: Vec<&str> = before.lines().collect();
+        let new: Vec<&str> = after.lines().collect();
+
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let same = |lines: &[&str]| lines.iter().map(|line| DiffLine::Same(line.to_string())).collect::<Vec<_>>();
+
+        let mut lines = same(&old[..prefix]);
+        let old_middle = &old[prefix..old.len() - suffix];
+        let new_middle = &new[prefix..new.len() - suffix];
+        if (old_middle.len() + 1).saturating_mul(new_middle.len() + 1) > MAX_DIFF_CELLS {
+            lines.extend(old_middle.iter().map(|line| DiffLine::Removed(line.to_string())));
+            lines.extend(new_middle.iter().map(|line| DiffLine::Added(line.to_string())));
+        } else {
+            lines.extend(lcs_diff(old_middle, new_middle));
+        }
+        lines.extend(same(&old[old.len() - suffix..]));
+        lines
+    }
+
+    fn lcs_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+        // common[i][j] = length of the LCS of old[i..] and new[j..]
+        let mut common = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                common[i][j] = if old[i] == new[j] {
+                    common[i + 1][j + 1] + 1
+                } else {
+                    common[i + 1][j].max(common[i][j + 1])
+                };
+            }
+        }
+
+        let mut lines = Vec::with_capacity(old.len().max(new.len()));
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() && j < new.len() {
+            if old[i] == new[j] {
+                lines.push(DiffLine::Same(old[i].to_string()));
+                i += 1;
+                j += 1;
+            } else if common[i + 1][j] >= common[i][j + 1] {
+                lines.push(DiffLine::Removed(old[i].to_string()));
+                i += 1;
+            } else {
+                lines.push(DiffLine::Added(new[j].to_string()));
+                j += 1;
+            }
+        }
+        lines.extend(old[i..].iter().map(|line| DiffLine::Removed(line.to_string())));
+        lines.extend(new[j..].iter().map(|line| DiffLine::Added(line.to_string())));
+        lines
+    }
+}
+
+/// Evaluation of the JSONPath subset CRDs use in `additionalPrinterColumns`
+pub mod json_path {
+    use k8s_openapi::serde_json::Value;
+
+    /// Evaluate paths such as `.spec.replicas`, `.status.containers[0].ready`, `.items[*].name`
+    /// and `.status.conditions[?(@.type=="Ready")].status`. Returns every match; unsupported
+    /// syntax or missing fields simply match nothing.
+    pub fn evaluate<'a>(value: &'a Value, path: &str) -> Vec<&'a Value> {
+        let path = path.trim().trim_start_matches('{').trim_end_matches('}');
+        let mut current = vec![value];
+        let mut rest = path.strip_prefix('$').unwrap_or(path);
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('.') {
+                let end = after.find(['.', '[']).unwrap_or(after.len());
+                let field = &after[..end];
+                rest = &after[end..];
+                if field.is_empty() {
+                    continue;
+                }
+                current = current.into_iter().filter_map(|v| v.get(field)).collect();
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let Some(end) = after.find(']') else { return Vec::new() };
+                let selector = &after[..end];
+                rest = &after[end + 1..];
+                current = current.into_iter().flat_map(|v| select(v, selector)).collect();
+            } else {
+                return Vec::new();
+            }
+        }
+        current
+    }
+
+    /// Render matches the way kubectl prints them: strings bare, several matches comma-separated
+    pub fn evaluate_to_string(value: &Value, path: &str) -> Option<String> {
+        let matches = evaluate(value, path);
+        if matches.is_empty() {
+            return None;
+        }
+        let rendered: Vec<String> = matches
+            .into_iter()
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect();
+        Some(rendered.join(","))
+    }
+
+    fn select<'a>(value: &'a Value, selector: &str) -> Vec<&'a Value> {
+        if selector == "*" {
+            return match value {
+                Value::Array(items) => items.iter().collect(),
+                Value::Object(map) => map.values().collect(),
+                _ => Vec::new(),
+            };
+        }
+        if let Ok(index) = selector.parse::<usize>() {
+            return value.get(index).into_iter().collect();
+        }
+        if let Some(key) = selector.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+            return value.get(key).into_iter().collect();
+        }
+        // Filter expression: ?(@.field=="value")
+        let Some(filter) = selector.strip_prefix("?(@.").and_then(|s| s.strip_suffix(')')) else {
+            return Vec::new();
+        };
+        let Some((field, expected)) = filter.split_once("==") else { return Vec::new() };
+        let expected = expected.trim().trim_matches(|c| c == '"' || c == '\'');
+        let field = field.trim();
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .filter(|item| match item.get(field) {
+                    Some(Value::String(s)) => s == expected,
+                    Some(Value::Bool(b)) => expected.parse() == Ok(*b),
+                    Some(Value::Number(n)) => expected.parse::<f64>().ok() == n.as_f64(),
+                    _ => false,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Passphrase-based encryption for kubeconfigs stored at rest
+pub mod crypto_utils {
+    use argon2::{Algorithm, Argon2, Params, Version};
+    use chacha20poly1305::{
+        aead::{Aead, KeyInit},
+        Key, XChaCha20Poly1305, XNonce,
+    };
+    use zeroize::Zeroizing;
+
+    /// Prefix identifying encrypted files, followed by the nonce and the ciphertext
+    pub const ENCRYPTED_MAGIC: &[u8] = b"KONTOUR-ENC-V1";
+    const NONCE_LEN: usize = 24;
+    pub const SALT_LEN: usize = 16;
+
+    /// A 256-bit key derived from the user's passphrase, wiped from memory on drop
+    #[derive(Clone)]
+    pub struct VaultKey(Zeroizing<[u8; 32]>);
+
+    /// Argon2id cost parameters, stored alongside the salt so they can be raised later
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct KdfParams {
+        pub memory_kib: u32,
+        pub iterations: u32,
+        pub parallelism: u32,
+    }
+
+    impl Default for KdfParams {
+        fn default() -> Self {
+            // OWASP's recommended minimum for Argon2id
+            Self {
+                memory_kib: 19 * 1024,
+                iterations: 2,
+                parallelism: 1,
+            }
+        }
+    }
+
+    /// Fill a buffer from the operating system's random number generator
+    pub fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+        let mut bytes = [0u8; N];
+        getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate random bytes: {}", e))?;
+        Ok(bytes)
+    }
+
+    /// Derive the encryption key for a passphrase with Argon2id
+    pub fn derive_key(passphrase: &str, salt: &[u8], params: KdfParams) -> Result<VaultKey, String> {
+        let params = Params::new(params.memory_kib, params.iterations, params.parallelism, Some(32))
+            .map_err(|e| format!("Invalid key derivation parameters: {}", e))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key[..])
+            .map_err(|e| format!("Key derivation failed: {}", e))?;
+        Ok(VaultKey(key))
+    }
+
+    /// Whether the data was produced by `encrypt`
+    pub fn is_encrypted(data: &[u8]) -> bool {
+        data.starts_with(ENCRYPTED_MAGIC)
+    }
+
+    /// Encrypt and authenticate data with XChaCha20-Poly1305 under a fresh random nonce
+    pub fn encrypt(key: &VaultKey, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&key.0[..]));
+        let nonce = random_bytes::<NONCE_LEN>()?;
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce), plaintext)
+            .map_err(|_| "Encryption failed".to_string())?;
+
+        let mut data = Vec::with_capacity(ENCRYPTED_MAGIC.len() + NONCE_LEN + ciphertext.len());
+        data.extend_from_slice(ENCRYPTED_MAGIC);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        Ok(data)
+    }
+
+    /// Decrypt data produced by `encrypt`, failing on a wrong key or any tampering
+    pub fn decrypt(key: &VaultKey, data: &[u8]) -> Result<Zeroizing<Vec<u8>>, String> {
+        let body = data
+            .strip_prefix(ENCRYPTED_MAGIC)
+            .filter(|body| body.len() > NONCE_LEN)
+            .ok_or_else(|| "Not an encrypted kubeconfig".to_string())?;
+        let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&key.0[..]));
+        cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map(Zeroizing::new)
+            .map_err(|_| "Wrong passphrase or corrupted file".to_string())
+    }
+}
+
 // Re-export for backwards compatibility
 pub use time_utils::{calculate_age_from_time};
 
@@ -131,6 +601,35 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_encrypt_roundtrip() {
+        use super::crypto_utils::*;
+
+        // Cheap parameters keep the test fast; the real ones only change the cost
+        let params = KdfParams { memory_kib: 1024, iterations: 1, parallelism: 1 };
+        let salt = random_bytes::<SALT_LEN>().unwrap();
+        let key = derive_key("correct horse", &salt, params).unwrap();
+        let plaintext = b"apiVersion: v1\nkind: Config\nusers:\n- name: admin\n  user:\n    token: secret";
+
+        let encrypted = encrypt(&key, plaintext).unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_encrypted(plaintext));
+        assert!(!encrypted.windows(6).any(|window| window == b"secret"));
+        assert_eq!(decrypt(&key, &encrypted).unwrap().as_slice(), plaintext);
+
+        // Same passphrase and salt give the same key, anything else fails to decrypt
+        let again = derive_key("correct horse", &salt, params).unwrap();
+        assert_eq!(decrypt(&again, &encrypted).unwrap().as_slice(), plaintext);
+        let wrong = derive_key("battery staple", &salt, params).unwrap();
+        assert!(decrypt(&wrong, &encrypted).is_err());
+
+        let mut tampered = encrypted.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(decrypt(&key, &tampered).is_err());
+        assert!(decrypt(&key, plaintext).is_err());
+    }
+
     #[test]
     fn test_calculate_age() {
         use super::time_utils::calculate_age;
@@ -145,4 +644,89 @@ mod tests {
         let invalid_age = calculate_age("invalid-timestamp");
         assert_eq!(invalid_age, "unknown");
     }
+
+    #[test]
+    fn test_terminal_buffer() {
+        use super::terminal_utils::TerminalBuffer;
+
+        let mut terminal = TerminalBuffer::default();
+        terminal.feed(b"\x1b]0;root@pod\x07\x1b[01;32mroot\x1b[0m# ls\r\n");
+        terminal.feed(b"bin  etc\r\n# ech\x08\x1b[K");
+        assert_eq!(terminal.text(), "root# ls\nbin  etc\n# ec");
+
+        // A multi-byte character split across two reads
+        let bytes = "é".as_bytes();
+        terminal.feed(&bytes[..1]);
+        terminal.feed(&bytes[1..]);
+        assert_eq!(terminal.text(), "root# ls\nbin  etc\n# ecé");
+
+        // Progress redraws erase the whole line before writing over it
+        terminal.feed(b"\r\n 10%\r\x1b[2K100%");
+        assert_eq!(terminal.text(), "root# ls\nbin  etc\n# ecé\n100%");
+        terminal.feed(b"\x08\x08\x1b[1K");
+        assert_eq!(terminal.text(), "root# ls\nbin  etc\n# ecé\n   %");
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        use super::diff_utils::{diff_lines, DiffLine};
+
+        let before = "replicas: 1\nimage: nginx:1.25\nport: 80";
+        let after = "replicas: 1\nimage: nginx:1.27\nport: 80\npaused: true";
+        assert_eq!(
+            diff_lines(before, after),
+            vec![
+                DiffLine::Same("replicas: 1".to_string()),
+                DiffLine::Removed("image: nginx:1.25".to_string()),
+                DiffLine::Added("image: nginx:1.27".to_string()),
+                DiffLine::Same("port: 80".to_string()),
+                DiffLine::Added("paused: true".to_string()),
+            ]
+        );
+        assert!(!diff_lines(before, before).iter().any(DiffLine::is_change));
+
+        // A large object with one change is matched around it without a full table
+        let lines: Vec<String> = (0..20_000).map(|i| format!("line {}", i)).collect();
+        let before = lines.join("\n");
+        let after = before.replace("line 10000\n", "line 10000 changed\n");
+        let changes: Vec<_> = diff_lines(&before, &after).into_iter().filter(DiffLine::is_change).collect();
+        assert_eq!(
+            changes,
+            vec![
+                DiffLine::Removed("line 10000".to_string()),
+                DiffLine::Added("line 10000 changed".to_string()),
+            ]
+        );
+
+        // Too much changed in between to compare: all of it is replaced
+        let after: Vec<String> = (0..20_000).map(|i| format!("line {}", i * 2)).collect();
+        let diff = diff_lines(&before, &after.join("\n"));
+        assert_eq!(diff[0], DiffLine::Same("line 0".to_string()));
+        assert_eq!(diff.iter().filter(|line| matches!(line, DiffLine::Removed(_))).count(), 19_999);
+        assert_eq!(diff.iter().filter(|line| matches!(line, DiffLine::Added(_))).count(), 19_999);
+    }
+
+    #[test]
+    fn test_json_path() {
+        use super::json_path::evaluate_to_string;
+        use k8s_openapi::serde_json::json;
+
+        let object = json!({
+            "spec": { "replicas": 3, "dnsNames": ["a.example.com", "b.example.com"] },
+            "status": {
+                "conditions": [
+                    { "type": "Issuing", "status": "False" },
+                    { "type": "Ready", "status": "True", "message": "Certificate is up to date" }
+                ]
+            }
+        });
+        assert_eq!(evaluate_to_string(&object, ".spec.replicas"), Some("3".to_string()));
+        assert_eq!(evaluate_to_string(&object, ".spec.dnsNames[1]"), Some("b.example.com".to_string()));
+        assert_eq!(evaluate_to_string(&object, ".spec.dnsNames[*]"), Some("a.example.com,b.example.com".to_string()));
+        assert_eq!(
+            evaluate_to_string(&object, r#".status.conditions[?(@.type=="Ready")].status"#),
+            Some("True".to_string())
+        );
+        assert_eq!(evaluate_to_string(&object, ".status.missing"), None);
+    }
 }
@@ -0,0 +1,238 @@
+use dioxus::{logger::tracing, prelude::*};
+use futures::StreamExt;
+use kube::{api::DynamicObject, Client, ResourceExt};
+
+use crate::components::{DynamicObjectRow, EmptyState, InlineError, NamespaceSelector, SearchInput};
+use crate::contexts::error::AppError;
+use crate::k8s::{discover_api_kinds, dynamic_snapshots, printer_columns, ApiGroupKinds, ApiKind, PrinterColumn};
+
+const API_RESOURCES_CSS: Asset = asset!("/assets/styling/api_resources.css");
+
+#[component]
+pub fn ApiResources() -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+
+    let mut groups = use_signal(Vec::<ApiGroupKinds>::new);
+    let mut is_discovering = use_signal(|| true);
+    let mut discovery_error = use_signal(|| None::<AppError>);
+    let mut kind_filter = use_signal(String::new);
+    let mut rediscover = use_signal(|| 0u32);
+
+    let mut selected_kind = use_signal(|| None::<ApiKind>);
+    let mut selected_namespace = use_signal(|| "All".to_string());
+    let mut search_query = use_signal(String::new);
+    let mut columns = use_signal(Vec::<PrinterColumn>::new);
+    let mut objects = use_signal(Vec::<DynamicObject>::new);
+    let mut is_loading = use_signal(|| false);
+    let mut watch_error = use_signal(|| None::<AppError>);
+    let mut watch_task = use_signal(|| None::<Task>);
+
+    // Discover every group and kind the cluster serves
+    use_effect(move || {
+        let _ = rediscover();
+        let Some(client) = client_signal() else { return };
+        is_discovering.set(true);
+        spawn(async move {
+            match discover_api_kinds(client).await {
+                Ok(found) => {
+                    groups.set(found);
+                    discovery_error.set(None);
+                }
+                Err(e) => discovery_error.set(Some(e)),
+            }
+            is_discovering.set(false);
+        });
+    });
+
+    // Watch the selected kind, restarting whenever the kind or namespace changes
+    use_effect(move || {
+        let kind = selected_kind();
+        let namespace = selected_namespace();
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+        objects.set(Vec::new());
+        columns.set(Vec::new());
+        watch_error.set(None);
+        is_loading.set(false);
+        let (Some(client), Some(kind)) = (client_signal(), kind) else { return };
+
+        let namespace = (kind.namespaced && namespace != "All").then_some(namespace);
+        is_loading.set(true);
+        let task = spawn(async move {
+            columns.set(printer_columns(client.clone(), &kind.resource).await);
+            let mut snapshots = Box::pin(dynamic_snapshots(client, kind.resource.clone(), namespace));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        objects.set(items);
+                        watch_error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch {}: {}", kind.resource.plural, e);
+                        watch_error.set(Some(e));
+                    }
+                }
+                is_loading.set(false);
+            }
+        });
+        watch_task.set(Some(task));
+    });
+
+    let filter = kind_filter.read().to_lowercase();
+    let visible_groups: Vec<ApiGroupKinds> = groups
+        .read()
+        .iter()
+        .map(|group| ApiGroupKinds {
+            name: group.name.clone(),
+            kinds: group
+                .kinds
+                .iter()
+                .filter(|kind| {
+                    filter.is_empty()
+                        || kind.resource.kind.to_lowercase().contains(&filter)
+                        || group.name.contains(&filter)
+                })
+                .cloned()
+                .collect(),
+        })
+        .filter(|group| !group.kinds.is_empty())
+        .collect();
+
+    let query = search_query.read().to_lowercase();
+    let visible_objects: Vec<DynamicObject> = objects
+        .read()
+        .iter()
+        .filter(|object| query.is_empty() || object.name_any().to_lowercase().contains(&query))
+        .cloned()
+        .collect();
+    let kind = selected_kind();
+    let show_namespace = kind.as_ref().is_some_and(|k| k.namespaced) && selected_namespace() == "All";
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: API_RESOURCES_CSS }
+        div { class: "api-resources-container",
+            div { class: "api-kinds-panel",
+                div { class: "api-kinds-header",
+                    h2 { "API Resources" }
+                    button {
+                        class: "btn-icon",
+                        title: "Rediscover",
+                        disabled: is_discovering(),
+                        onclick: move |_| rediscover += 1,
+                        "🔄"
+                    }
+                }
+                input {
+                    class: "api-kinds-filter",
+                    r#type: "text",
+                    placeholder: "Filter kinds...",
+                    value: "{kind_filter}",
+                    oninput: move |evt| kind_filter.set(evt.value()),
+                }
+                if let Some(error) = discovery_error() {
+                    InlineError { error, on_retry: move |_| rediscover += 1 }
+                }
+                if is_discovering() && visible_groups.is_empty() {
+                    div { class: "api-hint", "Discovering API groups..." }
+                }
+                div { class: "api-kinds-list",
+                    {visible_groups.into_iter().map(|group| rsx! {
+                        div { key: "{group.name}", class: "api-group",
+                            div { class: "api-group-name", "{group.name}" }
+                            {group.kinds.into_iter().map(|api_kind| {
+                                let is_selected = selected_kind.read().as_ref() == Some(&api_kind);
+                                let key = format!("{}/{}", api_kind.api_version(), api_kind.resource.plural);
+                                let label = api_kind.resource.kind.clone();
+                                let version = api_kind.resource.version.clone();
+                                rsx! {
+                                    div {
+                                        key: "{key}",
+                                        class: if is_selected { "api-kind selected" } else { "api-kind" },
+                                        title: "{key}",
+                                        onclick: move |_| {
+                                            search_query.set(String::new());
+                                            selected_kind.set(Some(api_kind.clone()));
+                                        },
+                                        span { "{label}" }
+                                        span { class: "api-kind-version", "{version}" }
+                                    }
+                                }
+                            })}
+                        }
+                    })}
+                }
+            }
+
+            div { class: "api-objects-panel",
+                if let Some(kind) = kind {
+                    div { class: "api-objects-header",
+                        div { class: "header-left",
+                            h1 { "{kind.resource.kind}" }
+                            span { class: "api-objects-version", "{kind.api_version()}" }
+                            div { class: "header-controls",
+                                SearchInput {
+                                    query: search_query(),
+                                    on_change: move |q| search_query.set(q)
+                                }
+                                if kind.namespaced {
+                                    NamespaceSelector {
+                                        selected_namespace: selected_namespace(),
+                                        on_change: move |ns| selected_namespace.set(ns)
+                                    }
+                                }
+                                span { class: "api-objects-count", "{visible_objects.len()} {kind.resource.plural}" }
+                            }
+                        }
+                    }
+
+                    if let Some(error) = watch_error() {
+                        InlineError { error }
+                    } else if is_loading() {
+                        div { class: "api-hint", "Loading {kind.resource.plural}..." }
+                    } else if visible_objects.is_empty() {
+                        EmptyState { message: "No {kind.resource.plural} found" }
+                    } else {
+                        table { class: "api-objects-table",
+                            thead {
+                                tr {
+                                    th { "Name" }
+                                    if show_namespace {
+                                        th { "Namespace" }
+                                    }
+                                    {columns.read().iter().map(|column| rsx! {
+                                        th {
+                                            key: "{column.name}",
+                                            title: column.description.clone().unwrap_or_default(),
+                                            "{column.name}"
+                                        }
+                                    })}
+                                    th { "Ready" }
+                                    th { "Age" }
+                                }
+                            }
+                            tbody {
+                                {visible_objects.into_iter().map(|object| {
+                                    let key = format!("{}/{}", object.namespace().unwrap_or_default(), object.name_any());
+                                    rsx! {
+                                        DynamicObjectRow {
+                                            key: "{key}",
+                                            object,
+                                            kind: kind.clone(),
+                                            columns: columns(),
+                                            show_namespace,
+                                        }
+                                    }
+                                })}
+                            }
+                        }
+                    }
+                } else {
+                    div { class: "api-hint api-hint-centered",
+                        "Pick a kind on the left to browse it, including custom resources from CRDs"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use dioxus::prelude::*;
+use kube::Client;
+
+use crate::components::DryRunPreview;
+use crate::k8s::{apply_manifest_object, parse_manifest, sort_for_apply, ApplyOutcome, KindResolver, ManifestResult};
+
+const CREATE_FORMS_CSS: Asset = asset!("/assets/styling/create_forms.css");
+
+#[component]
+pub fn ApplyManifest() -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+    let mut manifest = use_signal(String::new);
+    let mut default_namespace = use_signal(|| "default".to_string());
+    let mut force = use_signal(|| false);
+    let mut results = use_signal(Vec::<ManifestResult>::new);
+    let mut total = use_signal(|| 0usize);
+    let mut error = use_signal(String::new);
+    let mut is_applying = use_signal(|| false);
+    let mut is_dry_run = use_signal(|| false);
+    // Result row whose dry-run preview is open
+    let mut expanded = use_signal(|| None::<usize>);
+
+    let load_file = move |evt: Event<FormData>| {
+        spawn(async move {
+            let Some(file_engine) = evt.files() else { return };
+            let mut contents = Vec::new();
+            for file_name in file_engine.files() {
+                match file_engine.read_file_to_string(&file_name).await {
+                    Some(text) => contents.push(text),
+                    None => error.set(format!("Failed to read {}", file_name)),
+                }
+            }
+            if !contents.is_empty() {
+                manifest.set(contents.join("\n---\n"));
+            }
+        });
+    };
+
+    let mut apply_manifest = move |dry_run: bool| {
+        error.set(String::new());
+        results.set(Vec::new());
+        expanded.set(None);
+
+        let Some(client) = client_signal.read().clone() else {
+            error.set("No Kubernetes connection available".to_string());
+            return;
+        };
+        let mut objects = match parse_manifest(&manifest.read()) {
+            Ok(objects) if objects.is_empty() => {
+                error.set("The manifest does not contain any objects".to_string());
+                return;
+            }
+            Ok(objects) => objects,
+            Err(e) => {
+                error.set(format!("Failed to parse manifest: {}", e));
+                return;
+            }
+        };
+        sort_for_apply(&mut objects);
+        total.set(objects.len());
+        is_applying.set(true);
+        is_dry_run.set(dry_run);
+
+        let namespace = default_namespace.read().trim().to_string();
+        let namespace = if namespace.is_empty() { "default".to_string() } else { namespace };
+        let force = force();
+
+        spawn(async move {
+            let mut resolver = KindResolver::new(client);
+            // Apply one at a time so dependencies exist before the objects that need them
+            for object in &objects {
+                let result = apply_manifest_object(&mut resolver, object, &namespace, force, dry_run).await;
+                results.write().push(result);
+            }
+            is_applying.set(false);
+        });
+    };
+
+    let applied = results.read().clone();
+    let failed = applied.iter().filter(|r| matches!(r.outcome, ApplyOutcome::Failed(_))).count();
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: CREATE_FORMS_CSS }
+
+        div { class: "create-manifest-container",
+            h1 { class: "create-manifest-title", "Apply Manifest" }
+
+            form {
+                onsubmit: move |evt: Event<FormData>| {
+                    evt.prevent_default();
+                    apply_manifest(false);
+                },
+                div { class: "manifest-form",
+                    div { class: "form-group",
+                        label { class: "form-label", "Manifest" }
+                        textarea {
+                            class: "form-input manifest-input",
+                            spellcheck: "false",
+                            value: "{manifest}",
+                            oninput: move |evt| manifest.set(evt.value()),
+                            placeholder: "Paste one or more YAML documents separated by ---"
+                        }
+                        div { class: "manifest-upload",
+                            input {
+                                r#type: "file",
+                                accept: ".yaml,.yml,.json",
+                                multiple: true,
+                                onchange: load_file,
+                            }
+                            span { class: "form-help", "Or load manifests from files; several files are applied together" }
+                        }
+                    }
+
+                    div { class: "form-grid",
+                        div { class: "form-group",
+                            label { class: "form-label", "Default Namespace" }
+                            input {
+                                r#type: "text",
+                                class: "form-input",
+                                value: "{default_namespace}",
+                                onchange: move |evt| default_namespace.set(evt.value()),
+                                placeholder: "default"
+                            }
+                            span { class: "form-help", "Used for namespaced objects that don't set metadata.namespace" }
+                        }
+                        div { class: "form-group",
+                            label { class: "form-label", "Conflicts" }
+                            label { class: "manifest-checkbox",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: force(),
+                                    onchange: move |evt| force.set(evt.checked()),
+                                }
+                                "Force conflicts"
+                            }
+                            span { class: "form-help", "Take ownership of fields managed by other tools" }
+                        }
+                    }
+                }
+
+                if !error.read().is_empty() {
+                    div { class: "error-message",
+                        "{error.read()}"
+                    }
+                }
+
+                if !applied.is_empty() {
+                    div { class: "manifest-results",
+                        div { class: "manifest-results-summary",
+                            if is_dry_run() {
+                                "Dry run, nothing was persisted: "
+                            }
+                            if is_applying() {
+                                "Applying {applied.len()} of {total}..."
+                            } else if failed > 0 {
+                                "{failed} of {total} objects failed"
+                            } else {
+                                "All {total} objects applied"
+                            }
+                        }
+                        {applied.iter().enumerate().map(|(index, result)| {
+                            let object = result.display_name();
+                            let outcome = result.outcome.to_string();
+                            let has_preview = result.preview.is_some() || result.diff.is_some();
+                            let is_expanded = expanded() == Some(index);
+                            rsx! {
+                                div {
+                                    key: "{result.document}-{object}",
+                                    class: "manifest-result",
+                                    span { class: "manifest-result-outcome manifest-{outcome}", "{outcome}" }
+                                    span { class: "manifest-result-object",
+                                        "{object}"
+                                        if has_preview {
+                                            button {
+                                                r#type: "button",
+                                                class: "manifest-preview-toggle",
+                                                onclick: move |_| expanded.set(if is_expanded { None } else { Some(index) }),
+                                                if is_expanded { "Hide preview" } else { "Show preview" }
+                                            }
+                                        }
+                                    }
+                                    if let ApplyOutcome::Failed(message) = &result.outcome {
+                                        span { class: "manifest-result-error", "{message}" }
+                                    }
+                                    if is_expanded {
+                                        div { class: "manifest-result-preview",
+                                            DryRunPreview {
+                                                yaml: result.preview.clone(),
+                                                diff: result.diff.clone(),
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        })}
+                    }
+                }
+
+                div { class: "button-group",
+                    button {
+                        r#type: "submit",
+                        class: "create-form-btn create-form-btn-primary",
+                        disabled: is_applying() || manifest.read().trim().is_empty(),
+                        if is_applying() && !is_dry_run() { "Applying..." } else { "Apply" }
+                    }
+                    button {
+                        r#type: "button",
+                        class: "create-form-btn create-form-btn-secondary",
+                        disabled: is_applying() || manifest.read().trim().is_empty(),
+                        onclick: move |_| apply_manifest(true),
+                        if is_applying() && is_dry_run() { "Previewing..." } else { "Preview" }
+                    }
+                    button {
+                        r#type: "button",
+                        class: "create-form-btn create-form-btn-secondary",
+                        disabled: is_applying(),
+                        onclick: move |_| {
+                            manifest.set(String::new());
+                            results.set(Vec::new());
+                            error.set(String::new());
+                        },
+                        "Clear"
+                    }
+                }
+            }
+        }
+    }
+}
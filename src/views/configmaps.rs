@@ -1,105 +1,130 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::core::v1::ConfigMap;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, SearchInput, ConfigMapItem};
+use crate::components::{NamespaceSelector, SearchInput, ConfigMapItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, CONFIGMAPS};
 
 const CONFIGMAPS_CSS: Asset = asset!("/assets/styling/configmaps.css");
 
 #[derive(Clone)]
 struct ConfigMapFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     configmaps: Signal<Vec<ConfigMap>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl ConfigMapFetcher {
-    fn fetch(&self, ns: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut configmaps = self.configmaps.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting configmaps fetch...");
+        tracing::info!("Starting configmaps watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<ConfigMap>::all(client.clone())
-            } else {
-                Api::<ConfigMap>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(configmap_list) => {
-                    let filtered_configmaps = if query.is_empty() {
-                        configmap_list.items
-                    } else {
-                        configmap_list.items
-                            .into_iter()
-                            .filter(|cm: &ConfigMap| {
-                                let name_match = cm.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false);
-                                
-                                let namespace_match = cm.metadata.namespace.as_ref()
-                                    .map(|ns| ns.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false);
-                                
-                                let data_match = cm.data.as_ref()
-                                    .map(|data| data.iter().any(|(k, v)| {
-                                        k.to_lowercase().contains(&query.to_lowercase()) ||
-                                        v.to_lowercase().contains(&query.to_lowercase())
-                                    }))
-                                    .unwrap_or(false);
-                                
-                                name_match || namespace_match || data_match
-                            })
-                            .collect()
-                    };
-                    
-                    configmaps.set(filtered_configmaps);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch configmaps: {:?}", e);
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<ConfigMap>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        configmaps.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch configmaps: {}", e);
+                        error.set(Some(e));
+                    }
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn ConfigMaps() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", CONFIGMAPS, None));
 
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let configmaps = use_signal(|| Vec::<ConfigMap>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = ConfigMapFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     configmaps: configmaps.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Search is applied here so typing doesn't restart the watch
+    let filtered_configmaps = use_memo(move || {
+        let items = configmaps();
+        let query = search_query();
+        if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|cm: &ConfigMap| {
+                    let name_match = cm.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false);
+                
+                    let namespace_match = cm.metadata.namespace.as_ref()
+                        .map(|ns| ns.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false);
+                
+                    let data_match = cm.data.as_ref()
+                        .map(|data| data.iter().any(|(k, v)| {
+                            k.to_lowercase().contains(&query.to_lowercase()) ||
+                            v.to_lowercase().contains(&query.to_lowercase())
+                        }))
+                        .unwrap_or(false);
+                
+                    name_match || namespace_match || data_match
+                })
+                .collect()
+        }
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = ConfigMapFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     configmaps: configmaps.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All";
+
     rsx! {
         document::Link { rel: "stylesheet", href: CONFIGMAPS_CSS }
         div { class: "configmaps-container",
@@ -115,31 +140,40 @@ pub fn ConfigMaps() -> Element {
                             selected_namespace: selected_namespace(),
                             on_change: move |ns| selected_namespace.set(ns)
                         }
-                        span { class: "configmap-count", "{configmaps().len()} ConfigMaps" }
+                        span { class: "configmap-count", "{filtered_configmaps().len()} ConfigMaps" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "configmaps-grid",
-                {configmaps.read().iter().map(|cm| {
-                    let key = format!("{}-{}", 
-                        cm.metadata.namespace.clone().unwrap_or_default(),
-                        cm.metadata.name.clone().unwrap_or_default()
-                    );
-                    rsx! {
-                        ConfigMapItem {
-                            key: "{key}",
-                            configmap: cm.clone()
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_configmaps.read().is_empty() {
+                EmptyState {
+                    message: "No config maps found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "configmaps-grid",
+                    {filtered_configmaps.read().iter().map(|cm| {
+                        let key = format!("{}-{}", 
+                            cm.metadata.namespace.clone().unwrap_or_default(),
+                            cm.metadata.name.clone().unwrap_or_default()
+                        );
+                        rsx! {
+                            ConfigMapItem {
+                                key: "{key}",
+                                configmap: cm.clone()
+                            }
                         }
-                    }
-                })}
+                    })}
+                }
             }
         }
     }
@@ -7,6 +7,9 @@ use k8s_openapi::{
 use kube::{api::PostParams, Api, Client};
 use std::collections::BTreeMap;
 
+use crate::components::DryRunPreview;
+use crate::k8s::object_to_yaml;
+
 const CREATE_FORMS_CSS: Asset = asset!("/assets/styling/create_forms.css");
 
 #[derive(Default, Clone)]
@@ -82,8 +85,9 @@ pub fn CreateCronJob() -> Element {
     };
 
     let mut error = use_signal(|| None::<String>);
+    let mut preview = use_signal(|| None::<String>);
 
-    let submit = move |_| {
+    let mut submit = move |dry_run: bool| {
         let name = name().clone();
         let cronjob_name = name.clone();
         let namespace = namespace();
@@ -108,6 +112,7 @@ pub fn CreateCronJob() -> Element {
         }
 
         error.set(None);
+        preview.set(None);
         
         spawn(async move {
             use k8s_openapi::api::core::v1::ResourceRequirements;
@@ -231,12 +236,17 @@ pub fn CreateCronJob() -> Element {
             };
 
             let cronjobs: Api<CronJob> = Api::namespaced(client, &namespace);
-            match cronjobs.create(&PostParams::default(), &cronjob).await {
+            match cronjobs.create(&PostParams { dry_run, ..Default::default() }, &cronjob).await {
+                Ok(created) if dry_run => match object_to_yaml(&created) {
+                    Ok(yaml) => preview.set(Some(yaml)),
+                    Err(e) => error.set(Some(format!("Failed to render preview: {}", e))),
+                },
                 Ok(_) => {
                     navigate.push("/cronjobs");
                 }
                 Err(e) => {
-                    error.set(Some(format!("Failed to create cronjob: {}", e)));
+                    let action = if dry_run { "preview" } else { "create" };
+                    error.set(Some(format!("Failed to {} cronjob: {}", action, e)));
                 }
             }
         });
@@ -643,12 +653,21 @@ pub fn CreateCronJob() -> Element {
                 div { class: "error-message", "{err}" }
             ))}
 
+            {preview().map(|yaml| rsx!(
+                DryRunPreview { yaml, on_close: move |_| preview.set(None) }
+            ))}
+
             div { class: "button-group",
                 button {
                     class: "create-form-btn create-form-btn-primary",
-                    onclick: submit,
+                    onclick: move |_| submit(false),
                     "Create CronJob"
                 }
+                button {
+                    class: "create-form-btn create-form-btn-secondary",
+                    onclick: move |_| submit(true),
+                    "Preview"
+                }
                 button {
                     class: "create-form-btn create-form-btn-secondary",
                     onclick: move |_| {
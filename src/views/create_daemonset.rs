@@ -8,6 +8,9 @@ use k8s_openapi::{
 use kube::{api::PostParams, Api, Client};
 use std::collections::BTreeMap;
 
+use crate::components::DryRunPreview;
+use crate::k8s::object_to_yaml;
+
 const CREATE_FORMS_CSS: Asset = asset!("/assets/styling/create_forms.css");
 
 #[derive(Default, Clone)]
@@ -81,8 +84,9 @@ pub fn CreateDaemonSet() -> Element {
     };
 
     let mut error = use_signal(|| None::<String>);
+    let mut preview = use_signal(|| None::<String>);
 
-    let submit = move |_| {
+    let mut submit = move |dry_run: bool| {
         let name = name().clone();
         let daemonset_name = name.clone();
         let namespace = namespace();
@@ -102,6 +106,7 @@ pub fn CreateDaemonSet() -> Element {
         }
 
         error.set(None);
+        preview.set(None);
         
         spawn(async move {
             use k8s_openapi::api::core::v1::ResourceRequirements;
@@ -223,12 +228,17 @@ pub fn CreateDaemonSet() -> Element {
             };
 
             let daemonsets: Api<DaemonSet> = Api::namespaced(client, &namespace);
-            match daemonsets.create(&PostParams::default(), &daemonset).await {
+            match daemonsets.create(&PostParams { dry_run, ..Default::default() }, &daemonset).await {
+                Ok(created) if dry_run => match object_to_yaml(&created) {
+                    Ok(yaml) => preview.set(Some(yaml)),
+                    Err(e) => error.set(Some(format!("Failed to render preview: {}", e))),
+                },
                 Ok(_) => {
                     navigate.push("/daemonsets");
                 }
                 Err(e) => {
-                    error.set(Some(format!("Failed to create daemonset: {}", e)));
+                    let action = if dry_run { "preview" } else { "create" };
+                    error.set(Some(format!("Failed to {} daemonset: {}", action, e)));
                 }
             }
         });
@@ -647,12 +657,21 @@ pub fn CreateDaemonSet() -> Element {
                 div { class: "error-message", "{err}" }
             ))}
 
+            {preview().map(|yaml| rsx!(
+                DryRunPreview { yaml, on_close: move |_| preview.set(None) }
+            ))}
+
             div { class: "button-group",
                 button {
                     class: "create-form-btn create-form-btn-primary",
-                    onclick: submit,
+                    onclick: move |_| submit(false),
                     "Create DaemonSet"
                 }
+                button {
+                    class: "create-form-btn create-form-btn-secondary",
+                    onclick: move |_| submit(true),
+                    "Preview"
+                }
                 button {
                     class: "create-form-btn create-form-btn-secondary",
                     onclick: move |_| {
@@ -7,6 +7,9 @@ use k8s_openapi::{
 use kube::{api::PostParams, Api, Client};
 use std::collections::BTreeMap;
 
+use crate::components::DryRunPreview;
+use crate::k8s::object_to_yaml;
+
 const CREATE_FORMS_CSS: Asset = asset!("/assets/styling/create_forms.css");
 
 #[derive(Default, Clone)]
@@ -81,8 +84,9 @@ pub fn CreateDeployment() -> Element {
     };
 
     let mut error = use_signal(|| None::<String>);
+    let mut preview = use_signal(|| None::<String>);
 
-    let submit = move |_| {
+    let mut submit = move |dry_run: bool| {
         let name = name().clone();
         let deployment_name = name.clone();
         let namespace = namespace();
@@ -111,6 +115,7 @@ pub fn CreateDeployment() -> Element {
         };
 
         error.set(None);
+        preview.set(None);
         
         spawn(async move {
             use k8s_openapi::api::core::v1::ResourceRequirements;
@@ -234,12 +239,17 @@ pub fn CreateDeployment() -> Element {
             };
 
             let deployments: Api<Deployment> = Api::namespaced(client, &namespace);
-            match deployments.create(&PostParams::default(), &deployment).await {
+            match deployments.create(&PostParams { dry_run, ..Default::default() }, &deployment).await {
+                Ok(created) if dry_run => match object_to_yaml(&created) {
+                    Ok(yaml) => preview.set(Some(yaml)),
+                    Err(e) => error.set(Some(format!("Failed to render preview: {}", e))),
+                },
                 Ok(_) => {
                     navigate.push("/deployments");
                 }
                 Err(e) => {
-                    error.set(Some(format!("Failed to create deployment: {}", e)));
+                    let action = if dry_run { "preview" } else { "create" };
+                    error.set(Some(format!("Failed to {} deployment: {}", action, e)));
                 }
             }
         });
@@ -669,12 +679,21 @@ pub fn CreateDeployment() -> Element {
                 div { class: "error-message", "{err}" }
             ))}
 
+            {preview().map(|yaml| rsx!(
+                DryRunPreview { yaml, on_close: move |_| preview.set(None) }
+            ))}
+
             div { class: "button-group",
                 button {
                     class: "create-form-btn create-form-btn-primary",
-                    onclick: submit,
+                    onclick: move |_| submit(false),
                     "Create Deployment"
                 }
+                button {
+                    class: "create-form-btn create-form-btn-secondary",
+                    onclick: move |_| submit(true),
+                    "Preview"
+                }
                 button {
                     class: "create-form-btn create-form-btn-secondary",
                     onclick: move |_| {
@@ -3,6 +3,9 @@ use k8s_openapi::{api::core::v1::Namespace, apimachinery::pkg::apis::meta::v1::O
 use kube::{api::PostParams, Api, Client};
 use std::collections::BTreeMap;
 
+use crate::components::DryRunPreview;
+use crate::k8s::object_to_yaml;
+
 const CREATE_FORMS_CSS: Asset = asset!("/assets/styling/create_forms.css");
 
 #[component]
@@ -12,6 +15,7 @@ pub fn CreateNamespace() -> Element {
     let mut labels = use_signal(|| Vec::<(String, String)>::new());
     let mut error = use_signal(String::new);
     let mut is_submitting = use_signal(|| false);
+    let mut preview = use_signal(|| None::<String>);
 
     let add_label = move |_| {
         let mut current_labels = labels.read().to_vec();
@@ -35,10 +39,10 @@ pub fn CreateNamespace() -> Element {
         labels.set(current_labels);
     };
 
-    let create_namespace = move |evt: Event<FormData>| {
-        evt.prevent_default();
+    let mut create_namespace = move |dry_run: bool| {
         is_submitting.set(true);
         error.set(String::new());
+        preview.set(None);
 
         let Some(client) = client_signal.read().clone() else {
             error.set("No Kubernetes connection available".to_string());
@@ -67,12 +71,20 @@ pub fn CreateNamespace() -> Element {
                 ..Default::default()
             };
 
-            match api.create(&PostParams::default(), &namespace).await {
+            match api.create(&PostParams { dry_run, ..Default::default() }, &namespace).await {
+                Ok(created) if dry_run => {
+                    match object_to_yaml(&created) {
+                        Ok(yaml) => preview.set(Some(yaml)),
+                        Err(e) => error.set(format!("Failed to render preview: {}", e)),
+                    }
+                    is_submitting.set(false);
+                }
                 Ok(_) => {
                     router().push("/namespaces");
                 }
                 Err(e) => {
-                    error.set(format!("Failed to create namespace: {}", e));
+                    let action = if dry_run { "preview" } else { "create" };
+                    error.set(format!("Failed to {} namespace: {}", action, e));
                     is_submitting.set(false);
                 }
             }
@@ -85,7 +97,11 @@ pub fn CreateNamespace() -> Element {
         div { class: "create-namespace-container",
             h1 { class: "create-namespace-title", "Create Namespace" }
 
-            form { onsubmit: create_namespace,
+            form {
+                onsubmit: move |evt: Event<FormData>| {
+                    evt.prevent_default();
+                    create_namespace(false);
+                },
                 div { class: "form-grid",
                     div { class: "form-group",
                         label { class: "form-label", "Name" }
@@ -145,6 +161,10 @@ pub fn CreateNamespace() -> Element {
                     }
                 }
 
+                {preview().map(|yaml| rsx!(
+                    DryRunPreview { yaml, on_close: move |_| preview.set(None) }
+                ))}
+
                 div { class: "button-group",
                     button {
                         r#type: "submit",
@@ -152,6 +172,13 @@ pub fn CreateNamespace() -> Element {
                         disabled: "{is_submitting}",
                         if *is_submitting.read() { "Creating..." } else { "Create Namespace" }
                     }
+                    button {
+                        r#type: "button",
+                        class: "create-form-btn create-form-btn-secondary",
+                        disabled: "{is_submitting}",
+                        onclick: move |_| create_namespace(true),
+                        "Preview"
+                    }
                     button {
                         r#type: "button",
                         class: "create-form-btn create-form-btn-secondary",
@@ -1,6 +1,9 @@
 use dioxus::prelude::*;
 use kube::Client;
 
+use crate::components::DryRunPreview;
+use crate::k8s::object_to_yaml;
+
 const CREATE_FORMS_CSS: Asset = asset!("/assets/styling/create_forms.css");
 
 #[derive(Default, Clone)]
@@ -72,8 +75,9 @@ pub fn CreatePod() -> Element {
     };
 
     let mut error = use_signal(|| None::<String>);
+    let mut preview = use_signal(|| None::<String>);
 
-    let submit = move |_| {
+    let mut submit = move |dry_run: bool| {
         let name = name().clone();
         let pod_name = name.clone();
         let namespace = namespace();
@@ -96,6 +100,7 @@ pub fn CreatePod() -> Element {
         }
 
         error.set(None);
+        preview.set(None);
         
         spawn(async move {
             use k8s_openapi::api::core::v1::{Pod, PodSpec, Container, ResourceRequirements};
@@ -200,12 +205,17 @@ pub fn CreatePod() -> Element {
             };
 
             let pods: Api<Pod> = Api::namespaced(client, &namespace);
-            match pods.create(&PostParams::default(), &pod).await {
+            match pods.create(&PostParams { dry_run, ..Default::default() }, &pod).await {
+                Ok(created) if dry_run => match object_to_yaml(&created) {
+                    Ok(yaml) => preview.set(Some(yaml)),
+                    Err(e) => error.set(Some(format!("Failed to render preview: {}", e))),
+                },
                 Ok(_) => {
                     navigate.push("/pods");
                 }
                 Err(e) => {
-                    error.set(Some(format!("Failed to create pod: {}", e)));
+                    let action = if dry_run { "preview" } else { "create" };
+                    error.set(Some(format!("Failed to {} pod: {}", action, e)));
                 }
             }
         });
@@ -559,12 +569,21 @@ pub fn CreatePod() -> Element {
                 div { class: "error-message", "{err}" }
             ))}
 
+            {preview().map(|yaml| rsx!(
+                DryRunPreview { yaml, on_close: move |_| preview.set(None) }
+            ))}
+
             div { class: "button-group",
                 button {
                     class: "create-form-btn create-form-btn-primary",
-                    onclick: submit,
+                    onclick: move |_| submit(false),
                     "Create Pod"
                 }
+                button {
+                    class: "create-form-btn create-form-btn-secondary",
+                    onclick: move |_| submit(true),
+                    "Preview"
+                }
                 button {
                     class: "create-form-btn create-form-btn-secondary",
                     onclick: move |_| {
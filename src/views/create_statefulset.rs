@@ -10,6 +10,9 @@ use k8s_openapi::{
 use kube::{api::PostParams, Api, Client};
 use std::collections::BTreeMap;
 
+use crate::components::DryRunPreview;
+use crate::k8s::object_to_yaml;
+
 const CREATE_FORMS_CSS: Asset = asset!("/assets/styling/create_forms.css");
 
 #[derive(Default, Clone)]
@@ -120,8 +123,9 @@ pub fn CreateStatefulSet() -> Element {
     };
 
     let mut error = use_signal(|| None::<String>);
+    let mut preview = use_signal(|| None::<String>);
 
-    let submit = move |_| {
+    let mut submit = move |dry_run: bool| {
         let name = name().clone();
         let statefulset_name = name.clone();
         let namespace = namespace();
@@ -155,6 +159,7 @@ pub fn CreateStatefulSet() -> Element {
         };
 
         error.set(None);
+        preview.set(None);
 
         spawn(async move {
             use k8s_openapi::api::core::v1::ResourceRequirements;
@@ -368,14 +373,19 @@ pub fn CreateStatefulSet() -> Element {
 
             let statefulsets: Api<StatefulSet> = Api::namespaced(client, &namespace);
             match statefulsets
-                .create(&PostParams::default(), &statefulset)
+                .create(&PostParams { dry_run, ..Default::default() }, &statefulset)
                 .await
             {
+                Ok(created) if dry_run => match object_to_yaml(&created) {
+                    Ok(yaml) => preview.set(Some(yaml)),
+                    Err(e) => error.set(Some(format!("Failed to render preview: {}", e))),
+                },
                 Ok(_) => {
                     navigate.push("/statefulsets");
                 }
                 Err(e) => {
-                    error.set(Some(format!("Failed to create statefulset: {}", e)));
+                    let action = if dry_run { "preview" } else { "create" };
+                    error.set(Some(format!("Failed to {} statefulset: {}", action, e)));
                 }
             }
         });
@@ -910,12 +920,21 @@ pub fn CreateStatefulSet() -> Element {
                 div { class: "error-message", "{err}" }
             ))}
 
+            {preview().map(|yaml| rsx!(
+                DryRunPreview { yaml, on_close: move |_| preview.set(None) }
+            ))}
+
             div { class: "button-group",
                 button {
                     class: "create-form-btn create-form-btn-primary",
-                    onclick: submit,
+                    onclick: move |_| submit(false),
                     "Create StatefulSet"
                 }
+                button {
+                    class: "create-form-btn create-form-btn-secondary",
+                    onclick: move |_| submit(true),
+                    "Preview"
+                }
                 button {
                     class: "create-form-btn create-form-btn-secondary",
                     onclick: move |_| {
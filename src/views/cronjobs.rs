@@ -1,96 +1,123 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::batch::v1::CronJob;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, SearchInput, CronJobItem};
+use crate::components::{NamespaceSelector, SearchInput, CronJobItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, CRONJOBS};
 
 const CRONJOBS_CSS: Asset = asset!("/assets/styling/cronjobs.css");
 
 #[derive(Clone)]
 struct CronJobFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     cronjobs: Signal<Vec<CronJob>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl CronJobFetcher {
-    fn fetch(&self, ns: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut cronjobs = self.cronjobs.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting cronjobs fetch...");
+        tracing::info!("Starting cronjobs watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<CronJob>::all(client.clone())
-            } else {
-                Api::<CronJob>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(cronjob_list) => {
-                    let filtered_cronjobs = if query.is_empty() {
-                        cronjob_list.items
-                    } else {
-                        cronjob_list.items
-                            .into_iter()
-                            .filter(|cj| {
-                                cj.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false) ||
-                                cj.spec.as_ref()
-                                    .map(|s| s.schedule.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false)
-                            })
-                            .collect()
-                    };
-                    
-                    cronjobs.set(filtered_cronjobs);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch cronjobs: {:?}", e);
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<CronJob>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        cronjobs.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch cronjobs: {}", e);
+                        error.set(Some(e));
+                    }
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn CronJobs() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", CRONJOBS, None));
     let navigate = use_navigator();
 
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let cronjobs = use_signal(|| Vec::<CronJob>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = CronJobFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     cronjobs: cronjobs.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Search is applied here so typing doesn't restart the watch
+    let filtered_cronjobs = use_memo(move || {
+        let items = cronjobs();
+        let query = search_query();
+        if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|cj| {
+                    cj.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false) ||
+                    cj.spec.as_ref()
+                        .map(|s| s.schedule.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false)
+                })
+                .collect()
+        }
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = CronJobFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     cronjobs: cronjobs.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All";
+
+    let can_create = access_context.can("create", CRONJOBS, Some(&selected_namespace()));
+
     rsx! {
         document::Link { rel: "stylesheet", href: CRONJOBS_CSS }
         div { class: "cronjobs-container",
@@ -108,11 +135,13 @@ pub fn CronJobs() -> Element {
                             selected_namespace: selected_namespace(),
                             on_change: move |ns| selected_namespace.set(ns)
                         }
-                        span { class: "cronjob-count", "{cronjobs().len()} cronjobs" }
+                        span { class: "cronjob-count", "{filtered_cronjobs().len()} cronjobs" }
                     }
                     div { class: "header-actions",
                         button { 
                             class: "btn btn-primary",
+                            disabled: !can_create,
+                            title: if can_create { "" } else { "You're not allowed to create cronjobs in this namespace" },
                             onclick: move |_| {
                                 navigate.push("/cronjobs/create");
                             },
@@ -120,22 +149,31 @@ pub fn CronJobs() -> Element {
                         }
                         button { 
                             class: "btn btn-secondary",
-                            onclick: refresh,
+                            onclick: move |_| refresh(),
                             "Refresh" 
                         }
                     }
                 }
             }
 
-            div { class: "cronjobs-grid",
-                {cronjobs().iter().map(|cronjob| {
-                    rsx! {
-                        CronJobItem {
-                            key: "{cronjob.metadata.name.clone().unwrap_or_default()}",
-                            cronjob: cronjob.clone()
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_cronjobs.read().is_empty() {
+                EmptyState {
+                    message: "No cronjobs found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "cronjobs-grid",
+                    {filtered_cronjobs().iter().map(|cronjob| {
+                        rsx! {
+                            CronJobItem {
+                                key: "{cronjob.metadata.name.clone().unwrap_or_default()}",
+                                cronjob: cronjob.clone()
+                            }
                         }
-                    }
-                })}
+                    })}
+                }
             }
         }
     }
@@ -1,117 +1,144 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::apps::v1::DaemonSet;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, StatusSelector, SearchInput, DaemonSetItem};
+use crate::components::{NamespaceSelector, StatusSelector, SearchInput, DaemonSetItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, DAEMONSETS};
 
 const DAEMONSETS_CSS: Asset = asset!("/assets/styling/daemonsets.css");
 
 #[derive(Clone)]
 struct DaemonSetFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     daemonsets: Signal<Vec<DaemonSet>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl DaemonSetFetcher {
-    fn fetch(&self, ns: String, status: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut daemonsets = self.daemonsets.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting daemonset fetch...");
+        tracing::info!("Starting daemonset watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<DaemonSet>::all(client.clone())
-            } else {
-                Api::<DaemonSet>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(daemonset_list) => {
-                    let mut filtered_daemonsets = if query.is_empty() {
-                        daemonset_list.items
-                    } else {
-                        daemonset_list.items
-                            .into_iter()
-                            .filter(|ds: &DaemonSet| {
-                                ds.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false)
-                            })
-                            .collect::<Vec<_>>()
-                    };
-
-                    // Filter by status if not "All"
-                    if status != "All" {
-                        filtered_daemonsets = filtered_daemonsets.into_iter()
-                            .filter(|ds| {
-                                let desired = ds.status.as_ref().map_or(0, |s| s.desired_number_scheduled);
-                                let ready = ds.status.as_ref().map_or(0, |s| s.number_ready);
-                                let current = ds.status.as_ref().map_or(0, |s| s.current_number_scheduled);
-                                let updated = ds.status.as_ref().map_or(0, |s| s.updated_number_scheduled.unwrap_or(s.current_number_scheduled));
-
-                                match status.as_str() {
-                                    "Running" => ready == desired && current == desired && updated == desired,
-                                    "Progressing" => ready < desired && ready > 0,
-                                    "Not Ready" => ready == 0 && desired > 0,
-                                    "No Nodes" => desired == 0,
-                                    _ => false
-                                }
-                            })
-                            .collect();
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<DaemonSet>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        daemonsets.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch daemonsets: {}", e);
+                        error.set(Some(e));
                     }
-                    
-                    daemonsets.set(filtered_daemonsets);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch daemonsets: {:?}", e);
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn DaemonSets() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", DAEMONSETS, None));
     let navigate = use_navigator();
 
     let mut selected_status = use_signal(|| "All".to_string());
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let daemonsets = use_signal(|| Vec::<DaemonSet>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     // Always call use_effect but handle conditional logic inside
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = DaemonSetFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     daemonsets: daemonsets.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let status = selected_status();
-                let query = search_query();
-                fetcher.fetch(ns, status, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Status and search are applied here so typing doesn't restart the watch
+    let filtered_daemonsets = use_memo(move || {
+        let items = daemonsets();
+        let query = search_query();
+        let status = selected_status();
+        let mut filtered_daemonsets = if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|ds: &DaemonSet| {
+                    ds.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Filter by status if not "All"
+        if status != "All" {
+            filtered_daemonsets = filtered_daemonsets.into_iter()
+                .filter(|ds| {
+                    let desired = ds.status.as_ref().map_or(0, |s| s.desired_number_scheduled);
+                    let ready = ds.status.as_ref().map_or(0, |s| s.number_ready);
+                    let current = ds.status.as_ref().map_or(0, |s| s.current_number_scheduled);
+                    let updated = ds.status.as_ref().map_or(0, |s| s.updated_number_scheduled.unwrap_or(s.current_number_scheduled));
+
+                    match status.as_str() {
+                        "Running" => ready == desired && current == desired && updated == desired,
+                        "Progressing" => ready < desired && ready > 0,
+                        "Not Ready" => ready == 0 && desired > 0,
+                        "No Nodes" => desired == 0,
+                        _ => false
+                    }
+                })
+                .collect();
+        }
+        filtered_daemonsets
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = DaemonSetFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     daemonsets: daemonsets.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let status = selected_status();
-                let query = search_query();
-                fetcher.fetch(ns, status, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All" || selected_status() != "All";
+
+    let can_create = access_context.can("create", DAEMONSETS, Some(&selected_namespace()));
+
     rsx! {
         document::Link { rel: "stylesheet", href: DAEMONSETS_CSS }
         div { class: "daemonsets-container",
@@ -132,12 +159,14 @@ pub fn DaemonSets() -> Element {
                             on_change: move |status| selected_status.set(status),
                             custom_statuses: Some(vec!["All", "Running", "Progressing", "Not Ready", "No Nodes"])
                         }
-                        span { class: "daemonset-count", "{daemonsets().len()} daemonsets" }
+                        span { class: "daemonset-count", "{filtered_daemonsets().len()} daemonsets" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-primary",
+                        disabled: !can_create,
+                        title: if can_create { "" } else { "You're not allowed to create daemonsets in this namespace" },
                         onclick: move |_| {
                             navigate.push("/daemonsets/create");
                         },
@@ -145,18 +174,27 @@ pub fn DaemonSets() -> Element {
                     }
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "daemonsets-grid",
-                {daemonsets().iter().map(|daemonset| {
-                    rsx! {
-                        DaemonSetItem { daemonset: daemonset.clone() }
-                    }
-                })}
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_daemonsets.read().is_empty() {
+                EmptyState {
+                    message: "No daemonsets found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "daemonsets-grid",
+                    {filtered_daemonsets().iter().map(|daemonset| {
+                        rsx! {
+                            DaemonSetItem { daemonset: daemonset.clone() }
+                        }
+                    })}
+                }
             }
         }
     }
@@ -1,130 +1,157 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::apps::v1::Deployment;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, StatusSelector, SearchInput, DeploymentItem};
+use crate::components::{NamespaceSelector, StatusSelector, SearchInput, DeploymentItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, DEPLOYMENTS};
 
 const DEPLOYMENTS_CSS: Asset = asset!("/assets/styling/deployments.css");
 
 #[derive(Clone)]
 struct DeploymentFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     deployments: Signal<Vec<Deployment>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl DeploymentFetcher {
-    fn fetch(&self, ns: String, status: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut deployments = self.deployments.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting deployment fetch...");
+        tracing::info!("Starting deployment watch...");
         
-        spawn(async move {
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
             // First get all deployments since we need to examine conditions
-            let api = if ns == "All" {
-                Api::<Deployment>::all(client.clone())
-            } else {
-                Api::<Deployment>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(deployment_list) => {
-                    let mut filtered_deployments = if query.is_empty() {
-                        deployment_list.items
-                    } else {
-                        deployment_list.items
-                            .into_iter()
-                            .filter(|dep: &Deployment| {
-                                dep.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false)
-                            })
-                            .collect::<Vec<_>>()
-                    };
-
-                    // Filter by status if not "All"
-                    if status != "All" {
-                        filtered_deployments = filtered_deployments.into_iter()
-                            .filter(|dep| {
-                                let desired = dep.spec.as_ref().map_or(0, |s| s.replicas.unwrap_or(0));
-                                let updated = dep.status.as_ref().map_or(0, |s| s.updated_replicas.unwrap_or(0));
-                                let available = dep.status.as_ref().map_or(0, |s| s.available_replicas.unwrap_or(0));
-                                
-                                let conditions = dep.status.as_ref()
-                                    .and_then(|s| s.conditions.as_ref())
-                                    .map(|c| c.iter().map(|cond| (cond.type_.clone(), cond.status.clone(), cond.reason.clone())).collect::<Vec<_>>())
-                                    .unwrap_or_default();
-
-                                let is_progressing = conditions.iter().any(|(t, s, _)| t == "Progressing" && s == "True");
-                                let is_available = conditions.iter().any(|(t, s, _)| t == "Available" && s == "True");
-                                let has_replica_failure = conditions.iter().any(|(t, _, r)| t == "Progressing" && r.as_ref().map_or(false, |r| r == "ReplicaFailure"));
-                                
-                                match status.as_str() {
-                                    "Available" => is_available && is_progressing && updated == desired && available == desired,
-                                    "Progressing" => is_progressing && (!is_available || updated != desired),
-                                    "Degraded" => has_replica_failure || (!is_progressing && !is_available),
-                                    "Scaled Down" => desired == 0,
-                                    _ => false
-                                }
-                            })
-                            .collect();
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<Deployment>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        deployments.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch deployments: {}", e);
+                        error.set(Some(e));
                     }
-                    
-                    deployments.set(filtered_deployments);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch deployments: {:?}", e);
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn Deployments() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", DEPLOYMENTS, None));
     let navigate = use_navigator();
 
     let mut selected_status = use_signal(|| "All".to_string());
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let deployments = use_signal(|| Vec::<Deployment>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     // Always call use_effect but handle conditional logic inside
     use_effect({
-        let client_signal = client_signal.clone();
+        let cache_signal = cache_signal.clone();
         let deployments = deployments.clone();
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = DeploymentFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     deployments: deployments.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let status = selected_status();
-                let query = search_query();
-                fetcher.fetch(ns, status, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Status and search are applied here so typing doesn't restart the watch
+    let filtered_deployments = use_memo(move || {
+        let items = deployments();
+        let query = search_query();
+        let status = selected_status();
+        let mut filtered_deployments = if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|dep: &Deployment| {
+                    dep.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Filter by status if not "All"
+        if status != "All" {
+            filtered_deployments = filtered_deployments.into_iter()
+                .filter(|dep| {
+                    let desired = dep.spec.as_ref().map_or(0, |s| s.replicas.unwrap_or(0));
+                    let updated = dep.status.as_ref().map_or(0, |s| s.updated_replicas.unwrap_or(0));
+                    let available = dep.status.as_ref().map_or(0, |s| s.available_replicas.unwrap_or(0));
+                
+                    let conditions = dep.status.as_ref()
+                        .and_then(|s| s.conditions.as_ref())
+                        .map(|c| c.iter().map(|cond| (cond.type_.clone(), cond.status.clone(), cond.reason.clone())).collect::<Vec<_>>())
+                        .unwrap_or_default();
+
+                    let is_progressing = conditions.iter().any(|(t, s, _)| t == "Progressing" && s == "True");
+                    let is_available = conditions.iter().any(|(t, s, _)| t == "Available" && s == "True");
+                    let has_replica_failure = conditions.iter().any(|(t, _, r)| t == "Progressing" && r.as_ref().map_or(false, |r| r == "ReplicaFailure"));
+                
+                    match status.as_str() {
+                        "Available" => is_available && is_progressing && updated == desired && available == desired,
+                        "Progressing" => is_progressing && (!is_available || updated != desired),
+                        "Degraded" => has_replica_failure || (!is_progressing && !is_available),
+                        "Scaled Down" => desired == 0,
+                        _ => false
+                    }
+                })
+                .collect();
+        }
+        filtered_deployments
+    });
+
     let refresh = {
-        let client_signal = client_signal.clone();
+        let cache_signal = cache_signal.clone();
         let deployments = deployments.clone();
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = DeploymentFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     deployments: deployments.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let status = selected_status();
-                let query = search_query();
-                fetcher.fetch(ns, status, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All" || selected_status() != "All";
+
+    let can_create = access_context.can("create", DEPLOYMENTS, Some(&selected_namespace()));
+
     rsx! {
         document::Link { rel: "stylesheet", href: DEPLOYMENTS_CSS }
         div { class: "deployments-container",
@@ -145,12 +172,14 @@ pub fn Deployments() -> Element {
                             on_change: move |status| selected_status.set(status),
                             custom_statuses: Some(vec!["All", "Available", "Progressing", "Degraded", "Scaled Down"])
                         }
-                        span { class: "deployment-count", "{deployments().len()} deployments" }
+                        span { class: "deployment-count", "{filtered_deployments().len()} deployments" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-primary",
+                        disabled: !can_create,
+                        title: if can_create { "" } else { "You're not allowed to create deployments in this namespace" },
                         onclick: move |_| {
                             navigate.push("/deployments/create");
                         },
@@ -158,18 +187,27 @@ pub fn Deployments() -> Element {
                     }
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "deployments-grid",
-                {deployments().iter().map(|deployment| {
-                    rsx! {
-                        DeploymentItem { deployment: deployment.clone() }
-                    }
-                })}
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_deployments.read().is_empty() {
+                EmptyState {
+                    message: "No deployments found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "deployments-grid",
+                    {filtered_deployments().iter().map(|deployment| {
+                        rsx! {
+                            DeploymentItem { deployment: deployment.clone() }
+                        }
+                    })}
+                }
             }
         }
     }
@@ -1,4 +1,6 @@
-use crate::k8s::{get_cluster_resources, get_recent_events, ClusterResourceUsage};
+use crate::components::{EmptyState, InlineError, MetricsSourceNote, MetricsTrend, PromQlPanel, PrometheusCharts, PrometheusSetup};
+use crate::contexts::error::AppError;
+use crate::k8s::{get_cluster_resources, get_recent_events, ClusterCache, ClusterResourceUsage, MetricsHistory, MetricsSeries, PromScope};
 use dioxus::prelude::*;
 use k8s_openapi::api::core::v1::Event;
 use kube::Client;
@@ -9,8 +11,14 @@ const OVERVIEW_CSS: Asset = asset!("/assets/styling/overview.css");
 #[component]
 pub fn Home() -> Element {
     let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let metrics_history = use_context::<Signal<MetricsHistory>>();
     let events = use_signal(Vec::<Event>::new);
     let resources = use_signal(|| ClusterResourceUsage::default());
+    let mut events_error = use_signal(|| None::<AppError>);
+    let mut resources_error = use_signal(|| None::<AppError>);
+    let mut events_retry = use_signal(|| 0u32);
+    let mut resources_retry = use_signal(|| 0u32);
 
     // Check if we have a client available
     let has_client = client_signal.read().is_some();
@@ -19,12 +27,18 @@ pub fn Home() -> Element {
         let mut events = events.clone();
         let client_signal = client_signal.clone();
         move || {
+            let _ = events_retry();
             if let Some(client) = &*client_signal.read() {
                 spawn({
                     let client = client.clone();
                     async move {
-                        let recent_events = get_recent_events(client).await;
-                        events.set(recent_events);
+                        match get_recent_events(client).await {
+                            Ok(recent_events) => {
+                                events.set(recent_events);
+                                events_error.set(None);
+                            }
+                            Err(e) => events_error.set(Some(e)),
+                        }
                     }
                 });
             }
@@ -33,14 +47,21 @@ pub fn Home() -> Element {
     
     use_effect({
         let mut resources = resources.clone();
-        let client_signal = client_signal.clone();
         move || {
-            if let Some(client) = &*client_signal.read() {
+            let _ = resources_retry();
+            // Runs again with every round the sampler records
+            let metrics = metrics_history.read().current();
+            if let Some(cache) = &*cache_signal.read() {
                 spawn({
-                    let client = client.clone();
+                    let cache = cache.clone();
                     async move {
-                        let usage = get_cluster_resources(client).await;
-                        resources.set(usage);
+                        match get_cluster_resources(&cache, metrics.as_ref()).await {
+                            Ok(usage) => {
+                                resources.set(usage);
+                                resources_error.set(None);
+                            }
+                            Err(e) => resources_error.set(Some(e)),
+                        }
                     }
                 });
             }
@@ -55,6 +76,10 @@ pub fn Home() -> Element {
                     h1 { "Cluster Overview" }
                 }
 
+                if let Some(error) = resources_error() {
+                    InlineError { error, on_retry: move |_| resources_retry += 1 }
+                }
+
                 // Cluster Status Cards
                 div { class: "cluster-status",
                 div { class: "status-card",
@@ -95,7 +120,7 @@ pub fn Home() -> Element {
 
             // Resource Usage Section
             div { class: "resource-section",
-                h2 { "Resource Usage" }
+                h2 { "Resource Usage" MetricsSourceNote {} }
                 div { class: "resource-grid",
                     div { class: "resource-card",
                         div { class: "resource-header",
@@ -194,11 +219,25 @@ pub fn Home() -> Element {
                         }
                     }
                 }
+                MetricsTrend { series: MetricsSeries::Cluster }
+            }
+
+            // Prometheus Section
+            div { class: "resource-section",
+                h2 { "Prometheus" }
+                PrometheusSetup {}
+                PrometheusCharts { scope: PromScope::Cluster, expanded: true }
+                PromQlPanel {}
             }
 
             // Recent Events Section
             div { class: "events-section",
                 h2 { "Recent Events" }
+                if let Some(error) = events_error() {
+                    InlineError { error, on_retry: move |_| events_retry += 1 }
+                } else if events.read().is_empty() {
+                    EmptyState { message: "No recent events" }
+                }
                 div { class: "events-list",
                     {events.read().iter().map(|event| {
                         let reason = event.reason.as_deref().unwrap_or("Unknown");
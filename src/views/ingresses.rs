@@ -1,100 +1,125 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::networking::v1::Ingress;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, SearchInput, IngressItem};
+use crate::components::{NamespaceSelector, SearchInput, IngressItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, INGRESSES};
 
 const INGRESSES_CSS: Asset = asset!("/assets/styling/ingresses.css");
 
 #[derive(Clone)]
 struct IngressFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     ingresses: Signal<Vec<Ingress>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl IngressFetcher {
-    fn fetch(&self, ns: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut ingresses = self.ingresses.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting ingresses fetch...");
+        tracing::info!("Starting ingresses watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<Ingress>::all(client.clone())
-            } else {
-                Api::<Ingress>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(ingress_list) => {
-                    let filtered_ingresses = if query.is_empty() {
-                        ingress_list.items
-                    } else {
-                        ingress_list.items
-                            .into_iter()
-                            .filter(|ing: &Ingress| {
-                                ing.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false) ||
-                                ing.spec.as_ref()
-                                    .and_then(|s| s.rules.as_ref())
-                                    .map(|rules| rules.iter().any(|r| {
-                                        r.host.as_ref()
-                                            .map(|h| h.to_lowercase().contains(&query.to_lowercase()))
-                                            .unwrap_or(false)
-                                    }))
-                                    .unwrap_or(false)
-                            })
-                            .collect()
-                    };
-                    
-                    ingresses.set(filtered_ingresses);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch ingresses: {:?}", e);
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<Ingress>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        ingresses.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch ingresses: {}", e);
+                        error.set(Some(e));
+                    }
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn Ingresses() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", INGRESSES, None));
 
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let ingresses = use_signal(|| Vec::<Ingress>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = IngressFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     ingresses: ingresses.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Search is applied here so typing doesn't restart the watch
+    let filtered_ingresses = use_memo(move || {
+        let items = ingresses();
+        let query = search_query();
+        if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|ing: &Ingress| {
+                    ing.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false) ||
+                    ing.spec.as_ref()
+                        .and_then(|s| s.rules.as_ref())
+                        .map(|rules| rules.iter().any(|r| {
+                            r.host.as_ref()
+                                .map(|h| h.to_lowercase().contains(&query.to_lowercase()))
+                                .unwrap_or(false)
+                        }))
+                        .unwrap_or(false)
+                })
+                .collect()
+        }
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = IngressFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     ingresses: ingresses.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All";
+
     rsx! {
         document::Link { rel: "stylesheet", href: INGRESSES_CSS }
         div { class: "ingresses-container",
@@ -110,27 +135,36 @@ pub fn Ingresses() -> Element {
                             selected_namespace: selected_namespace(),
                             on_change: move |ns| selected_namespace.set(ns)
                         }
-                        span { class: "ingress-count", "{ingresses().len()} ingresses" }
+                        span { class: "ingress-count", "{filtered_ingresses().len()} ingresses" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "ingresses-grid",
-                {ingresses().iter().map(|ingress| {
-                    rsx! {
-                        IngressItem {
-                            key: "{ingress.metadata.name.clone().unwrap_or_default()}",
-                            ingress: ingress.clone()
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_ingresses.read().is_empty() {
+                EmptyState {
+                    message: "No ingresses found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "ingresses-grid",
+                    {filtered_ingresses().iter().map(|ingress| {
+                        rsx! {
+                            IngressItem {
+                                key: "{ingress.metadata.name.clone().unwrap_or_default()}",
+                                ingress: ingress.clone()
+                            }
                         }
-                    }
-                })}
+                    })}
+                }
             }
         }
     }
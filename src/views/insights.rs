@@ -1,19 +1,23 @@
+use crate::Route;
+use crate::components::{EmptyState, InlineError, MetricsSourceNote};
+use crate::contexts::error::AppError;
 use crate::k8s::{
     find_pods_without_limits, find_resource_hotspots, find_unused_configmaps, find_unused_pvcs,
     problem_pod::{check_pod_status, ProblemPod},
     resource_limits::PodResourceIssue,
     resource_metrics::ResourceHotspot,
-    ClusterStats,
+    ClusterCache, ClusterStats, MetricsHistory,
 };
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::core::v1::{ConfigMap, PersistentVolumeClaim, Pod};
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
 const INSIGHTS_CSS: Asset = asset!("/assets/styling/insights.css");
 
 #[component]
 pub fn Insights() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let metrics_history = use_context::<Signal<MetricsHistory>>();
     let problem_pods = use_signal(Vec::<ProblemPod>::new);
     let cluster_stats = use_signal(ClusterStats::default);
     let mut visible_pods = use_signal(|| 6); // Number of pods to show initially
@@ -25,6 +29,13 @@ pub fn Insights() -> Element {
     let unused_pvcs = use_signal(Vec::<(PersistentVolumeClaim, String)>::new);
     let pods_without_limits = use_signal(Vec::<PodResourceIssue>::new);
     let resource_hotspots = use_signal(Vec::<ResourceHotspot>::new);
+    let mut pods_error = use_signal(|| None::<AppError>);
+    let mut unused_error = use_signal(|| None::<AppError>);
+    let mut limits_error = use_signal(|| None::<AppError>);
+    let mut hotspots_error = use_signal(|| None::<AppError>);
+    // Bumped by the retry buttons to run the one-off fetches again
+    let mut retry = use_signal(|| 0u32);
+    let mut pods_task = use_signal(|| None::<Task>);
 
     // Fetch problem pods and compute stats
     // Effect to find unused ConfigMaps
@@ -33,15 +44,17 @@ pub fn Insights() -> Element {
         let mut unused_pvcs = unused_pvcs.clone();
 
         move || {
-            if let Some(client) = &*client_signal.read() {
+            let _ = retry();
+            if let Some(cache) = &*cache_signal.read() {
                 spawn({
-                    let client = client.clone();
+                    let cache = cache.clone();
                     async move {
-                        let unused_cm = find_unused_configmaps(client.clone()).await;
-                        unused_configmaps.set(unused_cm);
-
-                        let unused_pvc = find_unused_pvcs(client).await;
-                        unused_pvcs.set(unused_pvc);
+                        let result = async {
+                            unused_configmaps.set(find_unused_configmaps(&cache).await?);
+                            unused_pvcs.set(find_unused_pvcs(&cache).await?);
+                            Ok::<(), AppError>(())
+                        };
+                        unused_error.set(result.await.err());
                     }
                 });
             }
@@ -55,55 +68,32 @@ pub fn Insights() -> Element {
         let mut is_loading_more = is_loading_more.clone();
 
         move || {
-            if let Some(client) = &*client_signal.read() {
-                spawn({
-                    let client = client.clone();
-                    async move {
-                        let pods: Api<Pod> = Api::all(client);
-                        let mut params = ListParams::default().limit(20); // Fetch 20 pods at a time
-                        let mut all_problems = Vec::new();
-                        let mut stats = ClusterStats::default();
-
-                        loop {
-                            match pods.list(&params).await {
-                                Ok(pod_list) => {
-                                    // Update stats with this batch
-                                    let batch_stats =
-                                        ClusterStats::compute_from_pods(&pod_list.items);
-                                    stats.crashloop_count += batch_stats.crashloop_count;
-                                    stats.restart_count += batch_stats.restart_count;
-                                    stats.evicted_count += batch_stats.evicted_count;
-
-                                    // Process problem pods from this batch
-                                    let batch_problems: Vec<ProblemPod> = pod_list
-                                        .items
-                                        .iter()
-                                        .filter_map(check_pod_status)
-                                        .collect();
-                                    all_problems.extend(batch_problems);
-
-                                    // Update the UI with what we have so far
-                                    cluster_stats.set(stats.clone());
-                                    problem_pods.set(all_problems.clone());
-
-                                    // Check if we've received all pods
-                                    if let Some(continue_token) = pod_list.metadata.continue_ {
-                                        params = params.continue_token(&continue_token);
-                                        is_loading_more.set(true);
-                                    } else {
-                                        is_loading_more.set(false);
-                                        break;
-                                    }
-                                }
-                                Err(e) => {
-                                    tracing::error!("Failed to fetch pods: {}", e);
-                                    is_loading_more.set(false);
-                                    break;
-                                }
+            // Stop following the previous cluster's pods
+            if let Some(task) = pods_task.write().take() {
+                task.cancel();
+            }
+            if let Some(cache) = &*cache_signal.read() {
+                let cache = cache.clone();
+                is_loading_more.set(true);
+                let task = spawn(async move {
+                    // Recompute whenever the cached pods change
+                    let mut snapshots = Box::pin(cache.snapshots::<Pod>());
+                    while let Some(snapshot) = snapshots.next().await {
+                        match snapshot {
+                            Ok(pods) => {
+                                cluster_stats.set(ClusterStats::compute_from_pods(&pods));
+                                problem_pods.set(pods.iter().filter_map(check_pod_status).collect());
+                                pods_error.set(None);
+                            }
+                            Err(e) => {
+                                tracing::error!("Failed to fetch pods: {}", e);
+                                pods_error.set(Some(e));
                             }
                         }
+                        is_loading_more.set(false);
                     }
                 });
+                pods_task.set(Some(task));
             }
         }
     });
@@ -113,12 +103,18 @@ pub fn Insights() -> Element {
         let mut pods_without_limits = pods_without_limits.clone();
 
         move || {
-            if let Some(client) = &*client_signal.read() {
+            let _ = retry();
+            if let Some(cache) = &*cache_signal.read() {
                 spawn({
-                    let client = client.clone();
+                    let cache = cache.clone();
                     async move {
-                        let issues = find_pods_without_limits(client).await;
-                        pods_without_limits.set(issues);
+                        match find_pods_without_limits(&cache).await {
+                            Ok(issues) => {
+                                pods_without_limits.set(issues);
+                                limits_error.set(None);
+                            }
+                            Err(e) => limits_error.set(Some(e)),
+                        }
                     }
                 });
             }
@@ -130,12 +126,28 @@ pub fn Insights() -> Element {
         let mut resource_hotspots = resource_hotspots.clone();
 
         move || {
-            if let Some(client) = &*client_signal.read() {
+            let _ = retry();
+            // Usage comes from the background sampler, so this runs again with every round
+            let (usage, usage_error) = {
+                let history = metrics_history.read();
+                (history.current(), history.error().cloned())
+            };
+            if let Some(cache) = &*cache_signal.read() {
                 spawn({
-                    let client = client.clone();
+                    let cache = cache.clone();
                     async move {
-                        let hotspots = find_resource_hotspots(client).await;
-                        resource_hotspots.set(hotspots);
+                        let Some(usage) = usage else {
+                            // Nothing to go by until the first round, or if sampling fails
+                            hotspots_error.set(usage_error);
+                            return;
+                        };
+                        match find_resource_hotspots(&cache, &usage).await {
+                            Ok(hotspots) => {
+                                resource_hotspots.set(hotspots);
+                                hotspots_error.set(None);
+                            }
+                            Err(e) => hotspots_error.set(Some(e)),
+                        }
                     }
                 });
             }
@@ -169,6 +181,11 @@ pub fn Insights() -> Element {
         // Problem Pods Section
         div { class: "insights-section",
             h2 { "Problem Pods" }
+            if let Some(error) = pods_error() {
+                InlineError { error }
+            } else if !is_loading_more() && problem_pods.read().is_empty() {
+                EmptyState { message: "No problem pods" }
+            }
             div { class: "problem-pods-grid",
                 {problem_pods.read()
                     .iter()
@@ -183,6 +200,20 @@ pub fn Insights() -> Element {
                                 div { class: "issue-type", "{pod.issue_type}" }
                                 p { class: "issue-details", "{pod.details}" }
                             }
+                            // Crashed containers link straight into their logs
+                            if matches!(pod.issue_type.as_str(), "CrashLoopBackOff" | "Container Failed") {
+                                if let Some(container) = &pod.container {
+                                    Link {
+                                        class: "problem-pod-logs-link",
+                                        to: Route::PodLogs {
+                                            namespace: pod.namespace.clone(),
+                                            name: pod.name.clone(),
+                                            container: container.clone(),
+                                        },
+                                        "View logs →"
+                                    }
+                                }
+                            }
                         }
                     })
                 }
@@ -222,7 +253,12 @@ pub fn Insights() -> Element {
 
         // Resource Hotspots
         div { class: "insights-section",
-            h2 { "Resource Hot & Cold spots" }
+            h2 { "Resource Hot & Cold spots" MetricsSourceNote {} }
+            if let Some(error) = hotspots_error() {
+                InlineError { error, on_retry: move |_| retry += 1 }
+            } else if resource_hotspots.read().is_empty() {
+                EmptyState { message: "No hot or cold spots" }
+            }
             div { class: "resource-hotspots-grid",
                 {resource_hotspots.read()
                     .iter()
@@ -274,6 +310,11 @@ pub fn Insights() -> Element {
         // Pods Without Resource Limits
         div { class: "insights-section",
             h2 { "Pods Without Resource Limits" }
+            if let Some(error) = limits_error() {
+                InlineError { error, on_retry: move |_| retry += 1 }
+            } else if pods_without_limits.read().is_empty() {
+                EmptyState { message: "Every pod has resource limits" }
+            }
             div { class: "problem-pods-grid",
                 {pods_without_limits.read()
                     .iter()
@@ -319,6 +360,11 @@ pub fn Insights() -> Element {
         // Unused Resources
         div { class: "insights-section",
             h2 { "Unused Resources" }
+            if let Some(error) = unused_error() {
+                InlineError { error, on_retry: move |_| retry += 1 }
+            } else if unused_configmaps.read().is_empty() && unused_pvcs.read().is_empty() {
+                EmptyState { message: "No unused config maps or PVCs" }
+            }
             div { class: "problem-pods-grid",
                 // Create a combined iterator of configmaps and pvcs for pagination
                 {
@@ -1,97 +1,122 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::batch::v1::Job;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, SearchInput, JobItem};
+use crate::components::{NamespaceSelector, SearchInput, JobItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, JOBS};
 
 const JOBS_CSS: Asset = asset!("/assets/styling/jobs.css");
 
 #[derive(Clone)]
 struct JobFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     jobs: Signal<Vec<Job>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl JobFetcher {
-    fn fetch(&self, ns: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut jobs = self.jobs.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting jobs fetch...");
+        tracing::info!("Starting jobs watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<Job>::all(client.clone())
-            } else {
-                Api::<Job>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(job_list) => {
-                    let filtered_jobs = if query.is_empty() {
-                        job_list.items
-                    } else {
-                        job_list.items
-                            .into_iter()
-                            .filter(|j: &Job| {
-                                let name_match = j.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false);
-                                
-                                let namespace_match = j.metadata.namespace.as_ref()
-                                    .map(|ns| ns.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false);
-                                
-                                name_match || namespace_match
-                            })
-                            .collect()
-                    };
-                    
-                    jobs.set(filtered_jobs);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch jobs: {:?}", e);
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<Job>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        jobs.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch jobs: {}", e);
+                        error.set(Some(e));
+                    }
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn Jobs() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", JOBS, None));
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let jobs = use_signal(|| Vec::<Job>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = JobFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     jobs: jobs.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Search is applied here so typing doesn't restart the watch
+    let filtered_jobs = use_memo(move || {
+        let items = jobs();
+        let query = search_query();
+        if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|j: &Job| {
+                    let name_match = j.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false);
+                
+                    let namespace_match = j.metadata.namespace.as_ref()
+                        .map(|ns| ns.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false);
+                
+                    name_match || namespace_match
+                })
+                .collect()
+        }
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = JobFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     jobs: jobs.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All";
+
     rsx! {
         document::Link { rel: "stylesheet", href: JOBS_CSS }
         div { class: "jobs-container",
@@ -107,31 +132,40 @@ pub fn Jobs() -> Element {
                             selected_namespace: selected_namespace(),
                             on_change: move |ns| selected_namespace.set(ns)
                         }
-                        span { class: "job-count", "{jobs().len()} Jobs" }
+                        span { class: "job-count", "{filtered_jobs().len()} Jobs" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "jobs-grid",
-                {jobs.read().iter().map(|j| {
-                    let key = format!("{}-{}", 
-                        j.metadata.namespace.clone().unwrap_or_default(),
-                        j.metadata.name.clone().unwrap_or_default()
-                    );
-                    rsx! {
-                        JobItem {
-                            key: "{key}",
-                            job: j.clone()
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_jobs.read().is_empty() {
+                EmptyState {
+                    message: "No jobs found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "jobs-grid",
+                    {filtered_jobs.read().iter().map(|j| {
+                        let key = format!("{}-{}", 
+                            j.metadata.namespace.clone().unwrap_or_default(),
+                            j.metadata.name.clone().unwrap_or_default()
+                        );
+                        rsx! {
+                            JobItem {
+                                key: "{key}",
+                                job: j.clone()
+                            }
                         }
-                    }
-                })}
+                    })}
+                }
             }
         }
     }
@@ -50,6 +50,9 @@ pub use nodes::Nodes;
 mod pods;
 pub use pods::Pods;
 
+mod pod_logs;
+pub use pod_logs::PodLogs;
+
 mod create_pod;
 pub use create_pod::CreatePod;
 
@@ -66,4 +69,10 @@ mod create_daemonset;
 pub use create_daemonset::CreateDaemonSet;
 
 mod create_cronjob;
-pub use create_cronjob::CreateCronJob;
\ No newline at end of file
+pub use create_cronjob::CreateCronJob;
+
+mod apply_manifest;
+pub use apply_manifest::ApplyManifest;
+
+mod api_resources;
+pub use api_resources::ApiResources;
\ No newline at end of file
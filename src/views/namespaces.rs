@@ -6,71 +6,15 @@ use k8s_openapi::{
     apimachinery::pkg::api::resource::Quantity,
     chrono,
 };
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 use std::collections::BTreeMap;
 
-use crate::components::{LimitRange, NamespaceItem, NamespaceItemProps, ResourceQuota};
+use crate::components::{EmptyState, InlineError, LimitRange, NamespaceItem, NamespaceItemProps, ResourceQuota};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, ParsedQuantity, NAMESPACES};
 
 const NAMESPACES_CSS: Asset = asset!("/assets/styling/namespaces.css");
 
-fn parse_resource_quantity(value: &str) -> Option<f32> {
-    if value.is_empty() || value == "0" {
-        return None;
-    }
-
-    // Handle memory values
-    if value.ends_with("Gi") {
-        return value.trim_end_matches("Gi").parse::<f32>().ok();
-    }
-    if value.ends_with("Mi") {
-        return value
-            .trim_end_matches("Mi")
-            .parse::<f32>()
-            .ok()
-            .map(|v| v / 1024.0);
-    }
-    if value.ends_with("Ki") {
-        return value
-            .trim_end_matches("Ki")
-            .parse::<f32>()
-            .ok()
-            .map(|v| v / (1024.0 * 1024.0));
-    }
-
-    // Handle CPU values
-    if value.ends_with('m') {
-        return value
-            .trim_end_matches('m')
-            .parse::<f32>()
-            .ok()
-            .map(|v| v / 1000.0);
-    }
-
-    // Try parsing as a plain number
-    value.parse::<f32>().ok()
-}
-
-fn format_metric(value: &str) -> String {
-    if let Some(num) = parse_resource_quantity(value) {
-        if value.ends_with("Gi") || value.ends_with("Mi") || value.ends_with("Ki") {
-            // Memory values
-            if num >= 1.0 {
-                format!("{:.1}Gi", num)
-            } else {
-                format!("{:.0}Mi", num * 1024.0)
-            }
-        } else if value.ends_with('m') || num < 1.0 {
-            // CPU values in millicores
-            format!("{:.0}m", num * 1000.0)
-        } else {
-            // CPU values in cores
-            format!("{:.1}", num)
-        }
-    } else {
-        "0".to_string()
-    }
-}
-
 #[derive(Clone)]
 struct NamespaceInfo {
     namespace: Namespace,
@@ -81,165 +25,150 @@ struct NamespaceInfo {
 
 #[derive(Clone)]
 struct NamespaceFetcher {
-    client: Client,
+    cache: ClusterCache,
     namespaces: Signal<Vec<NamespaceInfo>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl NamespaceFetcher {
-    async fn fetch_namespace_info(client: Client, ns_name: &str) -> NamespaceInfo {
-        let pods_api: Api<Pod> = Api::namespaced(client.clone(), ns_name);
-        let quotas_api: Api<K8sResourceQuota> = Api::namespaced(client.clone(), ns_name);
-        let limitranges_api: Api<K8sLimitRange> = Api::namespaced(client.clone(), ns_name);
-
-        let pods = match pods_api.list(&ListParams::default()).await {
-            Ok(pod_list) => pod_list.items,
-            Err(e) => {
-                tracing::error!("Failed to fetch pods for namespace {}: {:?}", ns_name, e);
-                Vec::new()
-            }
-        };
-
-        let resource_quota = match quotas_api.list(&ListParams::default()).await {
-            Ok(quota_list) => quota_list.items.into_iter().next(),
-            Err(e) => {
-                tracing::error!(
-                    "Failed to fetch resource quotas for namespace {}: {:?}",
-                    ns_name,
-                    e
-                );
-                None
-            }
-        };
-
-        let limit_range = match limitranges_api.list(&ListParams::default()).await {
-            Ok(lr_list) => lr_list.items.into_iter().next(),
-            Err(e) => {
-                tracing::error!(
-                    "Failed to fetch limit ranges for namespace {}: {:?}",
-                    ns_name,
-                    e
-                );
-                None
-            }
-        };
+    async fn fetch_namespace_infos(cache: &ClusterCache, namespaces: Vec<Namespace>) -> Vec<NamespaceInfo> {
+        let pods = cache.list::<Pod>().await.unwrap_or_else(|e| {
+            tracing::error!("Failed to fetch pods for namespaces: {}", e);
+            Vec::new()
+        });
+        let quotas = cache.list::<K8sResourceQuota>().await.unwrap_or_else(|e| {
+            tracing::error!("Failed to fetch resource quotas: {}", e);
+            Vec::new()
+        });
+        let limit_ranges = cache.list::<K8sLimitRange>().await.unwrap_or_else(|e| {
+            tracing::error!("Failed to fetch limit ranges: {}", e);
+            Vec::new()
+        });
 
-        NamespaceInfo {
-            namespace: Namespace::default(), // Will be filled in later
-            pods,
-            resource_quota,
-            limit_range,
-        }
+        namespaces
+            .into_iter()
+            .filter_map(|namespace| {
+                let name = namespace.metadata.name.clone()?;
+                let in_namespace = |ns: &Option<String>| ns.as_deref() == Some(name.as_str());
+                Some(NamespaceInfo {
+                    pods: pods.iter().filter(|p| in_namespace(&p.metadata.namespace)).cloned().collect(),
+                    resource_quota: quotas.iter().find(|q| in_namespace(&q.metadata.namespace)).cloned(),
+                    limit_range: limit_ranges.iter().find(|l| in_namespace(&l.metadata.namespace)).cloned(),
+                    namespace,
+                })
+            })
+            .collect()
     }
 
-    fn fetch(&self, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, query: String) {
+        let cache = self.cache.clone();
         let mut namespaces = self.namespaces.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting namespace fetch...");
+        tracing::info!("Starting namespace watch...");
 
-        spawn(async move {
-            let api = Api::<Namespace>::all(client.clone());
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
 
-            match api.list(&ListParams::default()).await {
-                Ok(namespace_list) => {
-                    let filtered_namespaces = if query.is_empty() {
-                        namespace_list.items
-                    } else {
-                        namespace_list
-                            .items
-                            .into_iter()
-                            .filter(|ns| {
-                                let name_match = ns
-                                    .metadata
-                                    .name
-                                    .as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false);
-
-                                let label_match = ns
-                                    .metadata
-                                    .labels
-                                    .as_ref()
-                                    .map(|labels| {
-                                        labels.iter().any(|(k, v)| {
-                                            k.to_lowercase().contains(&query.to_lowercase())
-                                                || v.to_lowercase().contains(&query.to_lowercase())
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.snapshots::<Namespace>());
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        let filtered_namespaces = if query.is_empty() {
+                            items
+                        } else {
+                            items
+                                .into_iter()
+                                .filter(|ns| {
+                                    let name_match = ns
+                                        .metadata
+                                        .name
+                                        .as_ref()
+                                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                                        .unwrap_or(false);
+
+                                    let label_match = ns
+                                        .metadata
+                                        .labels
+                                        .as_ref()
+                                        .map(|labels| {
+                                            labels.iter().any(|(k, v)| {
+                                                k.to_lowercase().contains(&query.to_lowercase())
+                                                    || v.to_lowercase().contains(&query.to_lowercase())
+                                            })
                                         })
-                                    })
-                                    .unwrap_or(false);
-
-                                name_match || label_match
-                            })
-                            .collect::<Vec<_>>()
-                    };
-
-                    // Fetch additional info for each namespace
-                    let mut namespace_infos = Vec::new();
-                    for ns in filtered_namespaces {
-                        if let Some(name) = &ns.metadata.name {
-                            let mut info = Self::fetch_namespace_info(client.clone(), name).await;
-                            info.namespace = ns;
-                            namespace_infos.push(info);
-                        }
-                    }
+                                        .unwrap_or(false);
 
-                    namespaces.set(namespace_infos);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch namespaces: {:?}", e);
+                                    name_match || label_match
+                                })
+                                .collect::<Vec<_>>()
+                        };
+
+                        // Attach pods, quotas and limit ranges from the cache
+                        let namespace_infos = Self::fetch_namespace_infos(&cache, filtered_namespaces).await;
+
+                        namespaces.set(namespace_infos);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch namespaces: {}", e);
+                        error.set(Some(e));
+                    }
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 
-    fn parse_quantity(quantity: Option<&Quantity>) -> String {
+    /// Quotas and limits as kubectl prints them, e.g. `500m` or `2Gi`
+    fn format_quantity(quantity: Option<&Quantity>) -> String {
         quantity
-            .map(|q| q.0.clone())
-            .unwrap_or_else(|| "0".to_string())
-    }
-
-    fn format_quantity(quantity: Option<&Quantity>, is_memory: bool) -> String {
-        let raw = quantity
-            .map(|q| q.0.clone())
-            .unwrap_or_else(|| "0".to_string());
-
-        if is_memory {
-            format_metric(&raw)
-        } else {
-            format_metric(&raw)
-        }
+            .map(ParsedQuantity::from_quantity)
+            .unwrap_or_default()
+            .to_string()
     }
 }
 
 #[component]
 pub fn Namespaces() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
     let selected_status = use_signal(|| "all");
     let search_query = use_signal(String::new);
     let namespaces = use_signal(|| Vec::<NamespaceInfo>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = NamespaceFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
                     namespaces: namespaces.clone(),
+                    watch_task,
+                    error,
                 };
                 let query = search_query();
-                fetcher.fetch(query);
+                fetcher.watch(query);
             }
         }
     });
 
     let refresh = {
-        move |_: Event<MouseData>| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = NamespaceFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
                     namespaces: namespaces.clone(),
+                    watch_task,
+                    error,
                 };
                 let query = search_query();
-                fetcher.fetch(query);
+                fetcher.watch(query);
             }
         }
     };
@@ -289,27 +218,15 @@ pub fn Namespaces() -> Element {
                 let used = quota.status.as_ref().map(|s| &s.used).unwrap_or(&None);
 
                 ResourceQuota {
-                    cpu_used: NamespaceFetcher::format_quantity(
-                        used.as_ref().and_then(|m| m.get("cpu")),
-                        false,
-                    ),
-                    cpu_limit: NamespaceFetcher::format_quantity(
-                        hard.as_ref().and_then(|m| m.get("cpu")),
-                        false,
-                    ),
-                    memory_used: NamespaceFetcher::format_quantity(
-                        used.as_ref().and_then(|m| m.get("memory")),
-                        true,
-                    ),
-                    memory_limit: NamespaceFetcher::format_quantity(
-                        hard.as_ref().and_then(|m| m.get("memory")),
-                        true,
-                    ),
+                    cpu_used: NamespaceFetcher::format_quantity(used.as_ref().and_then(|m| m.get("cpu"))),
+                    cpu_limit: NamespaceFetcher::format_quantity(hard.as_ref().and_then(|m| m.get("cpu"))),
+                    memory_used: NamespaceFetcher::format_quantity(used.as_ref().and_then(|m| m.get("memory"))),
+                    memory_limit: NamespaceFetcher::format_quantity(hard.as_ref().and_then(|m| m.get("memory"))),
                     pods_used: pod_count,
                     pods_limit: hard
                         .as_ref()
                         .and_then(|map| map.get("pods"))
-                        .and_then(|q| q.0.parse::<u32>().ok())
+                        .map(|q| ParsedQuantity::from_quantity(q).value() as u32)
                         .unwrap_or(0),
                 }
             } else {
@@ -342,8 +259,7 @@ pub fn Namespaces() -> Element {
                     .unwrap_or(&binding);
 
                 let format_limit = |m: Option<&BTreeMap<String, Quantity>>, key: &str| {
-                    let value = NamespaceFetcher::parse_quantity(m.and_then(|map| map.get(key)));
-                    format_metric(&value)
+                    NamespaceFetcher::format_quantity(m.and_then(|map| map.get(key)))
                 };
 
                 LimitRange {
@@ -377,6 +293,8 @@ pub fn Namespaces() -> Element {
         })
         .collect();
 
+    let can_create = access_context.can("create", NAMESPACES, None);
+
     rsx! {
         document::Link { rel: "stylesheet", href: NAMESPACES_CSS }
 
@@ -407,28 +325,39 @@ pub fn Namespaces() -> Element {
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-primary",
+                        disabled: !can_create,
+                        title: if can_create { "" } else { "You're not allowed to create namespaces" },
                         onclick: move |_| {
                             use_navigator().push("/namespaces/create");
                         },
                         "Create Namespace"
                     }
-                    button { class: "btn btn-secondary", onclick: refresh, "Refresh" }
+                    button { class: "btn btn-secondary", onclick: move |_| refresh(), "Refresh" }
                 }
             }
 
-            div { class: "namespaces-grid",
-                {filtered_namespaces.iter().map(|(props, _name)| rsx!(
-                    NamespaceItem {
-                        name: props.name.clone(),
-                        status: props.status.clone(),
-                        age: props.age.clone(),
-                        labels: props.labels.clone(),
-                        pod_count: props.pod_count,
-                        resource_quota: props.resource_quota.clone(),
-                        limit_range: props.limit_range.clone(),
-                        phase: props.phase.clone(),
-                    }
-                ))}
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_namespaces.is_empty() {
+                EmptyState {
+                    message: "No namespaces found",
+                    hint: (!search_query().is_empty()).then(|| "Try clearing the search".to_string())
+                }
+            } else {
+                div { class: "namespaces-grid",
+                    {filtered_namespaces.iter().map(|(props, _name)| rsx!(
+                        NamespaceItem {
+                            name: props.name.clone(),
+                            status: props.status.clone(),
+                            age: props.age.clone(),
+                            labels: props.labels.clone(),
+                            pod_count: props.pod_count,
+                            resource_quota: props.resource_quota.clone(),
+                            limit_range: props.limit_range.clone(),
+                            phase: props.phase.clone(),
+                        }
+                    ))}
+                }
             }
         }
     }
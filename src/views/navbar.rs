@@ -1,6 +1,9 @@
-use crate::{Route, contexts::{FilePathsContext, ClientReloadContext, KubeconfigStorage}};
+use crate::{Route, contexts::{AccessContext, FilePathsContext, ClientReloadContext, KubeContext, KubeconfigStorage, PrometheusContext, list_contexts}};
+use crate::k8s::{self, AccessKind};
+use crate::components::ConnectionStatusPill;
 use crate::components::kubeconfig_name_dialog::KubeconfigNameDialog;
-use crate::utils::file_utils;
+use crate::components::vault_dialog::{VaultDialog, VaultDialogMode};
+use crate::utils::config;
 use dioxus::{logger::tracing, prelude::*};
 use kube::Client;
 
@@ -24,6 +27,8 @@ const PVC: Asset = asset!("/assets/images/pvc.svg");
 const CONFIGMAP: Asset = asset!("/assets/images/configmap.svg");
 const SECRET: Asset = asset!("/assets/images/secret.svg");
 const INSIGHTS: Asset = asset!("/assets/images/insights.svg");
+const APPLY: Asset = asset!("/assets/images/apply.svg");
+const API_RESOURCES: Asset = asset!("/assets/images/api_resources.svg");
 
 // Navigation item data structure
 #[derive(Clone)]
@@ -32,6 +37,8 @@ struct NavItem {
     icon: Asset,
     label: &'static str,
     class: &'static str,
+    /// Kind the page lists, disabling the entry when it can't be listed anywhere
+    access: Option<AccessKind>,
 }
 
 #[component]
@@ -40,6 +47,8 @@ pub fn Navbar() -> Element {
     let file_paths_context = use_context::<FilePathsContext>();
     let client_reload_context = use_context::<ClientReloadContext>();
     let kubeconfig_storage = use_context::<KubeconfigStorage>();
+    let prometheus_settings = use_context::<PrometheusContext>().settings;
+    let access_context = use_context::<AccessContext>();
     let client_signal = try_use_context::<Signal<Option<Client>>>();
     
     let has_client = client_signal
@@ -55,6 +64,35 @@ pub fn Navbar() -> Element {
         let paths = filenames.read();
         paths.first().cloned().unwrap_or_default()
     });
+    // Context inside the selected kubeconfig, `None` when the file can't be parsed
+    let mut selected_kube_context = use_signal(|| None::<String>);
+    // Bumped whenever a stored kubeconfig's content changes, so its contexts are re-read
+    let mut registry_revision = use_signal(|| 0u32);
+    let mut show_manage = use_signal(|| false);
+    let mut rename_target = use_signal(|| None::<String>);
+    let mut pending_remove = use_signal(|| None::<String>);
+    let mut registry_error = use_signal(|| None::<String>);
+    // Ask for the passphrase on startup when stored kubeconfigs are encrypted
+    let mut vault_dialog = use_signal({
+        let vault = kubeconfig_storage.vault().clone();
+        move || (vault.is_enabled() && !vault.is_unlocked()).then_some(VaultDialogMode::Unlock)
+    });
+    let mut vault_error = use_signal(|| None::<String>);
+
+    // Contexts of every loaded kubeconfig, or why it couldn't be read
+    let file_contexts = use_memo({
+        let storage = kubeconfig_storage.clone();
+        let client_revision = client_reload_context.revision;
+        move || {
+            // Also re-read when the active kubeconfig is rewritten on disk
+            let _ = (registry_revision(), client_revision());
+            filenames
+                .read()
+                .iter()
+                .map(|file| (file.clone(), list_contexts(file, &storage).map_err(|e| e.to_string())))
+                .collect::<Vec<(String, Result<Vec<KubeContext>, String>)>>()
+        }
+    });
 
     // Effects
     use_effect({
@@ -73,43 +111,89 @@ pub fn Navbar() -> Element {
         }
     });
 
+    // Fall back to the file's current-context (or its first one) when the selection isn't in it
+    use_effect(move || {
+        let file = selected_context();
+        let contexts = file_contexts.read();
+        let Some((_, Ok(contexts))) = contexts.iter().find(|(name, _)| *name == file) else {
+            selected_kube_context.set(None);
+            return;
+        };
+        let selected = selected_kube_context.peek().clone();
+        if !contexts.iter().any(|c| Some(&c.name) == selected.as_ref()) {
+            let fallback = contexts.iter().find(|c| c.is_current).or(contexts.first());
+            selected_kube_context.set(fallback.map(|c| c.name.clone()));
+        }
+    });
+
     use_effect({
         let mut current_path = client_reload_context.current_path;
+        let mut current_context = client_reload_context.current_context;
         move || {
             let new_path = selected_context();
+            let new_context = selected_kube_context();
             if !new_path.is_empty() {
-                tracing::info!("Context changed to: {}", new_path);
+                tracing::info!("Context changed to: {} ({:?})", new_path, new_context);
                 current_path.set(new_path);
+                current_context.set(new_context);
             }
         }
     });
 
+    // Every (file, context) pair the selector offers; files that failed to parse get a single entry
+    let choices: Vec<(String, Option<String>)> = file_contexts
+        .read()
+        .iter()
+        .flat_map(|(file, contexts)| match contexts {
+            Ok(contexts) if !contexts.is_empty() => contexts
+                .iter()
+                .map(|c| (file.clone(), Some(c.name.clone())))
+                .collect::<Vec<_>>(),
+            _ => vec![(file.clone(), None)],
+        })
+        .collect();
+    let selected_details = file_contexts.read().iter().find_map(|(file, contexts)| {
+        if *file != selected_context() {
+            return None;
+        }
+        match contexts {
+            Ok(contexts) => contexts
+                .iter()
+                .find(|c| Some(&c.name) == selected_kube_context.read().as_ref())
+                .cloned()
+                .map(Ok),
+            Err(e) => Some(Err(e.clone())),
+        }
+    });
+
     // Navigation items configuration
     let cluster_nav_items = vec![
-        NavItem { route: Route::Home {}, icon: OVERVIEW, label: "Overview", class: "nav-overview" },
-        NavItem { route: Route::Insights {}, icon: INSIGHTS, label: "Insights", class: "nav-insights" },
-        NavItem { route: Route::Nodes {}, icon: NODES, label: "Nodes", class: "nav-nodes" },
-        NavItem { route: Route::Namespaces {}, icon: NAMESPACE, label: "Namespaces", class: "nav-namespaces" },
+        NavItem { route: Route::Home {}, icon: OVERVIEW, label: "Overview", class: "nav-overview", access: None },
+        NavItem { route: Route::Insights {}, icon: INSIGHTS, label: "Insights", class: "nav-insights", access: None },
+        NavItem { route: Route::Nodes {}, icon: NODES, label: "Nodes", class: "nav-nodes", access: Some(k8s::NODES) },
+        NavItem { route: Route::Namespaces {}, icon: NAMESPACE, label: "Namespaces", class: "nav-namespaces", access: Some(k8s::NAMESPACES) },
+        NavItem { route: Route::ApiResources {}, icon: API_RESOURCES, label: "API Resources", class: "nav-api-resources", access: None },
+        NavItem { route: Route::ApplyManifest {}, icon: APPLY, label: "Apply Manifest", class: "nav-apply", access: None },
     ];
 
     let workload_nav_items = vec![
-        NavItem { route: Route::Pods {}, icon: POD, label: "Pods", class: "nav-pods" },
-        NavItem { route: Route::Deployments {}, icon: DEPLOYMENT, label: "Deployments", class: "nav-deployments" },
-        NavItem { route: Route::StatefulSets {}, icon: STATEFULSETS, label: "StatefulSets", class: "nav-statefulsets" },
-        NavItem { route: Route::DaemonSets {}, icon: DAEMONSETS, label: "DaemonSets", class: "nav-daemonsets" },
-        NavItem { route: Route::CronJobs {}, icon: CRONJOB, label: "CronJobs", class: "nav-cronjobs" },
-        NavItem { route: Route::Jobs {}, icon: JOB, label: "Jobs", class: "nav-jobs" },
+        NavItem { route: Route::Pods {}, icon: POD, label: "Pods", class: "nav-pods", access: Some(k8s::PODS) },
+        NavItem { route: Route::Deployments {}, icon: DEPLOYMENT, label: "Deployments", class: "nav-deployments", access: Some(k8s::DEPLOYMENTS) },
+        NavItem { route: Route::StatefulSets {}, icon: STATEFULSETS, label: "StatefulSets", class: "nav-statefulsets", access: Some(k8s::STATEFULSETS) },
+        NavItem { route: Route::DaemonSets {}, icon: DAEMONSETS, label: "DaemonSets", class: "nav-daemonsets", access: Some(k8s::DAEMONSETS) },
+        NavItem { route: Route::CronJobs {}, icon: CRONJOB, label: "CronJobs", class: "nav-cronjobs", access: Some(k8s::CRONJOBS) },
+        NavItem { route: Route::Jobs {}, icon: JOB, label: "Jobs", class: "nav-jobs", access: Some(k8s::JOBS) },
     ];
 
     let network_nav_items = vec![
-        NavItem { route: Route::Services {}, icon: SERVICE, label: "Services", class: "nav-services" },
-        NavItem { route: Route::Ingresses {}, icon: INGRESS, label: "Ingress", class: "nav-ingress" },
+        NavItem { route: Route::Services {}, icon: SERVICE, label: "Services", class: "nav-services", access: Some(k8s::SERVICES) },
+        NavItem { route: Route::Ingresses {}, icon: INGRESS, label: "Ingress", class: "nav-ingress", access: Some(k8s::INGRESSES) },
     ];
 
     let storage_nav_items = vec![
-        NavItem { route: Route::Pvcs {}, icon: PVC, label: "Persistent Volume Claims", class: "nav-pvcs" },
-        NavItem { route: Route::ConfigMaps {}, icon: CONFIGMAP, label: "Config Maps", class: "nav-configmaps" },
-        NavItem { route: Route::Secrets {}, icon: SECRET, label: "Secrets", class: "nav-secrets" },
+        NavItem { route: Route::Pvcs {}, icon: PVC, label: "Persistent Volume Claims", class: "nav-pvcs", access: Some(k8s::PVCS) },
+        NavItem { route: Route::ConfigMaps {}, icon: CONFIGMAP, label: "Config Maps", class: "nav-configmaps", access: Some(k8s::CONFIGMAPS) },
+        NavItem { route: Route::Secrets {}, icon: SECRET, label: "Secrets", class: "nav-secrets", access: Some(k8s::SECRETS) },
     ];
 
     // Helper function to render navigation group
@@ -119,22 +203,47 @@ pub fn Navbar() -> Element {
                 if !is_collapsed() {
                     span { class: "nav-group-title", "{title}" }
                 }
-                {items.iter().map(|item| rsx! {
-                    Link {
-                        key: "{item.label}",
-                        to: item.route.clone(),
-                        class: "{item.class}",
-                        title: if is_collapsed() { item.label } else { "" },
-                        img { src: "{item.icon}", alt: "", class: "nav-icon" }
-                        if !is_collapsed() {
-                            span { class: "nav-text", "{item.label}" }
-                        }
+                {items.iter().map(|item| {
+                    let denied = item.access.filter(|kind| !access_context.can_anywhere("list", *kind));
+                    match denied {
+                        Some(kind) => rsx! {
+                            span {
+                                key: "{item.label}",
+                                class: "{item.class} nav-disabled",
+                                title: kind.denied_message("list"),
+                                img { src: "{item.icon}", alt: "", class: "nav-icon" }
+                                if !is_collapsed() {
+                                    span { class: "nav-text", "{item.label}" }
+                                }
+                            }
+                        },
+                        None => rsx! {
+                            Link {
+                                key: "{item.label}",
+                                to: item.route.clone(),
+                                class: "{item.class}",
+                                title: if is_collapsed() { item.label } else { "" },
+                                img { src: "{item.icon}", alt: "", class: "nav-icon" }
+                                if !is_collapsed() {
+                                    span { class: "nav-text", "{item.label}" }
+                                }
+                            }
+                        },
                     }
                 })}
             }
         }
     };
 
+    // Re-read a stored kubeconfig whose file was replaced, reconnecting if it's the one in use
+    let mut kubeconfig_changed = move |name: &str| {
+        registry_revision += 1;
+        if *selected_context.peek() == name {
+            let mut revision = client_reload_context.revision;
+            revision += 1;
+        }
+    };
+
     // File upload handler
     let handle_file_upload = move |evt: Event<FormData>| {
         let mut dialog_state = dialog_state.clone();
@@ -155,32 +264,108 @@ pub fn Navbar() -> Element {
     };
 
     // Dialog close handler
-    let handle_dialog_close = move |result: Option<String>| {
-        if let Some(name) = result {
-            tracing::info!("Kubeconfig context named: {}", name);
-            
-            // Get the file content from dialog state
-            if let Some((_, file_content)) = dialog_state() {
-                match file_utils::save_kubeconfig_file(&name, &file_content) {
-                    Ok(file_path) => {
-                        if let Err(e) = kubeconfig_storage.store_file_path(name.clone(), file_path) {
-                            tracing::error!("Failed to store kubeconfig file path: {}", e);
-                            return;
+    let handle_dialog_close = {
+        let kubeconfig_storage = kubeconfig_storage.clone();
+        move |result: Option<String>| {
+            if let Some(name) = result {
+                tracing::info!("Kubeconfig context named: {}", name);
+                
+                // Get the file content from dialog state
+                if let Some((_, file_content)) = dialog_state() {
+                    match kubeconfig_storage.save_kubeconfig(&name, &file_content) {
+                        Ok(()) => {
+                            registry_error.set(None);
+                            if filenames.read().contains(&name) {
+                                // Uploading under an existing name replaces that kubeconfig
+                                kubeconfig_changed(&name);
+                            } else {
+                                filenames.write().push(name.clone());
+                                // Start on the new file's own current-context
+                                selected_kube_context.set(None);
+                                selected_context.set(name);
+                            }
                         }
-                        
-                        let mut current_files = filenames.write();
-                        if !current_files.contains(&name) {
-                            current_files.push(name.clone());
-                            selected_context.set(name);
+                        Err(e) => {
+                            tracing::error!("Failed to save kubeconfig file: {}", e);
+                            registry_error.set(Some(e.to_string()));
                         }
                     }
-                    Err(e) => tracing::error!("Failed to save kubeconfig file: {}", e),
                 }
             }
+            dialog_state.set(None);
+            input_key += 1;
+        }
+    };
+
+    // Rename dialog close handler
+    let handle_rename_close = {
+        let kubeconfig_storage = kubeconfig_storage.clone();
+        let prometheus_settings = prometheus_settings.clone();
+        move |result: Option<String>| {
+            let (Some(new_name), Some(old_name)) = (result, rename_target()) else {
+                rename_target.set(None);
+                return;
+            };
+            let new_name = new_name.trim().to_string();
+            if new_name != old_name {
+                match kubeconfig_storage.rename(&old_name, &new_name) {
+                    Ok(()) => {
+                        if let Err(e) = prometheus_settings.rename_kubeconfig(&old_name, &new_name) {
+                            tracing::error!("Failed to move Prometheus settings of {}: {}", old_name, e);
+                        }
+                        for file in filenames.write().iter_mut().filter(|file| **file == old_name) {
+                            *file = new_name.clone();
+                        }
+                        if selected_context() == old_name {
+                            selected_context.set(new_name);
+                        }
+                        registry_error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to rename kubeconfig {}: {}", old_name, e);
+                        registry_error.set(Some(e.to_string()));
+                    }
+                }
+            }
+            rename_target.set(None);
+        }
+    };
+
+    // Passphrase dialog handler, for both unlocking and first-time setup
+    let handle_vault_submit = {
+        let kubeconfig_storage = kubeconfig_storage.clone();
+        move |passphrase: String| {
+            let Some(mode) = vault_dialog() else { return };
+            let result = match mode {
+                VaultDialogMode::Unlock => kubeconfig_storage.unlock(&passphrase),
+                VaultDialogMode::Enable => kubeconfig_storage.enable_encryption(&passphrase),
+            };
+            match result {
+                Ok(migrated) => {
+                    if migrated > 0 {
+                        tracing::info!("Encrypted {} plaintext kubeconfig(s)", migrated);
+                    }
+                    vault_dialog.set(None);
+                    vault_error.set(None);
+                    // Stored kubeconfigs are readable now, and their paths may have moved
+                    registry_revision += 1;
+                    let mut revision = client_reload_context.revision;
+                    revision += 1;
+                }
+                Err(e) => vault_error.set(Some(e.to_string())),
+            }
         }
-        dialog_state.set(None);
-        input_key += 1;
     };
+    let vault_enabled = kubeconfig_storage.vault().is_enabled();
+    let vault_unlocked = kubeconfig_storage.vault().is_unlocked();
+
+    // Stored kubeconfigs, everything but the default one
+    let stored_names: Vec<String> = filenames
+        .read()
+        .iter()
+        .filter(|name| name.as_str() != config::DEFAULT_KUBECONFIG)
+        .cloned()
+        .collect();
 
     rsx! {
         document::Link { rel: "stylesheet", href: NAVBAR_CSS }
@@ -193,6 +378,26 @@ pub fn Navbar() -> Element {
             }
         }
 
+        if let Some(mode) = vault_dialog() {
+            VaultDialog {
+                mode,
+                error: vault_error(),
+                on_submit: handle_vault_submit,
+                on_cancel: move |_| {
+                    vault_dialog.set(None);
+                    vault_error.set(None);
+                }
+            }
+        }
+
+        if let Some(name) = rename_target() {
+            KubeconfigNameDialog {
+                original_filename: name.clone(),
+                title: "Rename Kubeconfig".to_string(),
+                on_close: handle_rename_close
+            }
+        }
+
         div { class: "layout-container",
             div { 
                 id: "sidebar", 
@@ -224,30 +429,82 @@ pub fn Navbar() -> Element {
                                 select {
                                     id: "cluster-select",
                                     class: "cluster-dropdown",
-                                    value: "{selected_context}",
-                                    onchange: move |evt| {
-                                        let new_selection = evt.value();
-                                        tracing::info!("User selected context: {}", new_selection);
-                                        selected_context.set(new_selection);
+                                    onchange: {
+                                        let choices = choices.clone();
+                                        move |evt: Event<FormData>| {
+                                            let Some((file, context)) = evt.value().parse::<usize>().ok().and_then(|i| choices.get(i)) else {
+                                                return;
+                                            };
+                                            tracing::info!("User selected context: {} ({:?})", file, context);
+                                            selected_kube_context.set(context.clone());
+                                            selected_context.set(file.clone());
+                                        }
                                     },
                                     option { 
                                         value: "", 
                                         disabled: true, 
                                         selected: selected_context.read().is_empty(),
-                                        "Select kubeconfig file"
+                                        "Select kubeconfig context"
                                     }
-                                    {filenames.read().iter().map(|filepath| {
-                                        let display_name = filepath.split('/').last().unwrap_or(filepath);
+                                    {file_contexts.read().iter().map(|(filepath, contexts)| {
+                                        let display_name = filepath.split('/').last().unwrap_or(filepath).to_string();
+                                        let options: Vec<(usize, String, String)> = choices
+                                            .iter()
+                                            .enumerate()
+                                            .filter(|(_, (file, _))| file == filepath)
+                                            .map(|(index, (_, context))| {
+                                                let found = contexts.as_ref().ok().and_then(|all| {
+                                                    all.iter().find(|c| Some(&c.name) == context.as_ref())
+                                                });
+                                                match found {
+                                                    Some(c) => (
+                                                        index,
+                                                        format!("{} · {}", c.name, c.cluster),
+                                                        format!(
+                                                            "cluster: {}\nuser: {}\nnamespace: {}",
+                                                            c.cluster,
+                                                            c.user.as_deref().unwrap_or("-"),
+                                                            c.namespace.as_deref().unwrap_or("default")
+                                                        ),
+                                                    ),
+                                                    None => (index, display_name.clone(), filepath.clone()),
+                                                }
+                                            })
+                                            .collect();
                                         rsx! {
-                                            option { 
-                                                key: "{filepath}", 
-                                                value: "{filepath}",
-                                                title: "{filepath}",
-                                                "{display_name}"
+                                            optgroup {
+                                                key: "{filepath}",
+                                                label: "{display_name}",
+                                                {options.into_iter().map(|(index, label, title)| {
+                                                    let (file, context) = &choices[index];
+                                                    let is_selected = *file == selected_context() && *context == selected_kube_context();
+                                                    rsx! {
+                                                        option {
+                                                            key: "{index}",
+                                                            value: "{index}",
+                                                            title: "{title}",
+                                                            selected: is_selected,
+                                                            "{label}"
+                                                        }
+                                                    }
+                                                })}
                                             }
                                         }
                                     })}
                                 }
+
+                                {selected_details.map(|details| match details {
+                                    Ok(context) => rsx! {
+                                        div { class: "context-details",
+                                            div { span { class: "context-details-key", "Cluster" } span { "{context.cluster}" } }
+                                            div { span { class: "context-details-key", "User" } span { {context.user.unwrap_or_else(|| "-".to_string())} } }
+                                            div { span { class: "context-details-key", "Namespace" } span { {context.namespace.unwrap_or_else(|| "default".to_string())} } }
+                                        }
+                                    },
+                                    Err(e) => rsx! {
+                                        div { class: "context-details context-details-error", title: "{e}", "Couldn't read contexts from this kubeconfig" }
+                                    },
+                                })}
                                 
                                 if !filenames.read().is_empty() {
                                     div { class: "file-count-info",
@@ -272,9 +529,134 @@ pub fn Navbar() -> Element {
                                 role: "button",
                                 "Add Kubeconfig"
                             }
+
+                            {registry_error().map(|e| rsx! {
+                                div { class: "kubeconfig-registry-error", "{e}" }
+                            })}
+
+                            if vault_enabled && !vault_unlocked {
+                                button {
+                                    class: "manage-kubeconfigs-toggle",
+                                    onclick: move |_| vault_dialog.set(Some(VaultDialogMode::Unlock)),
+                                    "🔒 Unlock stored kubeconfigs"
+                                }
+                            }
+
+                            if !stored_names.is_empty() {
+                                button {
+                                    class: "manage-kubeconfigs-toggle",
+                                    onclick: move |_| {
+                                        show_manage.toggle();
+                                        pending_remove.set(None);
+                                    },
+                                    if show_manage() { "Hide stored kubeconfigs" } else { "Manage stored kubeconfigs ({stored_names.len()})" }
+                                }
+                            }
+
+                            if show_manage() && !stored_names.is_empty() {
+                                div { class: "kubeconfig-registry",
+                                    {stored_names.iter().enumerate().map(|(index, name)| {
+                                        let replace_id = format!("kubeconfig-replace-{}", index);
+                                        let is_pending_remove = pending_remove.read().as_ref() == Some(name);
+                                        let rename_name = name.clone();
+                                        let replace_name = name.clone();
+                                        let remove_name = name.clone();
+                                        let replace_storage = kubeconfig_storage.clone();
+                                        let remove_storage = kubeconfig_storage.clone();
+                                        let remove_prometheus = prometheus_settings.clone();
+                                        rsx! {
+                                            div { key: "{name}", class: "kubeconfig-registry-item",
+                                                span { class: "kubeconfig-registry-name", title: "{name}", "{name}" }
+                                                button {
+                                                    class: "kubeconfig-registry-action",
+                                                    title: "Rename",
+                                                    onclick: move |_| rename_target.set(Some(rename_name.clone())),
+                                                    "✎"
+                                                }
+                                                input {
+                                                    key: "{replace_id}-{input_key()}",
+                                                    r#type: "file",
+                                                    accept: ".yaml,.kubeconfig,.yml",
+                                                    id: "{replace_id}",
+                                                    hidden: true,
+                                                    onchange: move |evt: Event<FormData>| {
+                                                        let name = replace_name.clone();
+                                                        let storage = replace_storage.clone();
+                                                        spawn(async move {
+                                                            let Some(file_engine) = evt.files() else { return };
+                                                            let Some(file_name) = file_engine.files().first().cloned() else { return };
+                                                            let Some(content) = file_engine.read_file_to_string(&file_name).await else {
+                                                                tracing::error!("Failed to read file {}", file_name);
+                                                                return;
+                                                            };
+                                                            match storage.save_kubeconfig(&name, &content) {
+                                                                Ok(()) => {
+                                                                    tracing::info!("Replaced kubeconfig {} with {}", name, file_name);
+                                                                    registry_error.set(None);
+                                                                    kubeconfig_changed(&name);
+                                                                }
+                                                                Err(e) => registry_error.set(Some(format!("Failed to replace {}: {}", name, e))),
+                                                            }
+                                                            input_key += 1;
+                                                        });
+                                                    }
+                                                }
+                                                label {
+                                                    r#for: "{replace_id}",
+                                                    class: "kubeconfig-registry-action",
+                                                    title: "Replace with another file",
+                                                    role: "button",
+                                                    "⟳"
+                                                }
+                                                button {
+                                                    class: if is_pending_remove { "kubeconfig-registry-action confirm" } else { "kubeconfig-registry-action" },
+                                                    title: if is_pending_remove { "Click again to remove and delete the stored file" } else { "Remove" },
+                                                    onclick: move |_| {
+                                                        if pending_remove.read().as_ref() != Some(&remove_name) {
+                                                            pending_remove.set(Some(remove_name.clone()));
+                                                            return;
+                                                        }
+                                                        match remove_storage.remove(&remove_name) {
+                                                            Ok(()) => {
+                                                                if let Err(e) = remove_prometheus.remove_kubeconfig(&remove_name) {
+                                                                    tracing::error!("Failed to forget Prometheus settings of {}: {}", remove_name, e);
+                                                                }
+                                                                filenames.write().retain(|file| *file != remove_name);
+                                                                registry_error.set(None);
+                                                            }
+                                                            Err(e) => {
+                                                                tracing::error!("Failed to remove kubeconfig {}: {}", remove_name, e);
+                                                                registry_error.set(Some(e.to_string()));
+                                                            }
+                                                        }
+                                                        pending_remove.set(None);
+                                                    },
+                                                    if is_pending_remove { "Remove?" } else { "✕" }
+                                                }
+                                            }
+                                        }
+                                    })}
+                                    div { class: "kubeconfig-registry-footer",
+                                        if vault_enabled {
+                                            span { title: "Stored kubeconfigs are encrypted with your passphrase", "🔒 Encrypted at rest" }
+                                        } else {
+                                            button {
+                                                class: "kubeconfig-registry-action",
+                                                title: "Encrypt stored kubeconfigs with a passphrase",
+                                                onclick: move |_| vault_dialog.set(Some(VaultDialogMode::Enable)),
+                                                "🔓 Encrypt with a passphrase"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
                     
+                    if has_client {
+                        ConnectionStatusPill { collapsed: is_collapsed() }
+                    }
+
                     // Navigation sections
                     if has_client {
                         {render_nav_group("CLUSTER", &cluster_nav_items)}
@@ -1,11 +1,12 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::{api::core::v1::{Node, Pod}, apimachinery::pkg::api::resource::Quantity};
-use kube::{api::{ListParams, Api}, Client};
+use futures::StreamExt;
 use std::collections::{BTreeMap, HashMap};
 
-use crate::k8s::{fetch_node_metrics, parse_resource_quantity};
+use crate::contexts::error::AppError;
+use crate::k8s::{ClusterCache, MetricsHistory, MetricsSeries, ParsedQuantity};
 
-use crate::components::NodeItem;
+use crate::components::{EmptyState, InlineError, MetricsSourceNote, NodeItem};
 
 const NODES_CSS: Asset = asset!("/assets/styling/nodes.css");
 
@@ -17,63 +18,70 @@ struct NodeInfo {
 
 #[derive(Clone)]
 struct NodeFetcher {
-    client: Client,
+    cache: ClusterCache,
     nodes: Signal<Vec<NodeInfo>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl NodeFetcher {
-    async fn fetch_node_info(client: Client, node: Node) -> NodeInfo {
-        let pods_api: Api<Pod> = Api::all(client.clone());
-        let node_name = node.metadata.name.as_deref().unwrap_or_default();
-        
-        let pods = match pods_api.list(&ListParams::default()).await {
-            Ok(pod_list) => pod_list.items.into_iter()
-                .filter(|pod| pod.spec.as_ref()
-                    .and_then(|spec| spec.node_name.as_ref())
-                    .map(|name| name == node_name)
-                    .unwrap_or(false))
-                .collect(),
+    async fn fetch_node_infos(cache: &ClusterCache, nodes: Vec<Node>) -> Vec<NodeInfo> {
+        // Group the cached pods by the node they are scheduled on
+        let mut pods_by_node = match cache.list::<Pod>().await {
+            Ok(pod_list) => pod_list.into_iter()
+                .fold(HashMap::<String, Vec<Pod>>::new(), |mut acc, pod| {
+                    if let Some(node_name) = pod.spec.as_ref().and_then(|spec| spec.node_name.clone()) {
+                        acc.entry(node_name).or_default().push(pod);
+                    }
+                    acc
+                }),
             Err(e) => {
-                tracing::error!("Failed to fetch pods for node {}: {:?}", node_name, e);
-                Vec::new()
+                tracing::error!("Failed to fetch pods for nodes: {}", e);
+                HashMap::new()
             }
         };
 
-        NodeInfo {
-            node,
-            pods,
-        }
+        nodes.into_iter()
+            .map(|node| {
+                let node_name = node.metadata.name.clone().unwrap_or_default();
+                NodeInfo {
+                    pods: pods_by_node.remove(&node_name).unwrap_or_default(),
+                    node,
+                }
+            })
+            .collect()
     }
 
-    fn fetch(&self) {
-        let client = self.client.clone();
+    fn watch(&self) {
+        let cache = self.cache.clone();
         let mut nodes = self.nodes.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting node fetch...");
+        tracing::info!("Starting node watch...");
 
-        spawn(async move {
-            let nodes_api = Api::<Node>::all(client.clone());
-            
-            // Fetch both nodes and metrics in parallel
-            let node_list = nodes_api.list(&ListParams::default()).await;
-
-            match node_list {
-                Ok(node_list) => {
-                    let mut node_infos = Vec::new();
-                    for node in node_list.items {
-                        let info = Self::fetch_node_info(client.clone(), node).await;
-                        node_infos.push(info);
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.snapshots::<Node>());
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(node_list) => {
+                        let node_infos = Self::fetch_node_infos(&cache, node_list).await;
+                        nodes.set(node_infos);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch nodes: {}", e);
+                        error.set(Some(e));
                     }
-                    nodes.set(node_infos);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch nodes: {:?}", e);
                 }
             }
         });
+        watch_task.set(Some(task));
     }
-
-    // Moved to k8s::node_metrics::parse_resource_quantity
 }
 
 #[derive(Clone)]
@@ -93,53 +101,47 @@ struct NodeData {
 
 #[component]
 pub fn Nodes() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
     let mut selected_node = use_signal(|| String::from("all"));
     let search_query = use_signal(String::new);
     let nodes = use_signal(|| Vec::<NodeInfo>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = NodeFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
                     nodes: nodes.clone(),
+                    watch_task,
+                    error,
                 };
-                fetcher.fetch();
+                fetcher.watch();
             }
         }
     });
 
     let refresh = {
-        move |_: Event<MouseData>| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = NodeFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
                     nodes: nodes.clone(),
+                    watch_task,
+                    error,
                 };
-                fetcher.fetch();
+                fetcher.watch();
             }
         }
     };
 
-    // Use a signal to track metrics and fetch them asynchronously
-    let metrics_map = use_signal(|| HashMap::<String, crate::k8s::node_metrics::NodeMetrics>::new());
-
-    // Fetch metrics asynchronously when client changes
-    use_effect({
-        let mut metrics_map = metrics_map.clone();
-        move || {
-            if let Some(client) = &*client_signal.read() {
-                let client = client.clone();
-                spawn(async move {
-                    let metrics = fetch_node_metrics(&client).await;
-                    metrics_map.set(metrics);
-                });
-            } else {
-                metrics_map.set(HashMap::new());
-            }
-        }
-    });
+    // Usage comes from the background sampler's newest round
+    let metrics_history = use_context::<Signal<MetricsHistory>>();
+    let (metrics, metrics_error) = {
+        let history = metrics_history.read();
+        (history.current(), history.error().cloned())
+    };
 
     // Convert k8s Node objects to our display format
     let node_data: Vec<NodeData> = nodes()
@@ -193,10 +195,6 @@ pub fn Nodes() -> Element {
 
             // Calculate resource usage
             let binding = std::collections::BTreeMap::<String, Quantity>::new();
-            let allocatable = node.status.as_ref()
-                .and_then(|status| status.allocatable.as_ref())
-                .unwrap_or(&binding);
-
             let capacity = node.status.as_ref()
                 .and_then(|status| status.capacity.as_ref())
                 .unwrap_or(&binding);
@@ -208,59 +206,33 @@ pub fn Nodes() -> Element {
             let current_pods = node_info.pods.len() as u32;
 
             // Use the pre-fetched metrics
-            let binding = BTreeMap::new();
-            let metrics_map_value = metrics_map();
-            let metrics = metrics_map_value
-                .get(&name)
-                .map(|m| &m.usage)
-                .unwrap_or(&binding);
-
-            // Calculate CPU usage
-            let cpu_total = parse_resource_quantity(&capacity.get("cpu")
-                .map(|q| q.0.clone())
-                .unwrap_or_else(|| "0".into()));
+            let sample = metrics.as_ref()
+                .and_then(|m| m.samples.get(&MetricsSeries::Node(name.clone())));
 
-            let cpu_used = parse_resource_quantity(&metrics.get("cpu")
-                .map(|q| q.0.clone())
-                .unwrap_or_else(|| "0".into()));
-
-            let cpu_usage = if cpu_total > 0.0 {
-                ((cpu_used / cpu_total * 100.0).min(100.0) * 100.0).round() / 100.0
-            } else {
-                0.0
+            let quantity = |resources: &BTreeMap<String, Quantity>, key: &str| {
+                resources.get(key).map(ParsedQuantity::from_quantity).unwrap_or_default().as_f64()
+            };
+            // Percentage of the total, capped at 100 and kept to two decimals
+            let usage_percent = |used: f64, total: f64| {
+                if total > 0.0 {
+                    ((used / total * 100.0).min(100.0) * 100.0).round() / 100.0
+                } else {
+                    0.0
+                }
             };
 
+            // Calculate CPU usage
+            let cpu_usage = usage_percent(sample.map(|s| s.cpu).unwrap_or_default(), quantity(capacity, "cpu"));
+
             // Calculate memory usage
-            let memory_total = parse_resource_quantity(&capacity.get("memory")
-                .map(|q| q.0.clone())
-                .unwrap_or_else(|| "0".into()));
-            let memory_used = parse_resource_quantity(&metrics.get("memory")
-                .map(|q| q.0.clone())
-                .unwrap_or_else(|| "0".into()));
-            let memory_usage = if memory_total > 0.0 {
-                ((memory_used / memory_total * 100.0).min(100.0) * 100.0).round() / 100.0
-            } else {
-                0.0
-            };
+            let memory_usage = usage_percent(sample.map(|s| s.memory).unwrap_or_default(), quantity(capacity, "memory"));
 
-            // Calculate storage usage (still based on capacity since it's not in metrics)
-            let storage_total = parse_resource_quantity(&capacity.get("ephemeral-storage")
-                .map(|q| q.0.clone())
-                .unwrap_or_else(|| "0".into()));
-            let storage_used = if let Some(fs) = metrics.get("ephemeral-storage") {
-                parse_resource_quantity(&fs.0)
-            } else {
-                // Fallback to capacity-allocatable if metrics aren't available
-                let storage_allocatable = parse_resource_quantity(&allocatable.get("ephemeral-storage")
-                    .map(|q| q.0.clone())
-                    .unwrap_or_else(|| "0".into()));
-                storage_total - storage_allocatable
-            };
-            let storage_usage = if storage_total > 0.0 {
-                ((storage_used / storage_total * 100.0).min(100.0) * 100.0).round() / 100.0
-            } else {
-                0.0
-            };
+            // Storage is the root filesystem as the kubelet reports it, unknown without it
+            let storage_usage = metrics.as_ref()
+                .and_then(|m| m.filesystems.as_ref())
+                .and_then(|fs| fs.nodes.get(&name))
+                .map(|fs| usage_percent(fs.used, fs.capacity))
+                .unwrap_or_default();
 
             NodeData {
                 name,
@@ -331,42 +303,53 @@ pub fn Nodes() -> Element {
                             option { value: "master", "Master Nodes ({node_data.iter().filter(|n| n.node_type == \"master\").count()})" }
                         }
                         span { class: "node-count", "{filtered_nodes.len()} nodes selected" }
+                        MetricsSourceNote {}
                     }
                 }
                 div { class: "header-actions",
-                    button { class: "btn btn-secondary", onclick: refresh, "Refresh" }
+                    button { class: "btn btn-secondary", onclick: move |_| refresh(), "Refresh" }
                 }
             }
 
-            div { class: "nodes-grid",
-                {filtered_nodes.iter().map(|node| {
-                    let binding = nodes();
-                    let original_node = binding.iter()
-                        .find(|n| n.node.metadata.name.as_ref().map_or(false, |name| name == &node.name));
-                    rsx!(NodeItem {
-                        name: node.name.clone(),
-                        node_type: node.node_type.clone(),
-                        status: node.status.clone(),
-                        kubernetes_version: node.kubernetes_version.clone(),
-                        os: node.os.clone(),
-                        architecture: node.architecture.clone(),
-                        ip: node.ip.clone(),
-                        pods: node.pods,
-                        cpu_usage: node.cpu_usage,
-                        memory_usage: node.memory_usage,
-                        storage_usage: node.storage_usage,
-                        conditions: original_node
-                            .and_then(|n| n.node.status.as_ref())
-                            .and_then(|status| status.conditions.as_ref())
-                            .map(|conditions| {
-                                conditions.iter().map(|c| crate::components::NodeCondition {
-                                    condition_type: c.type_.clone(),
-                                    status: c.status.clone(),
-                                }).collect()
-                            })
-                            .unwrap_or_default(),
-                    })
-                })}
+            if let Some(error) = metrics_error {
+                InlineError { error }
+            }
+
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_nodes.is_empty() {
+                EmptyState { message: "No nodes found" }
+            } else {
+                div { class: "nodes-grid",
+                    {filtered_nodes.iter().map(|node| {
+                        let binding = nodes();
+                        let original_node = binding.iter()
+                            .find(|n| n.node.metadata.name.as_ref().map_or(false, |name| name == &node.name));
+                        rsx!(NodeItem {
+                            name: node.name.clone(),
+                            node_type: node.node_type.clone(),
+                            status: node.status.clone(),
+                            kubernetes_version: node.kubernetes_version.clone(),
+                            os: node.os.clone(),
+                            architecture: node.architecture.clone(),
+                            ip: node.ip.clone(),
+                            pods: node.pods,
+                            cpu_usage: node.cpu_usage,
+                            memory_usage: node.memory_usage,
+                            storage_usage: node.storage_usage,
+                            conditions: original_node
+                                .and_then(|n| n.node.status.as_ref())
+                                .and_then(|status| status.conditions.as_ref())
+                                .map(|conditions| {
+                                    conditions.iter().map(|c| crate::components::NodeCondition {
+                                        condition_type: c.type_.clone(),
+                                        status: c.status.clone(),
+                                    }).collect()
+                                })
+                                .unwrap_or_default(),
+                        })
+                    })}
+                }
             }
         }
     }
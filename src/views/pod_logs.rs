@@ -0,0 +1,81 @@
+use dioxus::{logger::tracing, prelude::*};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{Api, Client};
+
+use crate::components::{InlineError, PodLogPanel};
+use crate::contexts::error::AppError;
+use crate::k8s::{container_names, prefers_previous_logs};
+
+const POD_LOGS_CSS: Asset = asset!("/assets/styling/pod_logs.css");
+
+/// Full page log viewer for a single pod container, linked from Insights
+#[component]
+pub fn PodLogs(namespace: String, name: String, container: String) -> Element {
+    let client_signal = use_context::<Signal<Option<Client>>>();
+    let navigate = use_navigator();
+    let pod = use_signal(|| None::<Pod>);
+    let mut error = use_signal(|| None::<AppError>);
+
+    use_effect({
+        let namespace = namespace.clone();
+        let name = name.clone();
+        let mut pod = pod.clone();
+        move || {
+            if let Some(client) = &*client_signal.read() {
+                let client = client.clone();
+                let namespace = namespace.clone();
+                let name = name.clone();
+                spawn(async move {
+                    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+                    match pods.get(&name).await {
+                        Ok(p) => pod.set(Some(p)),
+                        Err(e) => {
+                            tracing::error!("Failed to fetch pod {}/{}: {:?}", namespace, name, e);
+                            error.set(Some(AppError::from_kube(e, "get", "pods")));
+                        }
+                    }
+                });
+            }
+        }
+    });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: POD_LOGS_CSS }
+        div { class: "pod-logs-container",
+            div { class: "pods-header",
+                div { class: "header-left",
+                    h1 { "Logs: {name}" }
+                    span { class: "pod-logs-subtitle", "{namespace} / {container}" }
+                }
+                div { class: "header-actions",
+                    button {
+                        class: "btn btn-secondary",
+                        onclick: move |_| {
+                            navigate.go_back();
+                        },
+                        "Back"
+                    }
+                }
+            }
+
+            {error().map(|error| rsx! {
+                InlineError { error }
+            })}
+
+            // Wait for the pod so the container list and previous-log default are known
+            {pod().map(|pod| {
+                let previous = prefers_previous_logs(&pod, &container);
+                rsx! {
+                    PodLogPanel {
+                        key: "{namespace}-{name}-{container}",
+                        namespace: namespace.clone(),
+                        pod_name: name.clone(),
+                        containers: container_names(&pod),
+                        initial_container: container.clone(),
+                        initial_previous: previous,
+                    }
+                }
+            })}
+        }
+    }
+}
@@ -1,101 +1,126 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::core::v1::Pod;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{PodItem, NamespaceSelector, StatusSelector, SearchInput};
+use crate::components::{PodItem, NamespaceSelector, PortForwardList, StatusSelector, SearchInput, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, PODS};
 
 const PODS_CSS: Asset = asset!("/assets/styling/pods.css");
 
 #[derive(Clone)]
 struct PodFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     pods: Signal<Vec<Pod>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl PodFetcher {
-    fn fetch(&self, ns: String, status: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut pods = self.pods.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Fetching pods with status: {} in namespace: {}", status, ns);
+        tracing::info!("Watching pods in namespace: {}", ns);
+
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
         
-        spawn(async move {
-            let params = if status == "All" {
-                ListParams::default()
-            } else {
-                ListParams::default()
-                    .fields(&format!("status.phase={}", status))
-            };
-            
-            match if ns == "All" {
-                Api::all(client.clone()).list(&params).await
-            } else {
-                Api::namespaced(client.clone(), &ns).list(&params).await
-            } {
-                Ok(pod_list) => {
-                    let filtered_pods = if query.is_empty() {
-                        pod_list.items
-                    } else {
-                        pod_list.items
-                            .into_iter()
-                            .filter(|pod: &Pod| {
-                                pod.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false)
-                            })
-                            .collect()
-                    };
-                    pods.set(filtered_pods);
-                }
-                Err(e) => {
-                    println!("Error fetching pods: {:?}", e);
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<Pod>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        pods.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch pods: {}", e);
+                        error.set(Some(e));
+                    }
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn Pods() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    // Namespace-scoped users can't watch across the cluster, so watch the selected namespace instead
+    let list_all = use_memo(move || access_context.can("list", PODS, None));
     let navigate = use_navigator();
 
     let mut selected_status = use_signal(|| "All".to_string());
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let pods = use_signal(|| Vec::<Pod>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     // Always call use_effect but handle conditional logic inside
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = PodFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     pods: pods.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let status = selected_status();
-                let query = search_query();
-                fetcher.fetch(ns, status, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Status and search are applied here so typing doesn't restart the watch
+    let filtered_pods = use_memo(move || {
+        let status = selected_status();
+        let query = search_query().to_lowercase();
+        pods.read()
+            .iter()
+            .filter(|pod| {
+                status == "All" || pod.status.as_ref()
+                    .and_then(|s| s.phase.as_deref())
+                    .map(|phase| phase == status)
+                    .unwrap_or(false)
+            })
+            .filter(|pod| {
+                query.is_empty() || pod.metadata.name.as_ref()
+                    .map(|name| name.to_lowercase().contains(&query))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = PodFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     pods: pods.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let status = selected_status();
-                let query = search_query();
-                fetcher.fetch(ns, status, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All" || selected_status() != "All";
+
+    let can_create = access_context.can("create", PODS, Some(&selected_namespace()));
+
     rsx! {
         document::Link { rel: "stylesheet", href: PODS_CSS }
         div { class: "pods-container",
@@ -115,12 +140,14 @@ pub fn Pods() -> Element {
                             selected_status: selected_status(),
                             on_change: move |status| selected_status.set(status)
                         }
-                        span { class: "pod-count", "{pods().len()} pods" }
+                        span { class: "pod-count", "{filtered_pods().len()} pods" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-primary",
+                        disabled: !can_create,
+                        title: if can_create { "" } else { "You're not allowed to create pods in this namespace" },
                         onclick: move |_| {
                             navigate.push("/pods/create");
                         },
@@ -128,18 +155,29 @@ pub fn Pods() -> Element {
                     }
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "pods-grid",
-                {pods().iter().map(|pod| {
-                    rsx! {
-                        PodItem { pod: pod.clone() }
-                    }
-                })}
+            PortForwardList {}
+
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_pods.read().is_empty() {
+                EmptyState {
+                    message: "No pods found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "pods-grid",
+                    {filtered_pods().iter().map(|pod| {
+                        rsx! {
+                            PodItem { pod: pod.clone() }
+                        }
+                    })}
+                }
             }
         }
     }
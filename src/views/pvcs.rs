@@ -1,96 +1,121 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, SearchInput, PvcItem};
+use crate::components::{NamespaceSelector, SearchInput, PvcItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, PVCS};
 
 const PVCS_CSS: Asset = asset!("/assets/styling/pvcs.css");
 
 #[derive(Clone)]
 struct PvcFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     pvcs: Signal<Vec<PersistentVolumeClaim>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl PvcFetcher {
-    fn fetch(&self, ns: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut pvcs = self.pvcs.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting PVCs fetch...");
+        tracing::info!("Starting PVCs watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<PersistentVolumeClaim>::all(client.clone())
-            } else {
-                Api::<PersistentVolumeClaim>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(pvc_list) => {
-                    let filtered_pvcs = if query.is_empty() {
-                        pvc_list.items
-                    } else {
-                        pvc_list.items
-                            .into_iter()
-                            .filter(|pvc: &PersistentVolumeClaim| {
-                                pvc.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false) ||
-                                pvc.spec.as_ref()
-                                    .and_then(|s| s.storage_class_name.as_ref())
-                                    .map(|sc| sc.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false)
-                            })
-                            .collect()
-                    };
-                    
-                    pvcs.set(filtered_pvcs);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch PVCs: {:?}", e);
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<PersistentVolumeClaim>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        pvcs.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch PVCs: {}", e);
+                        error.set(Some(e));
+                    }
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn Pvcs() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", PVCS, None));
 
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let pvcs = use_signal(|| Vec::<PersistentVolumeClaim>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = PvcFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     pvcs: pvcs.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Search is applied here so typing doesn't restart the watch
+    let filtered_pvcs = use_memo(move || {
+        let items = pvcs();
+        let query = search_query();
+        if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|pvc: &PersistentVolumeClaim| {
+                    pvc.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false) ||
+                    pvc.spec.as_ref()
+                        .and_then(|s| s.storage_class_name.as_ref())
+                        .map(|sc| sc.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false)
+                })
+                .collect()
+        }
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = PvcFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     pvcs: pvcs.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All";
+
     rsx! {
         document::Link { rel: "stylesheet", href: PVCS_CSS }
         div { class: "pvcs-container",
@@ -106,27 +131,36 @@ pub fn Pvcs() -> Element {
                             selected_namespace: selected_namespace(),
                             on_change: move |ns| selected_namespace.set(ns)
                         }
-                        span { class: "pvc-count", "{pvcs().len()} PVCs" }
+                        span { class: "pvc-count", "{filtered_pvcs().len()} PVCs" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "pvcs-grid",
-                {pvcs().iter().map(|pvc| {
-                    rsx! {
-                        PvcItem {
-                            key: "{pvc.metadata.name.clone().unwrap_or_default()}",
-                            pvc: pvc.clone()
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_pvcs.read().is_empty() {
+                EmptyState {
+                    message: "No persistent volume claims found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "pvcs-grid",
+                    {filtered_pvcs().iter().map(|pvc| {
+                        rsx! {
+                            PvcItem {
+                                key: "{pvc.metadata.name.clone().unwrap_or_default()}",
+                                pvc: pvc.clone()
+                            }
                         }
-                    }
-                })}
+                    })}
+                }
             }
         }
     }
@@ -1,106 +1,131 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::core::v1::Secret;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, SearchInput, SecretItem};
+use crate::components::{NamespaceSelector, SearchInput, SecretItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, SECRETS};
 
 const SECRETS_CSS: Asset = asset!("/assets/styling/secrets.css");
 
 #[derive(Clone)]
 struct SecretFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     secrets: Signal<Vec<Secret>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl SecretFetcher {
-    fn fetch(&self, ns: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut secrets = self.secrets.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting secrets fetch...");
+        tracing::info!("Starting secrets watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<Secret>::all(client.clone())
-            } else {
-                Api::<Secret>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(secret_list) => {
-                    let filtered_secrets = if query.is_empty() {
-                        secret_list.items
-                    } else {
-                        secret_list.items
-                            .into_iter()
-                            .filter(|s: &Secret| {
-                                let name_match = s.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false);
-                                
-                                let namespace_match = s.metadata.namespace.as_ref()
-                                    .map(|ns| ns.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false);
-                                
-                                let type_match = s.type_.as_ref()
-                                    .map(|t| t.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false);
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
 
-                                let key_match = s.data.as_ref()
-                                    .map(|data| data.keys().any(|k| k.to_lowercase().contains(&query.to_lowercase())))
-                                    .unwrap_or(false);
-                                
-                                name_match || namespace_match || type_match || key_match
-                            })
-                            .collect()
-                    };
-                    
-                    secrets.set(filtered_secrets);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch secrets: {:?}", e);
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<Secret>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        secrets.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch secrets: {}", e);
+                        error.set(Some(e));
+                    }
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn Secrets() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", SECRETS, None));
 
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let secrets = use_signal(|| Vec::<Secret>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = SecretFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     secrets: secrets.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Search is applied here so typing doesn't restart the watch
+    let filtered_secrets = use_memo(move || {
+        let items = secrets();
+        let query = search_query();
+        if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|s: &Secret| {
+                    let name_match = s.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false);
+                
+                    let namespace_match = s.metadata.namespace.as_ref()
+                        .map(|ns| ns.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false);
+                
+                    let type_match = s.type_.as_ref()
+                        .map(|t| t.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false);
+
+                    let key_match = s.data.as_ref()
+                        .map(|data| data.keys().any(|k| k.to_lowercase().contains(&query.to_lowercase())))
+                        .unwrap_or(false);
+                
+                    name_match || namespace_match || type_match || key_match
+                })
+                .collect()
+        }
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = SecretFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     secrets: secrets.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let query = search_query();
-                fetcher.fetch(ns, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All";
+
     rsx! {
         document::Link { rel: "stylesheet", href: SECRETS_CSS }
         div { class: "secrets-container",
@@ -116,31 +141,40 @@ pub fn Secrets() -> Element {
                             selected_namespace: selected_namespace(),
                             on_change: move |ns| selected_namespace.set(ns)
                         }
-                        span { class: "secret-count", "{secrets().len()} Secrets" }
+                        span { class: "secret-count", "{filtered_secrets().len()} Secrets" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "secrets-grid",
-                {secrets.read().iter().map(|s| {
-                    let key = format!("{}-{}", 
-                        s.metadata.namespace.clone().unwrap_or_default(),
-                        s.metadata.name.clone().unwrap_or_default()
-                    );
-                    rsx! {
-                        SecretItem {
-                            key: "{key}",
-                            secret: s.clone()
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_secrets.read().is_empty() {
+                EmptyState {
+                    message: "No secrets found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "secrets-grid",
+                    {filtered_secrets.read().iter().map(|s| {
+                        let key = format!("{}-{}", 
+                            s.metadata.namespace.clone().unwrap_or_default(),
+                            s.metadata.name.clone().unwrap_or_default()
+                        );
+                        rsx! {
+                            SecretItem {
+                                key: "{key}",
+                                secret: s.clone()
+                            }
                         }
-                    }
-                })}
+                    })}
+                }
             }
         }
     }
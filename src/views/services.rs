@@ -1,107 +1,132 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::core::v1::Service;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, StatusSelector, SearchInput, ServiceItem};
+use crate::components::{NamespaceSelector, PortForwardList, StatusSelector, SearchInput, ServiceItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, SERVICES};
 
 const SERVICES_CSS: Asset = asset!("/assets/styling/services.css");
 
 #[derive(Clone)]
 struct ServiceFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     services: Signal<Vec<Service>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl ServiceFetcher {
-    fn fetch(&self, ns: String, service_type: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut services = self.services.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting services fetch...");
+        tracing::info!("Starting services watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<Service>::all(client.clone())
-            } else {
-                Api::<Service>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(service_list) => {
-                    let mut filtered_services = if query.is_empty() {
-                        service_list.items
-                    } else {
-                        service_list.items
-                            .into_iter()
-                            .filter(|svc: &Service| {
-                                svc.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false)
-                            })
-                            .collect::<Vec<_>>()
-                    };
-
-                    // Filter by type if not "All"
-                    if service_type != "All" {
-                        filtered_services = filtered_services.into_iter()
-                            .filter(|svc| {
-                                svc.spec.as_ref()
-                                    .and_then(|s| s.type_.as_ref())
-                                    .map(|t| t == &service_type)
-                                    .unwrap_or(false)
-                            })
-                            .collect();
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<Service>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        services.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch services: {}", e);
+                        error.set(Some(e));
                     }
-                    
-                    services.set(filtered_services);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch services: {:?}", e);
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn Services() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", SERVICES, None));
 
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut selected_type = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let services = use_signal(|| Vec::<Service>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = ServiceFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     services: services.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let type_ = selected_type();
-                let query = search_query();
-                fetcher.fetch(ns, type_, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Type and search are applied here so typing doesn't restart the watch
+    let filtered_services = use_memo(move || {
+        let items = services();
+        let query = search_query();
+        let service_type = selected_type();
+        let mut filtered_services = if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|svc: &Service| {
+                    svc.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Filter by type if not "All"
+        if service_type != "All" {
+            filtered_services = filtered_services.into_iter()
+                .filter(|svc| {
+                    svc.spec.as_ref()
+                        .and_then(|s| s.type_.as_ref())
+                        .map(|t| t == &service_type)
+                        .unwrap_or(false)
+                })
+                .collect();
+        }
+        filtered_services
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = ServiceFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     services: services.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let type_ = selected_type();
-                let query = search_query();
-                fetcher.fetch(ns, type_, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All" || selected_type() != "All";
+
     rsx! {
         document::Link { rel: "stylesheet", href: SERVICES_CSS }
         div { class: "services-container",
@@ -122,27 +147,38 @@ pub fn Services() -> Element {
                             on_change: move |t| selected_type.set(t),
                             custom_statuses: Some(vec!["All", "ClusterIP", "NodePort", "LoadBalancer", "ExternalName"])
                         }
-                        span { class: "service-count", "{services().len()} services" }
+                        span { class: "service-count", "{filtered_services().len()} services" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "services-grid",
-                {services().iter().map(|service| {
-                    rsx! {
-                        ServiceItem {
-                            key: "{service.metadata.name.clone().unwrap_or_default()}",
-                            service: service.clone()
+            PortForwardList {}
+
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_services.read().is_empty() {
+                EmptyState {
+                    message: "No services found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "services-grid",
+                    {filtered_services().iter().map(|service| {
+                        rsx! {
+                            ServiceItem {
+                                key: "{service.metadata.name.clone().unwrap_or_default()}",
+                                service: service.clone()
+                            }
                         }
-                    }
-                })}
+                    })}
+                }
             }
         }
     }
@@ -1,118 +1,145 @@
 use dioxus::{logger::tracing, prelude::*};
 use k8s_openapi::api::apps::v1::StatefulSet;
-use kube::{api::ListParams, Api, Client};
+use futures::StreamExt;
 
-use crate::components::{NamespaceSelector, StatusSelector, SearchInput, StatefulSetItem};
+use crate::components::{NamespaceSelector, StatusSelector, SearchInput, StatefulSetItem, EmptyState, InlineError};
+use crate::contexts::{error::AppError, AccessContext};
+use crate::k8s::{ClusterCache, STATEFULSETS};
 
 const STATEFULSETS_CSS: Asset = asset!("/assets/styling/statefulsets.css");
 
 #[derive(Clone)]
 struct StatefulSetFetcher {
-    client: Client,
+    cache: ClusterCache,
+    cluster_wide: bool,
     statefulsets: Signal<Vec<StatefulSet>>,
+    error: Signal<Option<AppError>>,
+    watch_task: Signal<Option<Task>>,
 }
 
 impl StatefulSetFetcher {
-    fn fetch(&self, ns: String, status: String, query: String) {
-        let client = self.client.clone();
+    fn watch(&self, ns: String) {
+        let cache = self.cache.clone();
+        let cluster_wide = self.cluster_wide;
         let mut statefulsets = self.statefulsets.clone();
+        let mut error = self.error;
+        let mut watch_task = self.watch_task.clone();
 
-        tracing::info!("Starting statefulset fetch...");
+        tracing::info!("Starting statefulset watch...");
         
-        spawn(async move {
-            let api = if ns == "All" {
-                Api::<StatefulSet>::all(client.clone())
-            } else {
-                Api::<StatefulSet>::namespaced(client.clone(), &ns)
-            };
-            
-            match api.list(&ListParams::default()).await {
-                Ok(statefulset_list) => {
-                    let mut filtered_statefulsets = if query.is_empty() {
-                        statefulset_list.items
-                    } else {
-                        statefulset_list.items
-                            .into_iter()
-                            .filter(|sts: &StatefulSet| {
-                                sts.metadata.name.as_ref()
-                                    .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
-                                    .unwrap_or(false)
-                            })
-                            .collect::<Vec<_>>()
-                    };
-
-                    // Filter by status if not "All"
-                    if status != "All" {
-                        filtered_statefulsets = filtered_statefulsets.into_iter()
-                            .filter(|sts| {
-                                let ready = sts.status.as_ref().map_or(0, |s| s.ready_replicas.unwrap_or(0));
-                                let desired = sts.spec.as_ref().map_or(0, |s| s.replicas.unwrap_or(0));
-                                let current = sts.status.as_ref().map_or(0, |s| s.current_replicas.unwrap_or(0));
-                                let updated = sts.status.as_ref().map_or(0, |s| s.updated_replicas.unwrap_or(0));
-
-                                match status.as_str() {
-                                    "Available" => ready == desired && current == desired && updated == desired,
-                                    "Progressing" => updated < desired,
-                                    "Rolling Update" => updated < desired && ready < desired,
-                                    "Degraded" => ready < desired,
-                                    "Scaled Down" => desired == 0,
-                                    _ => false
-                                }
-                            })
-                            .collect();
+        if let Some(task) = watch_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut snapshots = Box::pin(cache.scoped_snapshots::<StatefulSet>(&ns, cluster_wide));
+            while let Some(snapshot) = snapshots.next().await {
+                match snapshot {
+                    Ok(items) => {
+                        statefulsets.set(items);
+                        error.set(None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to watch statefulsets: {}", e);
+                        error.set(Some(e));
                     }
-                    
-                    statefulsets.set(filtered_statefulsets);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch statefulsets: {:?}", e);
                 }
             }
         });
+        watch_task.set(Some(task));
     }
 }
 
 #[component]
 pub fn StatefulSets() -> Element {
-    let client_signal = use_context::<Signal<Option<Client>>>();
+    let cache_signal = use_context::<Signal<Option<ClusterCache>>>();
+    let access_context = use_context::<AccessContext>();
+    let list_all = use_memo(move || access_context.can("list", STATEFULSETS, None));
     let navigate = use_navigator();
 
     let mut selected_status = use_signal(|| "All".to_string());
     let mut selected_namespace = use_signal(|| "All".to_string());
     let mut search_query = use_signal(String::new);
     let statefulsets = use_signal(|| Vec::<StatefulSet>::new());
+    let error = use_signal(|| None::<AppError>);
+    let watch_task = use_signal(|| None::<Task>);
 
     // Always call use_effect but handle conditional logic inside
     use_effect({
         move || {
-            if let Some(client) = &*client_signal.read() {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = StatefulSetFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     statefulsets: statefulsets.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let status = selected_status();
-                let query = search_query();
-                fetcher.fetch(ns, status, query);
+                fetcher.watch(selected_namespace());
             }
         }
     });
 
+    // Status and search are applied here so typing doesn't restart the watch
+    let filtered_statefulsets = use_memo(move || {
+        let items = statefulsets();
+        let query = search_query();
+        let status = selected_status();
+        let mut filtered_statefulsets = if query.is_empty() {
+            items
+        } else {
+            items
+                .into_iter()
+                .filter(|sts: &StatefulSet| {
+                    sts.metadata.name.as_ref()
+                        .map(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                        .unwrap_or(false)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Filter by status if not "All"
+        if status != "All" {
+            filtered_statefulsets = filtered_statefulsets.into_iter()
+                .filter(|sts| {
+                    let ready = sts.status.as_ref().map_or(0, |s| s.ready_replicas.unwrap_or(0));
+                    let desired = sts.spec.as_ref().map_or(0, |s| s.replicas.unwrap_or(0));
+                    let current = sts.status.as_ref().map_or(0, |s| s.current_replicas.unwrap_or(0));
+                    let updated = sts.status.as_ref().map_or(0, |s| s.updated_replicas.unwrap_or(0));
+
+                    match status.as_str() {
+                        "Available" => ready == desired && current == desired && updated == desired,
+                        "Progressing" => updated < desired,
+                        "Rolling Update" => updated < desired && ready < desired,
+                        "Degraded" => ready < desired,
+                        "Scaled Down" => desired == 0,
+                        _ => false
+                    }
+                })
+                .collect();
+        }
+        filtered_statefulsets
+    });
+
     let refresh = {
-        move |_| {
-            if let Some(client) = &*client_signal.read() {
+        move || {
+            if let Some(cache) = &*cache_signal.read() {
                 let fetcher = StatefulSetFetcher {
-                    client: client.clone(),
+                    cache: cache.clone(),
+                    cluster_wide: list_all(),
                     statefulsets: statefulsets.clone(),
+                    watch_task,
+                    error,
                 };
-                let ns = selected_namespace();
-                let status = selected_status();
-                let query = search_query();
-                fetcher.fetch(ns, status, query);
+                fetcher.watch(selected_namespace());
             }
         }
     };
 
+    let is_filtered = !search_query().is_empty() || selected_namespace() != "All" || selected_status() != "All";
+
+    let can_create = access_context.can("create", STATEFULSETS, Some(&selected_namespace()));
+
     rsx! {
         document::Link { rel: "stylesheet", href: STATEFULSETS_CSS }
         div { class: "statefulsets-container",
@@ -133,12 +160,14 @@ pub fn StatefulSets() -> Element {
                             on_change: move |status| selected_status.set(status),
                             custom_statuses: Some(vec!["All", "Available", "Progressing", "Rolling Update", "Degraded", "Scaled Down"])
                         }
-                        span { class: "statefulset-count", "{statefulsets().len()} statefulsets" }
+                        span { class: "statefulset-count", "{filtered_statefulsets().len()} statefulsets" }
                     }
                 }
                 div { class: "header-actions",
                     button { 
                         class: "btn btn-primary",
+                        disabled: !can_create,
+                        title: if can_create { "" } else { "You're not allowed to create statefulsets in this namespace" },
                         onclick: move |_| {
                             navigate.push("/statefulsets/create");
                         },
@@ -146,18 +175,27 @@ pub fn StatefulSets() -> Element {
                     }
                     button { 
                         class: "btn btn-secondary",
-                        onclick: refresh,
+                        onclick: move |_| refresh(),
                         "Refresh" 
                     }
                 }
             }
 
-            div { class: "statefulsets-grid",
-                {statefulsets().iter().map(|statefulset| {
-                    rsx! {
-                        StatefulSetItem { statefulset: statefulset.clone() }
-                    }
-                })}
+            if let Some(error) = error() {
+                InlineError { error, on_retry: move |_| refresh() }
+            } else if filtered_statefulsets.read().is_empty() {
+                EmptyState {
+                    message: "No statefulsets found",
+                    hint: is_filtered.then(|| "Try clearing the search or filters".to_string())
+                }
+            } else {
+                div { class: "statefulsets-grid",
+                    {filtered_statefulsets().iter().map(|statefulset| {
+                        rsx! {
+                            StatefulSetItem { statefulset: statefulset.clone() }
+                        }
+                    })}
+                }
             }
         }
     }